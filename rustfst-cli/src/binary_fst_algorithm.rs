@@ -0,0 +1,120 @@
+use anyhow::{format_err, Result};
+use clap::ArgMatches;
+use colored::Colorize;
+use log::{debug, info};
+
+use rustfst::prelude::*;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::unary_fst_algorithm::{timed_run, BenchResults};
+use crate::utils::{
+    parse_vector_fst, read_fst_types, read_input, write_fst, CliWeight, STD_STREAM,
+};
+
+pub trait BinaryFstAlgorithm {
+    fn get_path_in_1(&self) -> &str;
+    fn get_path_in_2(&self) -> &str;
+    fn get_path_out(&self) -> &str;
+    fn get_fst_type(&self) -> &str;
+    fn get_algorithm_name(&self) -> String;
+
+    fn read<W: CliWeight>(
+        &self,
+        data_1: &[u8],
+        data_2: &[u8],
+    ) -> Result<(VectorFst<W>, VectorFst<W>)> {
+        Ok((parse_vector_fst(data_1)?, parse_vector_fst(data_2)?))
+    }
+
+    fn run_algorithm<W: CliWeight>(
+        &self,
+        fst_1: VectorFst<W>,
+        fst_2: VectorFst<W>,
+    ) -> Result<VectorFst<W>>;
+
+    fn write<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<()> {
+        write_fst(fst, self.get_fst_type(), self.get_path_out())
+    }
+
+    fn run_cli_or_bench(&self, m: &ArgMatches) -> Result<()> {
+        if self.get_path_in_1() == STD_STREAM && self.get_path_in_2() == STD_STREAM {
+            return Err(format_err!(
+                "Only one of the input FSTs can be read from stdin"
+            ))
+            .categorize(ErrorCategory::Io);
+        }
+        if m.is_present("bench") && self.get_path_out() == STD_STREAM {
+            return Err(format_err!(
+                "The output FST can't be written to stdout when benchmarking"
+            ))
+            .categorize(ErrorCategory::Io);
+        }
+        let data_1 = read_input(self.get_path_in_1())?;
+        let data_2 = read_input(self.get_path_in_2())?;
+        let (_, arc_type_1) = read_fst_types(&data_1)?;
+        let (_, arc_type_2) = read_fst_types(&data_2)?;
+        if arc_type_1 != arc_type_2 {
+            return Err(format_err!(
+                "Input FSTs must have the same arc type : {} != {}",
+                arc_type_1,
+                arc_type_2
+            ))
+            .categorize(ErrorCategory::Algorithm);
+        }
+        dispatch_weight_type!(arc_type_1.as_str(), W => {
+            if m.is_present("bench") {
+                // Run bench
+                self.run_bench::<W>(
+                    &data_1,
+                    &data_2,
+                    m.value_of("n_warm_ups").unwrap().parse().unwrap(),
+                    m.value_of("n_iters").unwrap().parse().unwrap(),
+                    m.value_of("export-markdown"),
+                )
+            } else {
+                // Run cli
+                self.run_cli::<W>(&data_1, &data_2)
+            }
+        })
+    }
+
+    fn run_cli<W: CliWeight>(&self, data_1: &[u8], data_2: &[u8]) -> Result<()> {
+        info!("Running {} algorithm", self.get_algorithm_name().blue());
+        let (duration_parsing, duration_algo, duration_serialization) = timed_run(
+            || self.read::<W>(data_1, data_2),
+            |(fst_1, fst_2)| self.run_algorithm(fst_1, fst_2),
+            |fst| self.write(fst),
+        )?;
+        debug!("Duration parsing : {:?}", &duration_parsing);
+        debug!("Duration running algorithm : {:?}", &duration_algo);
+        debug!("Duration serialization : {:?}", &duration_serialization);
+
+        Ok(())
+    }
+
+    fn run_bench<W: CliWeight>(
+        &self,
+        data_1: &[u8],
+        data_2: &[u8],
+        n_warm_ups: usize,
+        n_iters: usize,
+        path_markdown_report: Option<&str>,
+    ) -> Result<()> {
+        eprintln!(
+            "Running benchmark for algorithm {}",
+            self.get_algorithm_name().blue()
+        );
+        let mut results = BenchResults::new(n_warm_ups, n_iters);
+
+        for i in 0..(n_warm_ups + n_iters) {
+            let (duration_parsing, duration_algo, duration_serialization) = timed_run(
+                || self.read::<W>(data_1, data_2),
+                |(fst_1, fst_2)| self.run_algorithm(fst_1, fst_2),
+                |fst| self.write(fst),
+            )?;
+            results.add_run(i, duration_parsing, duration_algo, duration_serialization);
+        }
+
+        results.report(path_markdown_report)
+    }
+}
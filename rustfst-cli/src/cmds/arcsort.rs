@@ -3,11 +3,13 @@ use anyhow::{bail, Result};
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 
 pub struct ArcsortAlgorithm {
     path_in: String,
     sort_type: String,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for ArcsortAlgorithm {
@@ -19,14 +21,15 @@ impl UnaryFstAlgorithm for ArcsortAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "arcsort".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         let cmp = match self.sort_type.as_str() {
             "ilabel" => ilabel_compare,
             "olabel" => olabel_compare,
@@ -38,11 +41,12 @@ impl UnaryFstAlgorithm for ArcsortAlgorithm {
 }
 
 impl ArcsortAlgorithm {
-    pub fn new(path_in: &str, sort_type: &str, path_out: &str) -> Self {
+    pub fn new(path_in: &str, sort_type: &str, path_out: &str, fst_type: &str) -> Self {
         Self {
             path_in: path_in.to_string(),
             sort_type: sort_type.to_string(),
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::Instant;
+
+use anyhow::{bail, format_err, Context, Result};
+use colored::Colorize;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::unary_fst_algorithm::{duration_to_seconds, standard_deviation};
+use crate::utils::{create_output, read_input_string, STD_STREAM};
+
+/// Placeholder replaced by the path of the input FST in the arguments of a benchmark.
+static INPUT_PLACEHOLDER: &str = "{input}";
+/// Placeholder replaced by the path of a temporary output file in the arguments of a benchmark.
+static OUTPUT_PLACEHOLDER: &str = "{output}";
+
+fn default_n_warm_ups() -> usize {
+    3
+}
+
+fn default_n_iters() -> usize {
+    10
+}
+
+/// Suite of benchmarks in JSON format. Every benchmark is run on every input for every set of
+/// parameters.
+#[derive(Deserialize, Debug)]
+struct BenchSuite {
+    #[serde(default = "default_n_warm_ups")]
+    n_warm_ups: usize,
+    #[serde(default = "default_n_iters")]
+    n_iters: usize,
+    inputs: Vec<String>,
+    benchmarks: Vec<BenchDefinition>,
+}
+
+/// Command run by a benchmark. `program` defaults to the running `rustfst-cli` which allows
+/// comparing its subcommands against the OpenFST binaries.
+#[derive(Deserialize, Debug)]
+struct BenchDefinition {
+    name: String,
+    #[serde(default)]
+    program: Option<String>,
+    args: Vec<String>,
+    /// Sets of arguments appended to `args`. Each one is benchmarked separately.
+    #[serde(default)]
+    parameters: Vec<Vec<String>>,
+}
+
+/// Statistics of the wall-clock times (in seconds) and of the peak memory of a benchmark
+/// run on one input with one set of parameters.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct BenchRecord {
+    benchmark: String,
+    program: String,
+    parameters: String,
+    input: String,
+    n_iters: usize,
+    mean: f64,
+    stddev: f64,
+    min: f64,
+    max: f64,
+    p50: f64,
+    p90: f64,
+    p99: f64,
+    peak_memory_bytes: Option<u64>,
+}
+
+impl BenchRecord {
+    fn key(&self) -> String {
+        format!(
+            "{} {} [{}] {}",
+            self.benchmark, self.program, self.parameters, self.input
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct BenchReport {
+    n_warm_ups: usize,
+    n_iters: usize,
+    results: Vec<BenchRecord>,
+}
+
+impl BenchReport {
+    fn read(path: &str) -> Result<Self> {
+        let text = read_input_string(path)?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Invalid benchmark results {:?}", path))
+            .categorize(ErrorCategory::Parse)
+    }
+
+    fn write_json<W: Write>(&self, output: &mut W) -> Result<()> {
+        serde_json::to_writer_pretty(&mut *output, self)?;
+        writeln!(output)?;
+        Ok(())
+    }
+
+    fn write_csv<W: Write>(&self, output: &mut W) -> Result<()> {
+        writeln!(
+            output,
+            "benchmark,program,parameters,input,n_iters,mean,stddev,min,max,p50,p90,p99,peak_memory_bytes"
+        )?;
+        for r in &self.results {
+            writeln!(
+                output,
+                "{},{},{},{},{},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{:.6},{}",
+                csv_field(&r.benchmark),
+                csv_field(&r.program),
+                csv_field(&r.parameters),
+                csv_field(&r.input),
+                r.n_iters,
+                r.mean,
+                r.stddev,
+                r.min,
+                r.max,
+                r.p50,
+                r.p90,
+                r.p99,
+                r.peak_memory_bytes
+                    .map_or_else(String::new, |m| m.to_string())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Nearest-rank percentile of sorted samples.
+fn percentile(sorted_samples: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * sorted_samples.len() as f64).ceil() as usize;
+    sorted_samples[rank.max(1) - 1]
+}
+
+/// Runs a suite of benchmarks, each run being a separate process, and writes the statistics
+/// in JSON or CSV format.
+pub struct BenchSuiteCommand {
+    path_suite: String,
+    path_out: String,
+    format: String,
+}
+
+impl BenchSuiteCommand {
+    pub fn new(path_suite: &str, path_out: &str, format: &str) -> Self {
+        Self {
+            path_suite: path_suite.to_string(),
+            path_out: path_out.to_string(),
+            format: format.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let text = read_input_string(&self.path_suite)?;
+        let suite: BenchSuite = serde_json::from_str(&text)
+            .with_context(|| format!("Invalid benchmark suite {:?}", self.path_suite))
+            .categorize(ErrorCategory::Parse)?;
+        if suite.n_iters == 0 {
+            return Err(format_err!("n_iters must be positive")).categorize(ErrorCategory::Parse);
+        }
+
+        let current_exe = env::current_exe()?.to_string_lossy().into_owned();
+        let path_output_fst =
+            env::temp_dir().join(format!("rustfst-bench-{}.out", std::process::id()));
+
+        let mut results = vec![];
+        for benchmark in suite.benchmarks.iter() {
+            let program = benchmark.program.as_deref().unwrap_or(&current_exe);
+            let no_parameters = [vec![]];
+            let parameters_sets = if benchmark.parameters.is_empty() {
+                &no_parameters[..]
+            } else {
+                &benchmark.parameters[..]
+            };
+            for parameters in parameters_sets {
+                for input in suite.inputs.iter() {
+                    eprintln!(
+                        "Running {} on {} {}",
+                        benchmark.name.blue(),
+                        input,
+                        parameters.join(" ").yellow()
+                    );
+                    let mut args: Vec<String> = benchmark
+                        .args
+                        .iter()
+                        .map(|arg| {
+                            arg.replace(INPUT_PLACEHOLDER, input)
+                                .replace(OUTPUT_PLACEHOLDER, &path_output_fst.to_string_lossy())
+                        })
+                        .collect();
+                    args.extend(parameters.iter().cloned());
+                    let record = bench_command(
+                        program,
+                        &args,
+                        suite.n_warm_ups,
+                        suite.n_iters,
+                        &path_output_fst,
+                    )
+                    .with_context(|| format!("Benchmark {} failed on {}", benchmark.name, input))?;
+                    results.push(BenchRecord {
+                        benchmark: benchmark.name.clone(),
+                        program: benchmark
+                            .program
+                            .clone()
+                            .unwrap_or_else(|| "rustfst".to_string()),
+                        parameters: parameters.join(" "),
+                        input: input.clone(),
+                        ..record
+                    });
+                }
+            }
+        }
+
+        let report = BenchReport {
+            n_warm_ups: suite.n_warm_ups,
+            n_iters: suite.n_iters,
+            results,
+        };
+        let mut output = create_output(&self.path_out)?;
+        match self.format.as_str() {
+            "json" => report.write_json(&mut output)?,
+            "csv" => report.write_csv(&mut output)?,
+            _ => bail!("Unknown format : {}", self.format),
+        };
+        output.flush()?;
+        Ok(())
+    }
+}
+
+/// Runs a command `n_warm_ups + n_iters` times and computes the statistics of the runs
+/// following the warmups. The benchmark, parameters and input are left empty.
+fn bench_command(
+    program: &str,
+    args: &[String],
+    n_warm_ups: usize,
+    n_iters: usize,
+    path_output_fst: &Path,
+) -> Result<BenchRecord> {
+    let mut times = vec![];
+    let mut peak_memory_bytes = None;
+    for i in 0..(n_warm_ups + n_iters) {
+        let start = Instant::now();
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Can't run {}", program))?;
+        let (status, stderr, peak_memory) = wait_with_peak_memory(child)?;
+        let duration = start.elapsed();
+        if !status.success() {
+            bail!("{} exited with {} : {}", program, status, stderr.trim());
+        }
+        if i >= n_warm_ups {
+            times.push(duration_to_seconds(&duration));
+            peak_memory_bytes = peak_memory.max(peak_memory_bytes);
+        }
+    }
+    // The output is only needed for the duration of the run.
+    fs::remove_file(path_output_fst).ok();
+
+    let mut sorted_times = times.clone();
+    sorted_times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(BenchRecord {
+        benchmark: String::new(),
+        program: String::new(),
+        parameters: String::new(),
+        input: String::new(),
+        n_iters,
+        mean: times.iter().sum::<f64>() / n_iters as f64,
+        stddev: standard_deviation(&times),
+        min: sorted_times[0],
+        max: sorted_times[n_iters - 1],
+        p50: percentile(&sorted_times, 50.0),
+        p90: percentile(&sorted_times, 90.0),
+        p99: percentile(&sorted_times, 99.0),
+        peak_memory_bytes,
+    })
+}
+
+/// Waits for the end of a process and returns its exit status, its stderr and its peak
+/// resident memory. On Linux, the peak memory can't be lower than the resident memory of
+/// the benchmark process itself, which the child has before calling `exec`.
+#[cfg(unix)]
+fn wait_with_peak_memory(mut child: Child) -> Result<(ExitStatus, String, Option<u64>)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    let mut stderr = String::new();
+    if let Some(mut child_stderr) = child.stderr.take() {
+        child_stderr.read_to_string(&mut stderr)?;
+    }
+    let mut status = 0;
+    // Safe as `rusage` is a plain C struct and `wait4` only writes into it.
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    let pid = child.id() as libc::pid_t;
+    if unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } < 0 {
+        return Err(std::io::Error::last_os_error()).context("Can't wait for the benchmark");
+    }
+    // `ru_maxrss` is in bytes on macOS and in kilobytes elsewhere.
+    let max_rss = usage.ru_maxrss as u64;
+    let peak_memory = if cfg!(target_os = "macos") {
+        max_rss
+    } else {
+        max_rss * 1024
+    };
+    Ok((ExitStatus::from_raw(status), stderr, Some(peak_memory)))
+}
+
+#[cfg(not(unix))]
+fn wait_with_peak_memory(child: Child) -> Result<(ExitStatus, String, Option<u64>)> {
+    let output = child.wait_with_output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    Ok((output.status, stderr, None))
+}
+
+/// Compares two benchmark results in JSON format and flags the benchmarks whose mean time or
+/// peak memory increased by more than `threshold` percents.
+pub struct BenchCompareCommand {
+    path_baseline: String,
+    path_current: String,
+    threshold: f64,
+    fail_on_regression: bool,
+}
+
+impl BenchCompareCommand {
+    pub fn new(
+        path_baseline: &str,
+        path_current: &str,
+        threshold: f64,
+        fail_on_regression: bool,
+    ) -> Self {
+        Self {
+            path_baseline: path_baseline.to_string(),
+            path_current: path_current.to_string(),
+            threshold,
+            fail_on_regression,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let baseline = BenchReport::read(&self.path_baseline)?;
+        let current = BenchReport::read(&self.path_current)?;
+        let baseline_records: HashMap<_, _> =
+            baseline.results.iter().map(|r| (r.key(), r)).collect();
+
+        let mut output = create_output(STD_STREAM)?;
+        let mut n_regressions = 0;
+        writeln!(
+            output,
+            "{:<60} {:>12} {:>12} {:>9} {:>9}",
+            "benchmark", "baseline", "current", "time", "memory"
+        )?;
+        for record in current.results.iter() {
+            let key = record.key();
+            let baseline_record = match baseline_records.get(&key) {
+                Some(r) => r,
+                None => {
+                    writeln!(output, "{:<60} {:>12} {:>12.6}", key, "-", record.mean)?;
+                    continue;
+                }
+            };
+            let time_change = relative_change(baseline_record.mean, record.mean);
+            let memory_change = match (baseline_record.peak_memory_bytes, record.peak_memory_bytes)
+            {
+                (Some(b), Some(c)) => Some(relative_change(b as f64, c as f64)),
+                _ => None,
+            };
+            let regression =
+                time_change > self.threshold || memory_change.is_some_and(|m| m > self.threshold);
+            if regression {
+                n_regressions += 1;
+            }
+            let line = format!(
+                "{:<60} {:>12.6} {:>12.6} {:>+8.1}% {:>9}",
+                key,
+                baseline_record.mean,
+                record.mean,
+                time_change,
+                memory_change.map_or_else(|| "-".to_string(), |m| format!("{:+.1}%", m))
+            );
+            if regression {
+                writeln!(output, "{} {}", line.red(), "REGRESSION".red().bold())?;
+            } else if time_change < -self.threshold {
+                writeln!(output, "{}", line.green())?;
+            } else {
+                writeln!(output, "{}", line)?;
+            }
+        }
+        output.flush()?;
+
+        if self.fail_on_regression && n_regressions > 0 {
+            bail!("{} regression(s) above {}%", n_regressions, self.threshold);
+        }
+        Ok(())
+    }
+}
+
+/// Change from `baseline` to `current` in percents.
+fn relative_change(baseline: f64, current: f64) -> f64 {
+    if baseline == 0.0 {
+        0.0
+    } else {
+        (current - baseline) / baseline * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("compose"), "compose");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        assert_eq!(percentile(&samples, 0.0), 1.0);
+        assert_eq!(percentile(&samples, 50.0), 5.0);
+        assert_eq!(percentile(&samples, 55.0), 6.0);
+        assert_eq!(percentile(&samples, 90.0), 9.0);
+        assert_eq!(percentile(&samples, 100.0), 10.0);
+        assert_eq!(percentile(&[42.0], 99.0), 42.0);
+    }
+
+    #[test]
+    fn test_relative_change() {
+        assert_eq!(relative_change(2.0, 3.0), 50.0);
+        assert_eq!(relative_change(2.0, 1.0), -50.0);
+        assert_eq!(relative_change(2.0, 2.0), 0.0);
+        assert_eq!(relative_change(0.0, 1.0), 0.0);
+    }
+}
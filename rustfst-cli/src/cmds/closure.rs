@@ -0,0 +1,52 @@
+use anyhow::{bail, Result};
+
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct ClosureAlgorithm {
+    path_in: String,
+    closure_type: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for ClosureAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "closure".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let closure_type = match self.closure_type.as_str() {
+            "star" => ClosureType::ClosureStar,
+            "plus" => ClosureType::ClosurePlus,
+            _ => bail!("Unknown closure_type : {}", self.closure_type),
+        };
+        closure(&mut fst, closure_type);
+        Ok(fst)
+    }
+}
+
+impl ClosureAlgorithm {
+    pub fn new(path_in: &str, closure_type: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            closure_type: closure_type.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::{bail, format_err, Context, Result};
+use log::{debug, info};
+
+use rustfst::prelude::*;
+use rustfst::{Label, SymbolTable};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{create_output, read_input_string, read_symbol_table};
+
+/// Compiles an FST in AT&T text format into its binary representation. Equivalent of
+/// OpenFST's `fstcompile`.
+pub struct CompileCommand {
+    path_in: String,
+    path_out: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    acceptor: bool,
+    keep_isymbols: bool,
+    keep_osymbols: bool,
+    arc_type: String,
+    fst_type: String,
+}
+
+impl CompileCommand {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        acceptor: bool,
+        keep_isymbols: bool,
+        keep_osymbols: bool,
+        arc_type: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymbols: isymbols.map(|s| s.to_string()),
+            osymbols: osymbols.map(|s| s.to_string()),
+            acceptor,
+            keep_isymbols,
+            keep_osymbols,
+            arc_type: arc_type.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Compiling {}", self.path_in);
+        let isymt = read_symbol_table(self.isymbols.as_deref())?;
+        let osymt = if self.acceptor && self.osymbols.is_none() {
+            isymt.clone()
+        } else {
+            read_symbol_table(self.osymbols.as_deref())?
+        };
+
+        debug!("Converting symbols to labels...");
+        let text = read_input_string(&self.path_in)?;
+        let numeric_text = self
+            .labels_text(&text, isymt.as_ref(), osymt.as_ref())
+            .categorize(ErrorCategory::Parse)?;
+
+        dispatch_fst_type!(self.fst_type.as_str(), self.arc_type.as_str(), F => {
+            self.compile::<F>(&numeric_text, isymt, osymt)
+        })
+    }
+
+    fn compile<F>(
+        &self,
+        numeric_text: &str,
+        isymt: Option<Rc<SymbolTable>>,
+        osymt: Option<Rc<SymbolTable>>,
+    ) -> Result<()>
+    where
+        F: SerializableFst + From<VectorFst<<F as CoreFst>::W>>,
+        F::W: SerializableSemiring + 'static,
+    {
+        let mut fst =
+            VectorFst::<F::W>::from_text_string(numeric_text).categorize(ErrorCategory::Parse)?;
+        if self.keep_isymbols {
+            if let Some(symt) = isymt {
+                fst.set_input_symbols(symt);
+            }
+        }
+        if self.keep_osymbols {
+            if let Some(symt) = osymt {
+                fst.set_output_symbols(symt);
+            }
+        }
+
+        debug!("Serialization...");
+        let fst: F = fst.into();
+        let mut output = create_output(&self.path_out)?;
+        fst.write_binary(&mut output)
+            .and_then(|_| Ok(output.flush()?))
+            .with_context(|| format!("Can't write FST to {:?}", self.path_out))
+            .categorize(ErrorCategory::Io)
+    }
+
+    /// Rewrites the text FST with tab separated numeric labels, as expected by the parser
+    /// of `SerializableFst`.
+    fn labels_text(
+        &self,
+        text: &str,
+        isymt: Option<&Rc<SymbolTable>>,
+        osymt: Option<&Rc<SymbolTable>>,
+    ) -> Result<String> {
+        let (min_arc_fields, max_arc_fields) = if self.acceptor { (3, 4) } else { (4, 5) };
+        let mut lines = vec![];
+        for (idx, line) in text.lines().enumerate() {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let nfields = fields.len();
+            if nfields == 0 {
+                continue;
+            }
+            let mut row = vec![];
+            if nfields >= min_arc_fields && nfields <= max_arc_fields {
+                let ilabel = parse_label(fields[2], isymt, idx + 1)?;
+                let olabel = if self.acceptor {
+                    parse_label(fields[2], osymt, idx + 1)?
+                } else {
+                    parse_label(fields[3], osymt, idx + 1)?
+                };
+                row.push(fields[0].to_string());
+                row.push(fields[1].to_string());
+                row.push(ilabel.to_string());
+                row.push(olabel.to_string());
+                row.extend(fields[min_arc_fields..].iter().map(|f| f.to_string()));
+            } else if nfields <= 2 {
+                row.extend(fields.iter().map(|f| f.to_string()));
+            } else {
+                bail!(
+                    "Line {}: wrong number of fields ({}) in {} : {:?}",
+                    idx + 1,
+                    nfields,
+                    if self.acceptor {
+                        "acceptor"
+                    } else {
+                        "transducer"
+                    },
+                    line
+                );
+            }
+            lines.push(row.join("\t"));
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+fn parse_label(field: &str, symt: Option<&Rc<SymbolTable>>, line: usize) -> Result<Label> {
+    if let Some(symt) = symt {
+        symt.get_label(field).ok_or_else(|| {
+            format_err!(
+                "Line {}: symbol {:?} not found in the symbol table",
+                line,
+                field
+            )
+        })
+    } else {
+        field
+            .parse()
+            .with_context(|| format!("Line {}: invalid label {:?}", line, field))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(acceptor: bool) -> CompileCommand {
+        CompileCommand::new(
+            "-", "-", None, None, acceptor, false, false, "standard", "vector",
+        )
+    }
+
+    fn symbol_table() -> Rc<SymbolTable> {
+        let mut symt = SymbolTable::new();
+        symt.add_symbol("a");
+        symt.add_symbol("b");
+        Rc::new(symt)
+    }
+
+    #[test]
+    fn test_labels_text_transducer() -> Result<()> {
+        let symt = symbol_table();
+        let text = "0 1 a b 0.5\n\n0 2 b a\n2\n1 0.25\n";
+        assert_eq!(
+            command(false).labels_text(text, Some(&symt), Some(&symt))?,
+            "0\t1\t1\t2\t0.5\n0\t2\t2\t1\n2\n1\t0.25"
+        );
+        assert_eq!(
+            command(false).labels_text("0 1 3 4\n1\n", None, None)?,
+            "0\t1\t3\t4\n1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_labels_text_acceptor() -> Result<()> {
+        let symt = symbol_table();
+        assert_eq!(
+            command(true).labels_text("0 1 b 0.5\n1\n", Some(&symt), Some(&symt))?,
+            "0\t1\t2\t2\t0.5\n1"
+        );
+        assert_eq!(
+            command(true).labels_text("0 1 3\n1\n", None, None)?,
+            "0\t1\t3\t3\n1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_labels_text_wrong_number_of_fields() {
+        // Too many fields for an acceptor, too few for a transducer.
+        assert!(command(true)
+            .labels_text("0 1 3 4 0.5\n", None, None)
+            .is_err());
+        assert!(command(false).labels_text("0 1 3\n", None, None).is_err());
+        assert!(command(false)
+            .labels_text("0 1 3 4 0.5 1\n", None, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_labels_text_unknown_labels() {
+        let symt = symbol_table();
+        let err = command(false)
+            .labels_text("0 1 a b\n0 1 a c\n", Some(&symt), Some(&symt))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Line 2: symbol \"c\" not found in the symbol table"
+        );
+        assert!(command(false).labels_text("0 1 a 2\n", None, None).is_err());
+    }
+}
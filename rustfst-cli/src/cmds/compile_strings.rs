@@ -0,0 +1,135 @@
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::{ensure, Context, Result};
+use log::{debug, info};
+
+use rustfst::prelude::*;
+use rustfst::utils::{acceptor, transducer};
+use rustfst::{Label, SymbolTable};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{
+    create_output, read_input_string, read_symbol_table, string_to_labels, TokenType,
+};
+
+/// Compiles strings into a linear FST. Each line of the input is a string to compile : an
+/// acceptor, or a transducer if the line contains an input and an output string separated by
+/// a tab. Several lines produce the union of the strings. Equivalent of OpenFST's
+/// `farcompilestrings`, without the FAR archive.
+pub struct CompileStringsCommand {
+    path_in: String,
+    path_out: String,
+    token_type: TokenType,
+    symbols: Option<String>,
+    unknown_symbol: Option<String>,
+    keep_symbols: bool,
+    arc_type: String,
+    fst_type: String,
+}
+
+impl CompileStringsCommand {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        token_type: TokenType,
+        symbols: Option<&str>,
+        unknown_symbol: Option<&str>,
+        keep_symbols: bool,
+        arc_type: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            token_type,
+            symbols: symbols.map(|s| s.to_string()),
+            unknown_symbol: unknown_symbol.map(|s| s.to_string()),
+            keep_symbols,
+            arc_type: arc_type.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Compiling strings of {}", self.path_in);
+        let symt = read_symbol_table(self.symbols.as_deref())?;
+        let text = read_input_string(&self.path_in)?;
+
+        debug!("Converting strings to labels...");
+        let strings = self
+            .strings_labels(&text, symt.as_deref())
+            .categorize(ErrorCategory::Parse)?;
+
+        dispatch_fst_type!(self.fst_type.as_str(), self.arc_type.as_str(), F => {
+            self.compile::<F>(&strings, symt)
+        })
+    }
+
+    /// Returns the input and output labels of each non empty line.
+    fn strings_labels(
+        &self,
+        text: &str,
+        symt: Option<&SymbolTable>,
+    ) -> Result<Vec<(Vec<Label>, Vec<Label>)>> {
+        let unknown_symbol = self.unknown_symbol.as_deref();
+        let strings = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(idx, line)| {
+                let mut fields = line.splitn(2, '\t');
+                let input = fields.next().unwrap_or_default();
+                let ilabels = string_to_labels(input, self.token_type, symt, unknown_symbol)
+                    .with_context(|| format!("Line {}", idx + 1))?;
+                let olabels = match fields.next() {
+                    Some(output) => string_to_labels(output, self.token_type, symt, unknown_symbol)
+                        .with_context(|| format!("Line {}", idx + 1))?,
+                    None => ilabels.clone(),
+                };
+                Ok((ilabels, olabels))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(!strings.is_empty(), "No string to compile");
+        Ok(strings)
+    }
+
+    fn compile<F>(
+        &self,
+        strings: &[(Vec<Label>, Vec<Label>)],
+        symt: Option<Rc<SymbolTable>>,
+    ) -> Result<()>
+    where
+        F: SerializableFst + From<VectorFst<<F as CoreFst>::W>>,
+        F::W: SerializableSemiring + 'static,
+    {
+        let mut fst = VectorFst::<F::W>::new();
+        for (idx, (ilabels, olabels)) in strings.iter().enumerate() {
+            let linear_fst: VectorFst<_> = if ilabels == olabels {
+                acceptor(ilabels, F::W::one())
+            } else {
+                transducer(ilabels, olabels, F::W::one())
+            };
+            if idx == 0 {
+                fst = linear_fst;
+            } else {
+                union(&mut fst, &linear_fst).categorize(ErrorCategory::Algorithm)?;
+            }
+        }
+        if self.keep_symbols {
+            if let Some(symt) = symt {
+                fst.set_input_symbols(Rc::clone(&symt));
+                fst.set_output_symbols(symt);
+            }
+        }
+
+        debug!("Serialization...");
+        let fst: F = fst.into();
+        let mut output = create_output(&self.path_out)?;
+        fst.write_binary(&mut output)
+            .and_then(|_| Ok(output.flush()?))
+            .with_context(|| format!("Can't write FST to {:?}", self.path_out))
+            .categorize(ErrorCategory::Io)
+    }
+}
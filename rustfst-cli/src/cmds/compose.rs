@@ -0,0 +1,82 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Result};
+
+use rustfst::algorithms::compose::{compose_with_config, ComposeConfig, ComposeFilterEnum};
+use rustfst::prelude::*;
+
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct ComposeAlgorithm {
+    path_in_1: String,
+    path_in_2: String,
+    compose_filter: String,
+    connect: bool,
+    path_out: String,
+    fst_type: String,
+}
+
+impl BinaryFstAlgorithm for ComposeAlgorithm {
+    fn get_path_in_1(&self) -> &str {
+        self.path_in_1.as_str()
+    }
+
+    fn get_path_in_2(&self) -> &str {
+        self.path_in_2.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "compose".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(
+        &self,
+        fst_1: VectorFst<W>,
+        fst_2: VectorFst<W>,
+    ) -> Result<VectorFst<W>> {
+        let compose_filter = match self.compose_filter.as_str() {
+            "auto" => ComposeFilterEnum::AutoFilter,
+            "null" => ComposeFilterEnum::NullFilter,
+            "trivial" => ComposeFilterEnum::TrivialFilter,
+            "sequence" => ComposeFilterEnum::SequenceFilter,
+            "alt_sequence" => ComposeFilterEnum::AltSequenceFilter,
+            "match" => ComposeFilterEnum::MatchFilter,
+            "no_match" => ComposeFilterEnum::NoMatchFilter,
+            _ => bail!("Unknown compose filter : {}", self.compose_filter),
+        };
+        let config = ComposeConfig {
+            compose_filter,
+            connect: self.connect,
+        };
+        compose_with_config(Rc::new(fst_1), Rc::new(fst_2), config)
+    }
+}
+
+impl ComposeAlgorithm {
+    pub fn new(
+        path_in_1: &str,
+        path_in_2: &str,
+        compose_filter: &str,
+        connect: bool,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in_1: path_in_1.to_string(),
+            path_in_2: path_in_2.to_string(),
+            compose_filter: compose_filter.to_string(),
+            connect,
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
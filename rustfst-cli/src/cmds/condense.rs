@@ -0,0 +1,45 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct CondenseAlgorithm {
+    path_in: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for CondenseAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "condense".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let (_scc, condensed_fst) = condense(&fst)?;
+        Ok(condensed_fst)
+    }
+}
+
+impl CondenseAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
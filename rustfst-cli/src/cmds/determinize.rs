@@ -0,0 +1,52 @@
+use anyhow::{bail, Result};
+
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct DeterminizeAlgorithm {
+    path_in: String,
+    det_type: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for DeterminizeAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "determinize".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let det_type = match self.det_type.as_str() {
+            "functional" => DeterminizeType::DeterminizeFunctional,
+            "nonfunctional" => DeterminizeType::DeterminizeNonFunctional,
+            "disambiguate" => DeterminizeType::DeterminizeDisambiguate,
+            _ => bail!("Unknown det_type : {}", self.det_type),
+        };
+        determinize(&fst, det_type)
+    }
+}
+
+impl DeterminizeAlgorithm {
+    pub fn new(path_in: &str, det_type: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            det_type: det_type.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
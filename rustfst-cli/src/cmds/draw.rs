@@ -0,0 +1,71 @@
+use std::io::Write;
+
+use anyhow::{format_err, Context, Result};
+use log::info;
+
+use rustfst::prelude::*;
+use rustfst::DrawingConfig;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{create_output, read_fst_types, read_input, resolve_symbol_tables};
+
+/// Draws a binary FST in the DOT format of GraphViz. Equivalent of OpenFST's `fstdraw`.
+pub struct DrawCommand {
+    path_in: String,
+    path_out: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    config: DrawingConfig,
+}
+
+impl DrawCommand {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        config: DrawingConfig,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymbols: isymbols.map(|s| s.to_string()),
+            osymbols: osymbols.map(|s| s.to_string()),
+            config,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Drawing {}", self.path_in);
+        let data = read_input(&self.path_in)?;
+        let (fst_type, arc_type) = read_fst_types(&data)?;
+        dispatch_fst_type!(fst_type.as_str(), arc_type.as_str(), F => {
+            let (_, fst) = F::parse_binary(&data)
+                .map_err(|_| format_err!("Error while parsing binary {} fst", fst_type))
+                .categorize(ErrorCategory::Parse)?;
+            self.draw(fst)
+        })
+    }
+
+    fn draw<F: SerializableFst>(&self, mut fst: F) -> Result<()>
+    where
+        F::W: SerializableSemiring,
+    {
+        let (isymt, osymt) =
+            resolve_symbol_tables(&fst, self.isymbols.as_deref(), self.osymbols.as_deref())?;
+        if let Some(isymt) = isymt {
+            fst.set_input_symbols(isymt);
+        }
+        if let Some(osymt) = osymt {
+            fst.set_output_symbols(osymt);
+        }
+
+        // Writing errors are categorized as I/O errors, the other ones come from labels
+        // missing in the symbol tables.
+        let mut output = create_output(&self.path_out)?;
+        fst.draw_to_writer(&mut output, &self.config)
+            .and_then(|_| Ok(output.flush()?))
+            .with_context(|| format!("Can't draw FST to {:?}", self.path_out))
+            .categorize(ErrorCategory::Parse)
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct EncodeAlgorithm {
+    path_in: String,
+    encode_labels: bool,
+    encode_weights: bool,
+    decode: bool,
+    codex: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for EncodeAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        if self.decode {
+            "decode".to_string()
+        } else {
+            "encode".to_string()
+        }
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        if self.decode {
+            let encode_table =
+                EncodeTable::read_text(&self.codex).categorize(ErrorCategory::Parse)?;
+            decode(&mut fst, encode_table)?;
+        } else {
+            let encode_table = encode(&mut fst, self.encode_labels, self.encode_weights)?;
+            encode_table
+                .write_text(&self.codex)
+                .categorize(ErrorCategory::Io)?;
+        }
+        Ok(fst)
+    }
+}
+
+impl EncodeAlgorithm {
+    pub fn new(
+        path_in: &str,
+        encode_labels: bool,
+        encode_weights: bool,
+        decode: bool,
+        codex: &str,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            encode_labels,
+            encode_weights,
+            decode,
+            codex: codex.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::{format_err, Result};
+use log::info;
+
+use rustfst::fst_properties::{compute_fst_properties, FstProperties};
+use rustfst::prelude::*;
+use rustfst::{SymbolTable, EPS_LABEL};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{create_output, read_fst_types, read_input, STD_STREAM};
+
+/// Name displayed for each pair of (positive, negative) properties.
+static PROPERTIES_NAMES: [(&str, FstProperties, FstProperties); 16] = [
+    (
+        "acceptor",
+        FstProperties::ACCEPTOR,
+        FstProperties::NOT_ACCEPTOR,
+    ),
+    (
+        "input deterministic",
+        FstProperties::I_DETERMINISTIC,
+        FstProperties::NOT_I_DETERMINISTIC,
+    ),
+    (
+        "output deterministic",
+        FstProperties::O_DETERMINISTIC,
+        FstProperties::NOT_O_DETERMINISTIC,
+    ),
+    (
+        "input/output epsilons",
+        FstProperties::EPSILONS,
+        FstProperties::NO_EPSILONS,
+    ),
+    (
+        "input epsilons",
+        FstProperties::I_EPSILONS,
+        FstProperties::NO_I_EPSILONS,
+    ),
+    (
+        "output epsilons",
+        FstProperties::O_EPSILONS,
+        FstProperties::NO_O_EPSILONS,
+    ),
+    (
+        "input label sorted",
+        FstProperties::I_LABEL_SORTED,
+        FstProperties::NOT_I_LABEL_SORTED,
+    ),
+    (
+        "output label sorted",
+        FstProperties::O_LABEL_SORTED,
+        FstProperties::NOT_O_LABEL_SORTED,
+    ),
+    (
+        "weighted",
+        FstProperties::WEIGHTED,
+        FstProperties::UNWEIGHTED,
+    ),
+    ("cyclic", FstProperties::CYCLIC, FstProperties::ACYCLIC),
+    (
+        "cyclic at initial state",
+        FstProperties::INITIAL_CYCLIC,
+        FstProperties::INITIAL_ACYCLIC,
+    ),
+    (
+        "top sorted",
+        FstProperties::TOP_SORTED,
+        FstProperties::NOT_TOP_SORTED,
+    ),
+    (
+        "accessible",
+        FstProperties::ACCESSIBLE,
+        FstProperties::NOT_ACCESSIBLE,
+    ),
+    (
+        "coaccessible",
+        FstProperties::COACCESSIBLE,
+        FstProperties::NOT_COACCESSIBLE,
+    ),
+    ("string", FstProperties::STRING, FstProperties::NOT_STRING),
+    (
+        "weighted cycles",
+        FstProperties::WEIGHTED_CYCLES,
+        FstProperties::UNWEIGHTED_CYCLES,
+    ),
+];
+
+/// Prints information and statistics about a binary FST. Equivalent of OpenFST's `fstinfo`.
+pub struct InfoCommand {
+    path_in: String,
+}
+
+impl InfoCommand {
+    pub fn new(path_in: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Computing info of {}", self.path_in);
+        let data = read_input(&self.path_in)?;
+        let (fst_type, arc_type) = read_fst_types(&data)?;
+        dispatch_fst_type!(fst_type.as_str(), arc_type.as_str(), F => {
+            let (_, fst) = F::parse_binary(&data)
+                .map_err(|_| format_err!("Error while parsing binary {} fst", fst_type))
+                .categorize(ErrorCategory::Parse)?;
+            print_info(&fst, &fst_type, &arc_type)
+        })
+    }
+}
+
+fn print_line<T: std::fmt::Display>(output: &mut dyn Write, name: &str, value: T) -> Result<()> {
+    writeln!(output, "{:<50}{}", name, value)?;
+    Ok(())
+}
+
+fn symt_info(symt: Option<Rc<SymbolTable>>) -> String {
+    match symt {
+        Some(symt) => format!("present ({} symbols)", symt.len()),
+        None => "none".to_string(),
+    }
+}
+
+fn print_info<F: SerializableFst>(fst: &F, fst_type: &str, arc_type: &str) -> Result<()>
+where
+    F::W: SerializableSemiring,
+{
+    let mut num_arcs = 0;
+    let mut num_epsilons = 0;
+    let mut num_input_epsilons = 0;
+    let mut num_output_epsilons = 0;
+    let mut max_out_degree = 0;
+    for s in fst.states_iter() {
+        let n = fst.num_arcs(s)?;
+        num_arcs += n;
+        max_out_degree = max_out_degree.max(n);
+        num_input_epsilons += fst.num_input_epsilons(s)?;
+        num_output_epsilons += fst.num_output_epsilons(s)?;
+        num_epsilons += fst
+            .arcs_iter(s)?
+            .filter(|arc| arc.ilabel == EPS_LABEL && arc.olabel == EPS_LABEL)
+            .count();
+    }
+
+    let (nscc, access, coaccess) = scc_stats(fst)?;
+    let num_accessible = access.iter().filter(|a| **a).count();
+    let num_coaccessible = coaccess.iter().filter(|a| **a).count();
+    let num_connected = access
+        .iter()
+        .zip(coaccess.iter())
+        .filter(|(a, c)| **a && **c)
+        .count();
+
+    let mut output = create_output(STD_STREAM)?;
+    print_line(&mut output, "fst type", fst_type)?;
+    print_line(&mut output, "arc type", arc_type)?;
+    print_line(
+        &mut output,
+        "input symbol table",
+        symt_info(fst.input_symbols()),
+    )?;
+    print_line(
+        &mut output,
+        "output symbol table",
+        symt_info(fst.output_symbols()),
+    )?;
+    print_line(&mut output, "# of states", fst.num_states())?;
+    print_line(&mut output, "# of arcs", num_arcs)?;
+    print_line(
+        &mut output,
+        "initial state",
+        fst.start().map_or("none".to_string(), |s| s.to_string()),
+    )?;
+    print_line(
+        &mut output,
+        "# of final states",
+        fst.final_states_iter().count(),
+    )?;
+    print_line(&mut output, "# of input/output epsilons", num_epsilons)?;
+    print_line(&mut output, "# of input epsilons", num_input_epsilons)?;
+    print_line(&mut output, "# of output epsilons", num_output_epsilons)?;
+    print_line(&mut output, "max out-degree", max_out_degree)?;
+    print_line(&mut output, "# of accessible states", num_accessible)?;
+    print_line(&mut output, "# of coaccessible states", num_coaccessible)?;
+    print_line(&mut output, "# of connected states", num_connected)?;
+    print_line(&mut output, "# of strongly conn components", nscc)?;
+
+    let props = compute_fst_properties(fst)?;
+    for (name, positive, negative) in PROPERTIES_NAMES.iter() {
+        let value = if props.contains(*positive) {
+            "y"
+        } else if props.contains(*negative) {
+            "n"
+        } else {
+            "?"
+        };
+        print_line(&mut output, name, value)?;
+    }
+    output.flush()?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::rc::Rc;
+
+use anyhow::{ensure, Result};
+
+use rustfst::algorithms::compose::{compose_with_config, ComposeConfig};
+use rustfst::fst_properties::{compute_fst_properties, FstProperties};
+use rustfst::prelude::*;
+
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct IntersectAlgorithm {
+    path_in_1: String,
+    path_in_2: String,
+    connect: bool,
+    path_out: String,
+    fst_type: String,
+}
+
+impl BinaryFstAlgorithm for IntersectAlgorithm {
+    fn get_path_in_1(&self) -> &str {
+        self.path_in_1.as_str()
+    }
+
+    fn get_path_in_2(&self) -> &str {
+        self.path_in_2.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "intersect".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(
+        &self,
+        fst_1: VectorFst<W>,
+        fst_2: VectorFst<W>,
+    ) -> Result<VectorFst<W>> {
+        // The intersection of two acceptors is their composition.
+        ensure!(
+            compute_fst_properties(&fst_1)?.contains(FstProperties::ACCEPTOR)
+                && compute_fst_properties(&fst_2)?.contains(FstProperties::ACCEPTOR),
+            "Intersect: input FSTs must be acceptors"
+        );
+        let config = ComposeConfig {
+            connect: self.connect,
+            ..ComposeConfig::default()
+        };
+        compose_with_config(Rc::new(fst_1), Rc::new(fst_2), config)
+    }
+}
+
+impl IntersectAlgorithm {
+    pub fn new(
+        path_in_1: &str,
+        path_in_2: &str,
+        connect: bool,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in_1: path_in_1.to_string(),
+            path_in_2: path_in_2.to_string(),
+            connect,
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
@@ -3,10 +3,12 @@ use anyhow::Result;
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 
 pub struct InvertAlgorithm {
     path_in: String,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for InvertAlgorithm {
@@ -18,24 +20,26 @@ impl UnaryFstAlgorithm for InvertAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "invert".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         invert(&mut fst);
         Ok(fst)
     }
 }
 
 impl InvertAlgorithm {
-    pub fn new(path_in: &str, path_out: &str) -> Self {
+    pub fn new(path_in: &str, path_out: &str, fst_type: &str) -> Self {
         Self {
             path_in: path_in.to_string(),
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
@@ -4,12 +4,14 @@ use unsafe_unwrap::UnsafeUnwrap;
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{parse_weight, CliWeight};
 
 pub struct MapAlgorithm {
     path_in: String,
     map_type: String,
-    weight: Option<f32>,
+    weight: Option<String>,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for MapAlgorithm {
@@ -21,14 +23,15 @@ impl UnaryFstAlgorithm for MapAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         format!("map {}", self.map_type)
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         match self.map_type.as_str() {
             "arc_sum" => {
                 arc_sum(&mut fst);
@@ -60,8 +63,8 @@ impl UnaryFstAlgorithm for MapAlgorithm {
             }
             "plus" => {
                 // Safe because there is a check at parsing time.
-                let mut mapper =
-                    arc_mappers::PlusMapper::new(unsafe { self.weight.unsafe_unwrap() });
+                let weight: W = parse_weight(unsafe { self.weight.as_ref().unsafe_unwrap() })?;
+                let mut mapper = arc_mappers::PlusMapper::new(weight.take_value());
                 arc_map(&mut fst, &mut mapper)?;
                 Ok(fst)
             }
@@ -78,8 +81,8 @@ impl UnaryFstAlgorithm for MapAlgorithm {
             }
             "times" => {
                 // Safe because there is a check at parsing time.
-                let mut mapper =
-                    arc_mappers::TimesMapper::new(unsafe { self.weight.unsafe_unwrap() });
+                let weight: W = parse_weight(unsafe { self.weight.as_ref().unsafe_unwrap() })?;
+                let mut mapper = arc_mappers::TimesMapper::new(weight.take_value());
                 arc_map(&mut fst, &mut mapper)?;
                 Ok(fst)
             }
@@ -92,12 +95,19 @@ impl UnaryFstAlgorithm for MapAlgorithm {
 }
 
 impl MapAlgorithm {
-    pub fn new(path_in: &str, map_type: &str, weight: Option<&str>, path_out: &str) -> Self {
+    pub fn new(
+        path_in: &str,
+        map_type: &str,
+        weight: Option<&str>,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
         Self {
             path_in: path_in.to_string(),
             map_type: map_type.to_string(),
-            weight: weight.map(|f| f.parse().unwrap()),
+            weight: weight.map(|w| w.to_string()),
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
@@ -1,12 +1,14 @@
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 use anyhow::Result;
 
 pub struct MinimizeAlgorithm {
     path_in: String,
     allow_nondet: bool,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for MinimizeAlgorithm {
@@ -18,25 +20,27 @@ impl UnaryFstAlgorithm for MinimizeAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "minimize".to_string()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         minimize(&mut fst, self.allow_nondet)?;
         Ok(fst)
     }
 }
 
 impl MinimizeAlgorithm {
-    pub fn new(path_in: &str, allow_nondet: bool, path_out: &str) -> Self {
+    pub fn new(path_in: &str, allow_nondet: bool, path_out: &str, fst_type: &str) -> Self {
         Self {
             path_in: path_in.to_string(),
             allow_nondet,
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
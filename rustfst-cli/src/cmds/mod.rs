@@ -1,11 +1,33 @@
 pub mod arcsort;
+pub mod bench;
+pub mod closure;
+pub mod compile;
+pub mod compile_strings;
+pub mod compose;
+pub mod concat;
+pub mod condense;
 pub mod connect;
+pub mod determinize;
+pub mod draw;
+pub mod encode;
+pub mod info;
+pub mod intersect;
 pub mod invert;
 pub mod map;
 pub mod minimize;
+pub mod print;
+pub mod print_strings;
 pub mod project;
 pub mod push;
+pub mod relabel;
+pub mod replace;
 pub mod reverse;
+pub mod reweight;
+pub mod rm_epsilon;
 pub mod rm_final_epsilon;
+pub mod shortest_distance;
 pub mod shortest_path;
+pub mod state_sort;
+pub mod symbols;
 pub mod topsort;
+pub mod union;
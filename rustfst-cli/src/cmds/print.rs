@@ -0,0 +1,120 @@
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::{format_err, Result};
+use log::info;
+
+use rustfst::prelude::*;
+use rustfst::{Label, StateId, SymbolTable};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{create_output, read_fst_types, read_input, resolve_symbol_tables};
+
+/// Prints a binary FST in AT&T text format. Equivalent of OpenFST's `fstprint`.
+pub struct PrintCommand {
+    path_in: String,
+    path_out: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    acceptor: bool,
+    show_weight_one: bool,
+}
+
+impl PrintCommand {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        acceptor: bool,
+        show_weight_one: bool,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            isymbols: isymbols.map(|s| s.to_string()),
+            osymbols: osymbols.map(|s| s.to_string()),
+            acceptor,
+            show_weight_one,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Printing {}", self.path_in);
+        let data = read_input(&self.path_in)?;
+        let (fst_type, arc_type) = read_fst_types(&data)?;
+        dispatch_fst_type!(fst_type.as_str(), arc_type.as_str(), F => {
+            let (_, fst) = F::parse_binary(&data)
+                .map_err(|_| format_err!("Error while parsing binary {} fst", fst_type))
+                .categorize(ErrorCategory::Parse)?;
+            self.print(&fst)
+        })
+    }
+
+    fn print<F: SerializableFst>(&self, fst: &F) -> Result<()>
+    where
+        F::W: SerializableSemiring,
+    {
+        let (isymt, osymt) =
+            resolve_symbol_tables(fst, self.isymbols.as_deref(), self.osymbols.as_deref())?;
+
+        let mut output = create_output(&self.path_out)?;
+        if let Some(start_state) = fst.start() {
+            self.print_state(fst, start_state, &isymt, &osymt, &mut output)?;
+            for state in fst.states_iter() {
+                if state != start_state {
+                    self.print_state(fst, state, &isymt, &osymt, &mut output)?;
+                }
+            }
+        }
+        output.flush()?;
+        Ok(())
+    }
+
+    fn print_state<F: SerializableFst, O: Write>(
+        &self,
+        fst: &F,
+        state: StateId,
+        isymt: &Option<Rc<SymbolTable>>,
+        osymt: &Option<Rc<SymbolTable>>,
+        output: &mut O,
+    ) -> Result<()>
+    where
+        F::W: SerializableSemiring,
+    {
+        for arc in fst.arcs_iter(state)? {
+            write!(
+                output,
+                "{}\t{}\t{}",
+                state,
+                arc.nextstate,
+                format_label(arc.ilabel, isymt)?
+            )?;
+            if !self.acceptor {
+                write!(output, "\t{}", format_label(arc.olabel, osymt)?)?;
+            }
+            if self.show_weight_one || !arc.weight.is_one() {
+                write!(output, "\t{}", arc.weight)?;
+            }
+            writeln!(output)?;
+        }
+        if let Some(final_weight) = fst.final_weight(state)? {
+            if self.show_weight_one || !final_weight.is_one() {
+                writeln!(output, "{}\t{}", state, final_weight)?;
+            } else {
+                writeln!(output, "{}", state)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_label(label: Label, symt: &Option<Rc<SymbolTable>>) -> Result<String> {
+    if let Some(symt) = symt {
+        symt.get_symbol(label)
+            .map(|s| s.to_string())
+            .ok_or_else(|| format_err!("Label {} not found in the symbol table", label))
+    } else {
+        Ok(label.to_string())
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::{ensure, Result};
+use log::info;
+
+use rustfst::fst_properties::{compute_fst_properties, FstProperties};
+use rustfst::prelude::*;
+use rustfst::utils::decode_linear_fst;
+use rustfst::{FstPath, Label, SymbolTable};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{
+    create_output, labels_to_string, parse_vector_fst, read_fst_types, read_input,
+    resolve_symbol_tables, CliWeight, TokenType,
+};
+
+/// Prints the paths of an FST as strings, one path per line followed by its weight. The
+/// output string is only printed if the FST is not an acceptor. Equivalent of OpenFST's
+/// `farprintstrings` on a single FST.
+pub struct PrintStringsCommand {
+    path_in: String,
+    path_out: String,
+    token_type: TokenType,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    nshortest: Option<usize>,
+    unique: bool,
+}
+
+impl PrintStringsCommand {
+    pub fn new(
+        path_in: &str,
+        path_out: &str,
+        token_type: TokenType,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        nshortest: Option<usize>,
+        unique: bool,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            token_type,
+            isymbols: isymbols.map(|s| s.to_string()),
+            osymbols: osymbols.map(|s| s.to_string()),
+            nshortest,
+            unique,
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Printing strings of {}", self.path_in);
+        let data = read_input(&self.path_in)?;
+        let (_, arc_type) = read_fst_types(&data)?;
+        dispatch_weight_type!(arc_type.as_str(), W => self.print::<W>(&data))
+    }
+
+    fn print<W: CliWeight>(&self, data: &[u8]) -> Result<()> {
+        let fst = parse_vector_fst::<W>(data)?;
+        let (isymt, osymt) =
+            resolve_symbol_tables(&fst, self.isymbols.as_deref(), self.osymbols.as_deref())?;
+
+        let props = compute_fst_properties(&fst)?;
+        let paths = self
+            .paths(&fst, props)
+            .categorize(ErrorCategory::Algorithm)?;
+        let acceptor = props.contains(FstProperties::ACCEPTOR);
+
+        let mut output = create_output(&self.path_out)?;
+        for path in paths {
+            write!(output, "{}", self.format_labels(&path.ilabels, &isymt)?)?;
+            if !acceptor {
+                write!(output, "\t{}", self.format_labels(&path.olabels, &osymt)?)?;
+            }
+            writeln!(output, "\t{}", path.weight)?;
+        }
+        output.flush()?;
+        Ok(())
+    }
+
+    /// Returns all the paths of the FST, or its n shortest paths if `nshortest` is set.
+    fn paths<W: CliWeight>(
+        &self,
+        fst: &VectorFst<W>,
+        props: FstProperties,
+    ) -> Result<Vec<FstPath<W>>> {
+        match self.nshortest {
+            Some(1) => {
+                let best: VectorFst<W> = shortest_path(fst, 1, self.unique)?;
+                if best.start().is_some() {
+                    Ok(vec![decode_linear_fst(&best)?])
+                } else {
+                    Ok(vec![])
+                }
+            }
+            Some(n) => {
+                let best: VectorFst<W> = shortest_path(fst, n, self.unique)?;
+                Ok(best.paths_iter().collect())
+            }
+            None => {
+                ensure!(
+                    props.contains(FstProperties::ACYCLIC),
+                    "The FST is cyclic and has an infinite number of paths, use --nshortest"
+                );
+                Ok(fst.paths_iter().collect())
+            }
+        }
+    }
+
+    fn format_labels(&self, labels: &[Label], symt: &Option<Rc<SymbolTable>>) -> Result<String> {
+        labels_to_string(labels, self.token_type, symt.as_deref()).categorize(ErrorCategory::Parse)
+    }
+}
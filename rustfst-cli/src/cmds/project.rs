@@ -3,11 +3,13 @@ use anyhow::Result;
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 
 pub struct ProjectFstAlgorithm {
     path_in: String,
     project_type: ProjectType,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for ProjectFstAlgorithm {
@@ -19,21 +21,27 @@ impl UnaryFstAlgorithm for ProjectFstAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "project".into()
     }
 
-    fn run_algorithm(
-        &self,
-        mut fst: VectorFst<TropicalWeight>,
-    ) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
         project(&mut fst, self.project_type);
         Ok(fst)
     }
 }
 
 impl ProjectFstAlgorithm {
-    pub fn new(path_in: &str, project_output: bool, path_out: &str) -> ProjectFstAlgorithm {
+    pub fn new(
+        path_in: &str,
+        project_output: bool,
+        path_out: &str,
+        fst_type: &str,
+    ) -> ProjectFstAlgorithm {
         Self {
             path_in: path_in.to_string(),
             project_type: if project_output {
@@ -42,6 +50,7 @@ impl ProjectFstAlgorithm {
                 ProjectType::ProjectInput
             },
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
@@ -3,10 +3,12 @@ use anyhow::Result;
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 
 pub struct PushAlgorithm {
     path_in: String,
     path_out: String,
+    fst_type: String,
     push_type: PushType,
     reweight_type: ReweightType,
 }
@@ -20,19 +22,25 @@ impl UnaryFstAlgorithm for PushAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "push".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         push(&fst, self.reweight_type, self.push_type)
     }
 }
 
 impl PushAlgorithm {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         path_in: &str,
         path_out: &str,
+        fst_type: &str,
         reweight_to_final: bool,
         push_weights: bool,
         push_labels: bool,
@@ -55,6 +63,7 @@ impl PushAlgorithm {
         Self {
             path_in: path_in.to_string(),
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
             push_type,
             reweight_type: if reweight_to_final {
                 ReweightType::ReweightToFinal
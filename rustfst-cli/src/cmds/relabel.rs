@@ -0,0 +1,63 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{read_label_pairs, CliWeight};
+
+pub struct RelabelAlgorithm {
+    path_in: String,
+    relabel_ipairs: Option<String>,
+    relabel_opairs: Option<String>,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for RelabelAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "relabel".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let ipairs = match &self.relabel_ipairs {
+            Some(path) => read_label_pairs(path)?,
+            None => vec![],
+        };
+        let opairs = match &self.relabel_opairs {
+            Some(path) => read_label_pairs(path)?,
+            None => vec![],
+        };
+        relabel_pairs(&mut fst, ipairs, opairs)?;
+        Ok(fst)
+    }
+}
+
+impl RelabelAlgorithm {
+    pub fn new(
+        path_in: &str,
+        relabel_ipairs: Option<&str>,
+        relabel_opairs: Option<&str>,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            relabel_ipairs: relabel_ipairs.map(|s| s.to_string()),
+            relabel_opairs: relabel_opairs.map(|s| s.to_string()),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
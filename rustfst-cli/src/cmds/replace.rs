@@ -0,0 +1,98 @@
+use anyhow::{format_err, Result};
+use log::info;
+
+use rustfst::prelude::*;
+use rustfst::Label;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{
+    parse_vector_fst, read_fst_types, read_input, write_fst, CliWeight, STD_STREAM,
+};
+
+/// Recursively replaces arcs in the root FST with other FSTs. Equivalent of OpenFST's
+/// `fstreplace`.
+pub struct ReplaceCommand {
+    fst_list: Vec<(Label, String)>,
+    root: Label,
+    epsilon_on_replace: bool,
+    path_out: String,
+    fst_type: String,
+}
+
+impl ReplaceCommand {
+    /// `fst_labels` contains the path of the root FST and its label, followed by the path
+    /// and the non-terminal label of each of the other FSTs.
+    pub fn new(
+        fst_labels: &[&str],
+        epsilon_on_replace: bool,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Result<Self> {
+        let fst_list = fst_labels
+            .chunks(2)
+            .map(|chunk| match chunk {
+                [path, label] => Ok((label.parse()?, path.to_string())),
+                _ => Err(format_err!(
+                    "Replace expects pairs of fst and label, found {:?}",
+                    chunk
+                )),
+            })
+            .collect::<Result<Vec<(Label, String)>>>()
+            .categorize(ErrorCategory::Parse)?;
+        let root = fst_list
+            .first()
+            .ok_or_else(|| format_err!("Missing root fst"))?
+            .0;
+        Ok(Self {
+            fst_list,
+            root,
+            epsilon_on_replace,
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        })
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Running replace algorithm");
+        let n_stdin = self
+            .fst_list
+            .iter()
+            .filter(|(_, path)| path == STD_STREAM)
+            .count();
+        if n_stdin > 1 {
+            return Err(format_err!(
+                "Only one of the input FSTs can be read from stdin"
+            ))
+            .categorize(ErrorCategory::Io);
+        }
+        let data = self
+            .fst_list
+            .iter()
+            .map(|(label, path)| Ok((*label, read_input(path)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let (_, arc_type) = read_fst_types(&data[0].1)?;
+        for (_, fst_data) in data.iter().skip(1) {
+            let (_, other_arc_type) = read_fst_types(fst_data)?;
+            if other_arc_type != arc_type {
+                return Err(format_err!(
+                    "Input FSTs must have the same arc type : {} != {}",
+                    arc_type,
+                    other_arc_type
+                ))
+                .categorize(ErrorCategory::Algorithm);
+            }
+        }
+        dispatch_weight_type!(arc_type.as_str(), W => self.replace::<W>(&data))
+    }
+
+    fn replace<W: CliWeight>(&self, data: &[(Label, Vec<u8>)]) -> Result<()> {
+        let fst_list = data
+            .iter()
+            .map(|(label, fst_data)| Ok((*label, parse_vector_fst::<W>(fst_data)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let fst: VectorFst<W> =
+            replace::<VectorFst<_>, _, _>(fst_list, self.root, self.epsilon_on_replace)
+                .categorize(ErrorCategory::Algorithm)?;
+        write_fst(fst, &self.fst_type, &self.path_out)
+    }
+}
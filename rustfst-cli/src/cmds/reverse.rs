@@ -3,10 +3,12 @@ use rustfst::prelude::*;
 use anyhow::Result;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 
 pub struct ReverseAlgorithm {
     path_in: String,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for ReverseAlgorithm {
@@ -18,20 +20,25 @@ impl UnaryFstAlgorithm for ReverseAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "reverse".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         reverse(&fst)
     }
 }
 
 impl ReverseAlgorithm {
-    pub fn new(path_in: &str, path_out: &str) -> Self {
+    pub fn new(path_in: &str, path_out: &str, fst_type: &str) -> Self {
         Self {
             path_in: path_in.to_string(),
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
@@ -0,0 +1,61 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{read_potentials, CliWeight};
+
+pub struct ReweightAlgorithm {
+    path_in: String,
+    potentials: String,
+    to_final: bool,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for ReweightAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "reweight".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let potentials: Vec<W> = read_potentials(&self.potentials, fst.num_states())?;
+        let reweight_type = if self.to_final {
+            ReweightType::ReweightToFinal
+        } else {
+            ReweightType::ReweightToInitial
+        };
+        reweight(&mut fst, &potentials, reweight_type)?;
+        Ok(fst)
+    }
+}
+
+impl ReweightAlgorithm {
+    pub fn new(
+        path_in: &str,
+        potentials: &str,
+        to_final: bool,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            potentials: potentials.to_string(),
+            to_final,
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
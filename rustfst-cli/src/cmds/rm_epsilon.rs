@@ -0,0 +1,74 @@
+use anyhow::{Context, Result};
+
+use rustfst::algorithms::arc_filters::EpsilonArcFilter;
+use rustfst::algorithms::queues::AutoQueue;
+use rustfst::prelude::*;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{parse_weight, CliWeight};
+
+pub struct RmEpsilonAlgorithm {
+    path_in: String,
+    connect: bool,
+    weight_threshold: Option<String>,
+    nstate: Option<usize>,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for RmEpsilonAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "rmepsilon".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let queue = AutoQueue::new(&fst, None, &EpsilonArcFilter {})?;
+        let weight_threshold = match &self.weight_threshold {
+            Some(w) => parse_weight(w)?,
+            None => W::zero(),
+        };
+        let config = RmEpsilonConfig::new(queue, self.connect, weight_threshold, self.nstate);
+        rm_epsilon_with_config(&mut fst, config)?;
+        Ok(fst)
+    }
+}
+
+impl RmEpsilonAlgorithm {
+    pub fn new(
+        path_in: &str,
+        connect: bool,
+        weight_threshold: Option<&str>,
+        nstate: Option<&str>,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Result<Self> {
+        let nstate = nstate
+            .map(|n| {
+                n.parse()
+                    .with_context(|| format!("Invalid number of states {:?}", n))
+            })
+            .transpose()
+            .categorize(ErrorCategory::Parse)?;
+        Ok(Self {
+            path_in: path_in.to_string(),
+            connect,
+            weight_threshold: weight_threshold.map(|w| w.to_string()),
+            nstate,
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        })
+    }
+}
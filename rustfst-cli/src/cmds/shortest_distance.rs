@@ -0,0 +1,47 @@
+use std::io::Write;
+
+use anyhow::Result;
+use log::info;
+
+use rustfst::prelude::*;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{create_output, parse_vector_fst, read_fst_types, read_input, CliWeight};
+
+/// Computes the shortest distance from the start state to every state, or from every state
+/// to the final states if `reverse` is set. Equivalent of OpenFST's `fstshortestdistance`.
+pub struct ShortestDistanceCommand {
+    path_in: String,
+    reverse: bool,
+    path_out: String,
+}
+
+impl ShortestDistanceCommand {
+    pub fn new(path_in: &str, reverse: bool, path_out: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            reverse,
+            path_out: path_out.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Running shortest distance algorithm");
+        let data = read_input(&self.path_in)?;
+        let (_, arc_type) = read_fst_types(&data)?;
+        dispatch_weight_type!(arc_type.as_str(), W => self.shortest_distance::<W>(&data))
+    }
+
+    fn shortest_distance<W: CliWeight>(&self, data: &[u8]) -> Result<()> {
+        let fst = parse_vector_fst::<W>(data)?;
+        let distances =
+            shortest_distance(&fst, self.reverse).categorize(ErrorCategory::Algorithm)?;
+
+        let mut output = create_output(&self.path_out)?;
+        for (state, distance) in distances.iter().enumerate() {
+            writeln!(output, "{}\t{}", state, distance)?;
+        }
+        output.flush()?;
+        Ok(())
+    }
+}
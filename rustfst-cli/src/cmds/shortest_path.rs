@@ -1,6 +1,7 @@
 use rustfst::prelude::*;
 
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::CliWeight;
 use anyhow::Result;
 
 pub struct ShortestPathAlgorithm {
@@ -8,6 +9,7 @@ pub struct ShortestPathAlgorithm {
     unique: bool,
     nshortest: usize,
     path_out: String,
+    fst_type: String,
 }
 
 impl UnaryFstAlgorithm for ShortestPathAlgorithm {
@@ -19,22 +21,33 @@ impl UnaryFstAlgorithm for ShortestPathAlgorithm {
         self.path_out.as_str()
     }
 
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
     fn get_algorithm_name(&self) -> String {
         "shortest path".to_string()
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>> {
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>> {
         shortest_path(&fst, self.nshortest, self.unique)
     }
 }
 
 impl ShortestPathAlgorithm {
-    pub fn new(path_in: &str, unique: bool, nshortest: usize, path_out: &str) -> Self {
+    pub fn new(
+        path_in: &str,
+        unique: bool,
+        nshortest: usize,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
         Self {
             path_in: path_in.to_string(),
             unique,
             nshortest,
             path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
         }
     }
 }
@@ -0,0 +1,86 @@
+use anyhow::{ensure, Result};
+
+use rustfst::prelude::*;
+use rustfst::StateId;
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{read_state_order, CliWeight};
+
+pub struct StateSortAlgorithm {
+    path_in: String,
+    order: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for StateSortAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "statesort".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let order = read_state_order(&self.order)?;
+        check_order(&order, fst.num_states()).categorize(ErrorCategory::Parse)?;
+        state_sort(&mut fst, &order)?;
+        Ok(fst)
+    }
+}
+
+impl StateSortAlgorithm {
+    pub fn new(path_in: &str, order: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            order: order.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
+
+/// Checks that the order is a permutation of the states of the FST.
+fn check_order(order: &[StateId], num_states: usize) -> Result<()> {
+    ensure!(
+        order.len() == num_states,
+        "The order contains {} states but the FST has {} states",
+        order.len(),
+        num_states
+    );
+    let mut seen = vec![false; num_states];
+    for s in order {
+        ensure!(
+            *s < num_states && !seen[*s],
+            "The order is not a permutation of the states of the FST : state {} is out of range or repeated",
+            s
+        );
+        seen[*s] = true;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_order() {
+        assert!(check_order(&[2, 0, 1], 3).is_ok());
+        assert!(check_order(&[], 0).is_ok());
+        assert!(check_order(&[0, 7], 2).is_err());
+        assert!(check_order(&[1, 1], 2).is_err());
+        assert!(check_order(&[1, 0], 3).is_err());
+        assert!(check_order(&[1, 0, 2], 2).is_err());
+    }
+}
@@ -0,0 +1,463 @@
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::rc::Rc;
+
+use anyhow::{bail, ensure, format_err, Result};
+use log::info;
+
+use rustfst::prelude::*;
+use rustfst::{Label, SymbolTable, EPS_LABEL, EPS_SYMBOL};
+
+use crate::errors::{Categorize, ErrorCategory};
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{
+    create_output, read_fst_types, read_input, read_symbol_table, read_symbol_table_any_format,
+    CliWeight,
+};
+
+/// Writes a symbol table in `text` or `binary` format.
+fn write_symbol_table(symt: &SymbolTable, format: &str, path: &str) -> Result<()> {
+    let mut output = create_output(path)?;
+    match format {
+        "text" => write!(output, "{}", symt)?,
+        "binary" => symt.write_binary(&mut output)?,
+        _ => bail!("Unknown symbol table format : {}", format),
+    };
+    output.flush()?;
+    Ok(())
+}
+
+/// Returns the input or output labels used on the arcs of an FST.
+fn used_labels<W: Semiring + 'static>(fst: &VectorFst<W>, output: bool) -> Result<BTreeSet<Label>> {
+    let mut labels = BTreeSet::new();
+    for s in fst.states_iter() {
+        for arc in fst.arcs_iter(s)? {
+            labels.insert(if output { arc.olabel } else { arc.ilabel });
+        }
+    }
+    Ok(labels)
+}
+
+/// Writes the input or output symbol table of a binary FST. Equivalent of the
+/// `--save_isymbols` and `--save_osymbols` options of OpenFST's `fstsymbols`.
+pub struct SymbolsExtractCommand {
+    path_in: String,
+    path_out: String,
+    output: bool,
+    format: String,
+}
+
+impl SymbolsExtractCommand {
+    pub fn new(path_in: &str, path_out: &str, output: bool, format: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            output,
+            format: format.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Extracting symbol table of {}", self.path_in);
+        let data = read_input(&self.path_in)?;
+        let (fst_type, arc_type) = read_fst_types(&data)?;
+        let symt = dispatch_fst_type!(fst_type.as_str(), arc_type.as_str(), F => {
+            let (_, fst) = F::parse_binary(&data)
+                .map_err(|_| format_err!("Error while parsing binary {} fst", fst_type))
+                .categorize(ErrorCategory::Parse)?;
+            if self.output {
+                Ok(fst.output_symbols())
+            } else {
+                Ok(fst.input_symbols())
+            }
+        })?;
+        let symt = symt
+            .ok_or_else(|| {
+                format_err!(
+                    "The FST has no {} symbol table",
+                    if self.output { "output" } else { "input" }
+                )
+            })
+            .categorize(ErrorCategory::Algorithm)?;
+        write_symbol_table(&symt, &self.format, &self.path_out)
+    }
+}
+
+/// Converts a symbol table between the text and the binary formats.
+pub struct SymbolsConvertCommand {
+    path_in: String,
+    path_out: String,
+    format: String,
+}
+
+impl SymbolsConvertCommand {
+    pub fn new(path_in: &str, path_out: &str, format: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            format: format.to_string(),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        info!("Converting symbol table {}", self.path_in);
+        let symt = read_symbol_table_any_format(&self.path_in)?;
+        write_symbol_table(&symt, &self.format, &self.path_out)
+    }
+}
+
+/// Attaches new symbol tables to an FST or removes them, without changing its labels.
+pub struct SymbolsReplaceAlgorithm {
+    path_in: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    clear_isymbols: bool,
+    clear_osymbols: bool,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for SymbolsReplaceAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "symbols replace".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        if self.clear_isymbols {
+            fst.unset_input_symbols();
+        }
+        if self.clear_osymbols {
+            fst.unset_output_symbols();
+        }
+        if let Some(symt) = read_symbol_table(self.isymbols.as_deref())? {
+            fst.set_input_symbols(symt);
+        }
+        if let Some(symt) = read_symbol_table(self.osymbols.as_deref())? {
+            fst.set_output_symbols(symt);
+        }
+        Ok(fst)
+    }
+}
+
+impl SymbolsReplaceAlgorithm {
+    pub fn new(
+        path_in: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        clear_isymbols: bool,
+        clear_osymbols: bool,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            isymbols: isymbols.map(|s| s.to_string()),
+            osymbols: osymbols.map(|s| s.to_string()),
+            clear_isymbols,
+            clear_osymbols,
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
+
+/// Relabels an FST so that its labels match the symbols of new symbol tables, and attaches
+/// them to the FST. The symbols of the current labels are looked up in the symbol tables
+/// stored in the FST.
+pub struct SymbolsRelabelAlgorithm {
+    path_in: String,
+    isymbols: Option<String>,
+    osymbols: Option<String>,
+    unknown_symbol: Option<String>,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for SymbolsRelabelAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "symbols relabel".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let new_isymt = read_symbol_table(self.isymbols.as_deref())?;
+        let new_osymt = read_symbol_table(self.osymbols.as_deref())?;
+        let ipairs = match &new_isymt {
+            Some(new_symt) => self.relabel_pairs(&fst, fst.input_symbols(), new_symt, false)?,
+            None => vec![],
+        };
+        let opairs = match &new_osymt {
+            Some(new_symt) => self.relabel_pairs(&fst, fst.output_symbols(), new_symt, true)?,
+            None => vec![],
+        };
+        relabel_pairs(&mut fst, ipairs, opairs)?;
+        if let Some(symt) = new_isymt {
+            fst.set_input_symbols(symt);
+        }
+        if let Some(symt) = new_osymt {
+            fst.set_output_symbols(symt);
+        }
+        Ok(fst)
+    }
+}
+
+impl SymbolsRelabelAlgorithm {
+    pub fn new(
+        path_in: &str,
+        isymbols: Option<&str>,
+        osymbols: Option<&str>,
+        unknown_symbol: Option<&str>,
+        path_out: &str,
+        fst_type: &str,
+    ) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            isymbols: isymbols.map(|s| s.to_string()),
+            osymbols: osymbols.map(|s| s.to_string()),
+            unknown_symbol: unknown_symbol.map(|s| s.to_string()),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+
+    /// Maps the labels used in the FST to the labels of their symbol in `new_symt`.
+    fn relabel_pairs<W: Semiring + 'static>(
+        &self,
+        fst: &VectorFst<W>,
+        old_symt: Option<Rc<SymbolTable>>,
+        new_symt: &SymbolTable,
+        output: bool,
+    ) -> Result<Vec<(Label, Label)>> {
+        let side = if output { "output" } else { "input" };
+        let old_symt = old_symt.ok_or_else(|| {
+            format_err!(
+                "The FST has no {} symbol table to find the symbols of its labels",
+                side
+            )
+        })?;
+        let unknown_label = match &self.unknown_symbol {
+            Some(unknown_symbol) => {
+                Some(new_symt.get_label(unknown_symbol.as_str()).ok_or_else(|| {
+                    format_err!(
+                        "Unknown symbol {:?} not found in the new {} symbol table",
+                        unknown_symbol,
+                        side
+                    )
+                })?)
+            }
+            None => None,
+        };
+        used_labels(fst, output)?
+            .into_iter()
+            .filter(|label| *label != EPS_LABEL)
+            .map(|label| {
+                let symbol = old_symt.get_symbol(label).ok_or_else(|| {
+                    format_err!(
+                        "Label {} not found in the {} symbol table of the FST",
+                        label,
+                        side
+                    )
+                })?;
+                let new_label = new_symt
+                    .get_label(symbol)
+                    .or(unknown_label)
+                    .ok_or_else(|| {
+                        format_err!(
+                            "Symbol {:?} not found in the new {} symbol table",
+                            symbol,
+                            side
+                        )
+                    })?;
+                Ok((label, new_label))
+            })
+            .collect()
+    }
+}
+
+/// Removes the symbols that don't appear on the arcs of an FST from its symbol tables and
+/// relabels the FST so that its labels are contiguous. Epsilon keeps the label 0.
+pub struct SymbolsCompactAlgorithm {
+    path_in: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl UnaryFstAlgorithm for SymbolsCompactAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "symbols compact".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(&self, mut fst: VectorFst<W>) -> Result<VectorFst<W>> {
+        let isymt = fst.input_symbols();
+        let osymt = fst.output_symbols();
+        ensure!(
+            isymt.is_some() || osymt.is_some(),
+            "The FST has no symbol table to compact"
+        );
+        let mut ipairs = vec![];
+        let mut opairs = vec![];
+        if let Some(symt) = isymt {
+            let (pairs, compact_symt) = compact_symbol_table(&fst, &symt, false)?;
+            ipairs = pairs;
+            fst.set_input_symbols(Rc::new(compact_symt));
+        }
+        if let Some(symt) = osymt {
+            let (pairs, compact_symt) = compact_symbol_table(&fst, &symt, true)?;
+            opairs = pairs;
+            fst.set_output_symbols(Rc::new(compact_symt));
+        }
+        relabel_pairs(&mut fst, ipairs, opairs)?;
+        Ok(fst)
+    }
+}
+
+impl SymbolsCompactAlgorithm {
+    pub fn new(path_in: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
+
+/// Returns the symbol table restricted to the labels used in the FST along with the mapping
+/// from the old labels to the new ones.
+fn compact_symbol_table<W: Semiring + 'static>(
+    fst: &VectorFst<W>,
+    symt: &SymbolTable,
+    output: bool,
+) -> Result<(Vec<(Label, Label)>, SymbolTable)> {
+    let mut labels = used_labels(fst, output)?;
+    labels.insert(EPS_LABEL);
+    let mut compact_symt = SymbolTable::empty();
+    let mut pairs = vec![];
+    for label in labels {
+        let symbol = match symt.get_symbol(label) {
+            Some(symbol) => symbol,
+            None if label == EPS_LABEL => EPS_SYMBOL,
+            None => bail!("Label {} not found in the symbol table of the FST", label),
+        };
+        pairs.push((label, compact_symt.add_symbol(symbol)));
+    }
+    Ok((pairs, compact_symt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol_table(symbols: &[&str]) -> SymbolTable {
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(symbols.iter().copied());
+        symt
+    }
+
+    /// FST reading `b d` (labels 2 and 4) and writing `c` (label 3).
+    fn fst() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_arc(0, Arc::new(2, 0, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(4, 3, TropicalWeight::one(), 2))?;
+        fst.set_final(2, TropicalWeight::one())?;
+        Ok(fst)
+    }
+
+    fn relabel_algorithm(unknown_symbol: Option<&str>) -> SymbolsRelabelAlgorithm {
+        SymbolsRelabelAlgorithm::new("-", None, None, unknown_symbol, "-", "vector")
+    }
+
+    #[test]
+    fn test_compact_symbol_table() -> Result<()> {
+        let fst = fst()?;
+        let symt = symbol_table(&["a", "b", "c", "d"]);
+
+        let (pairs, compact_symt) = compact_symbol_table(&fst, &symt, false)?;
+        assert_eq!(pairs, vec![(0, 0), (2, 1), (4, 2)]);
+        assert_eq!(compact_symt.get_symbol(EPS_LABEL), Some(EPS_SYMBOL));
+        assert_eq!(compact_symt.get_label("b"), Some(1));
+        assert_eq!(compact_symt.get_label("d"), Some(2));
+        assert_eq!(compact_symt.len(), 3);
+
+        let (pairs, compact_symt) = compact_symbol_table(&fst, &symt, true)?;
+        assert_eq!(pairs, vec![(0, 0), (3, 1)]);
+        assert_eq!(compact_symt.get_symbol(EPS_LABEL), Some(EPS_SYMBOL));
+        assert_eq!(compact_symt.get_label("c"), Some(1));
+        assert_eq!(compact_symt.len(), 2);
+
+        let symt = symbol_table(&["a", "b"]);
+        assert!(compact_symbol_table(&fst, &symt, false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_relabel_pairs() -> Result<()> {
+        let fst = fst()?;
+        let old_symt = Rc::new(symbol_table(&["a", "b", "c", "d"]));
+        let new_symt = symbol_table(&["d", "b", "<unk>"]);
+
+        let pairs = relabel_algorithm(None).relabel_pairs(
+            &fst,
+            Some(Rc::clone(&old_symt)),
+            &new_symt,
+            false,
+        )?;
+        assert_eq!(pairs, vec![(2, 2), (4, 1)]);
+
+        // `c` is not in the new symbol table.
+        assert!(relabel_algorithm(None)
+            .relabel_pairs(&fst, Some(Rc::clone(&old_symt)), &new_symt, true)
+            .is_err());
+        let pairs = relabel_algorithm(Some("<unk>")).relabel_pairs(
+            &fst,
+            Some(Rc::clone(&old_symt)),
+            &new_symt,
+            true,
+        )?;
+        assert_eq!(pairs, vec![(3, 3)]);
+
+        assert!(relabel_algorithm(Some("<oov>"))
+            .relabel_pairs(&fst, Some(Rc::clone(&old_symt)), &new_symt, true)
+            .is_err());
+        assert!(relabel_algorithm(None)
+            .relabel_pairs(&fst, None, &new_symt, false)
+            .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+
+use rustfst::prelude::*;
+
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
+use crate::utils::CliWeight;
+
+pub struct UnionAlgorithm {
+    path_in_1: String,
+    path_in_2: String,
+    path_out: String,
+    fst_type: String,
+}
+
+impl BinaryFstAlgorithm for UnionAlgorithm {
+    fn get_path_in_1(&self) -> &str {
+        self.path_in_1.as_str()
+    }
+
+    fn get_path_in_2(&self) -> &str {
+        self.path_in_2.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_fst_type(&self) -> &str {
+        self.fst_type.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "union".to_string()
+    }
+
+    fn run_algorithm<W: CliWeight>(
+        &self,
+        mut fst_1: VectorFst<W>,
+        fst_2: VectorFst<W>,
+    ) -> Result<VectorFst<W>> {
+        union(&mut fst_1, &fst_2)?;
+        Ok(fst_1)
+    }
+}
+
+impl UnionAlgorithm {
+    pub fn new(path_in_1: &str, path_in_2: &str, path_out: &str, fst_type: &str) -> Self {
+        Self {
+            path_in_1: path_in_1.to_string(),
+            path_in_2: path_in_2.to_string(),
+            path_out: path_out.to_string(),
+            fst_type: fst_type.to_string(),
+        }
+    }
+}
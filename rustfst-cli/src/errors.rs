@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use anyhow::Result;
+use exitcode::ExitCode;
+
+/// Category of the errors reported by the CLI. Each category has its own exit code so that
+/// scripts can tell the failures apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Reading or writing a file or a standard stream failed.
+    Io,
+    /// An input (FST, symbol table, weight, text file...) is malformed or unsupported.
+    Parse,
+    /// The inputs don't satisfy the preconditions of an algorithm or the algorithm failed.
+    Algorithm,
+}
+
+impl ErrorCategory {
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            ErrorCategory::Io => exitcode::IOERR,
+            ErrorCategory::Parse => exitcode::DATAERR,
+            ErrorCategory::Algorithm => exitcode::SOFTWARE,
+        }
+    }
+
+    /// Returns the category attached to an error. Errors without a category are considered
+    /// as I/O errors if caused by an `io::Error` and as algorithm errors otherwise.
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<CategorizedError>())
+            .map(|e| e.category)
+            .unwrap_or_else(|| {
+                if is_io_error(error) {
+                    ErrorCategory::Io
+                } else {
+                    ErrorCategory::Algorithm
+                }
+            })
+    }
+}
+
+fn is_io_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<io::Error>())
+}
+
+/// Error carrying its category. It is transparent : it displays as the wrapped error and
+/// exposes the same causes.
+#[derive(Debug)]
+struct CategorizedError {
+    category: ErrorCategory,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for CategorizedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Error for CategorizedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Attaches an `ErrorCategory` to the error of a `Result`.
+pub trait Categorize<T> {
+    /// The category of an error that already has one is kept, so that the innermost
+    /// category wins. An error caused by an `io::Error` is always an I/O error.
+    fn categorize(self, category: ErrorCategory) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> Categorize<T> for std::result::Result<T, E> {
+    fn categorize(self, category: ErrorCategory) -> Result<T> {
+        self.map_err(|e| {
+            let error = e.into();
+            if error.chain().any(|cause| cause.is::<CategorizedError>()) {
+                return error;
+            }
+            let category = if is_io_error(&error) {
+                ErrorCategory::Io
+            } else {
+                category
+            };
+            CategorizedError { category, error }.into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::{format_err, Context};
+
+    fn categorized(category: ErrorCategory) -> anyhow::Error {
+        Err::<(), _>(format_err!("Error"))
+            .categorize(category)
+            .unwrap_err()
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(ErrorCategory::Io.exit_code(), 74);
+        assert_eq!(ErrorCategory::Parse.exit_code(), 65);
+        assert_eq!(ErrorCategory::Algorithm.exit_code(), 70);
+    }
+
+    #[test]
+    fn test_categorize() {
+        for category in [
+            ErrorCategory::Io,
+            ErrorCategory::Parse,
+            ErrorCategory::Algorithm,
+        ] {
+            let error = categorized(category);
+            assert_eq!(ErrorCategory::of(&error), category);
+            assert_eq!(error.to_string(), "Error");
+        }
+    }
+
+    #[test]
+    fn test_uncategorized_error_is_algorithm() {
+        let error = format_err!("Error");
+        assert_eq!(ErrorCategory::of(&error), ErrorCategory::Algorithm);
+    }
+
+    #[test]
+    fn test_innermost_category_wins() {
+        let error = Err::<(), _>(categorized(ErrorCategory::Parse))
+            .context("Context")
+            .categorize(ErrorCategory::Algorithm)
+            .unwrap_err();
+        assert_eq!(ErrorCategory::of(&error), ErrorCategory::Parse);
+        assert_eq!(error.to_string(), "Context");
+    }
+
+    #[test]
+    fn test_io_error_is_always_io() {
+        let io_error = || io::Error::new(io::ErrorKind::NotFound, "Not found");
+
+        let error = Err::<(), _>(io_error())
+            .categorize(ErrorCategory::Parse)
+            .unwrap_err();
+        assert_eq!(ErrorCategory::of(&error), ErrorCategory::Io);
+
+        let error = Err::<(), _>(io_error())
+            .context("Context")
+            .categorize(ErrorCategory::Algorithm)
+            .unwrap_err();
+        assert_eq!(ErrorCategory::of(&error), ErrorCategory::Io);
+
+        let error = anyhow::Error::new(io_error()).context("Context");
+        assert_eq!(ErrorCategory::of(&error), ErrorCategory::Io);
+    }
+}
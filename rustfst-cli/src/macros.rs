@@ -0,0 +1,65 @@
+/// Binds the concrete FST type matching the `fst_type` and `arc_type` strings to `$fst` and
+/// evaluates `$body` with it. The body must return a `Result`.
+macro_rules! dispatch_fst_type {
+    ($fst_type:expr, $arc_type:expr, $fst:ident => $body:expr) => {
+        match ($fst_type, $arc_type) {
+            ("vector", "standard") => {
+                type $fst = rustfst::prelude::VectorFst<rustfst::prelude::TropicalWeight>;
+                $body
+            }
+            ("vector", "log") => {
+                type $fst = rustfst::prelude::VectorFst<rustfst::prelude::LogWeight>;
+                $body
+            }
+            ("vector", "log64") => {
+                type $fst = rustfst::prelude::VectorFst<rustfst::prelude::LogWeight64>;
+                $body
+            }
+            ("const", "standard") => {
+                type $fst = rustfst::prelude::ConstFst<rustfst::prelude::TropicalWeight>;
+                $body
+            }
+            ("const", "log") => {
+                type $fst = rustfst::prelude::ConstFst<rustfst::prelude::LogWeight>;
+                $body
+            }
+            ("const", "log64") => {
+                type $fst = rustfst::prelude::ConstFst<rustfst::prelude::LogWeight64>;
+                $body
+            }
+            (fst_type, arc_type) => crate::errors::Categorize::categorize(
+                Err(anyhow::format_err!(
+                    "Unsupported FST type {} with arc type {}",
+                    fst_type,
+                    arc_type
+                )),
+                crate::errors::ErrorCategory::Parse,
+            ),
+        }
+    };
+}
+
+/// Binds the weight matching the `arc_type` string to `$weight` and evaluates `$body` with it.
+/// The body must return a `Result`.
+macro_rules! dispatch_weight_type {
+    ($arc_type:expr, $weight:ident => $body:expr) => {
+        match $arc_type {
+            "standard" => {
+                type $weight = rustfst::prelude::TropicalWeight;
+                $body
+            }
+            "log" => {
+                type $weight = rustfst::prelude::LogWeight;
+                $body
+            }
+            "log64" => {
+                type $weight = rustfst::prelude::LogWeight64;
+                $body
+            }
+            arc_type => crate::errors::Categorize::categorize(
+                Err(anyhow::format_err!("Unsupported arc type {}", arc_type)),
+                crate::errors::ErrorCategory::Parse,
+            ),
+        }
+    };
+}
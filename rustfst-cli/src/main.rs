@@ -1,30 +1,74 @@
 use std::process;
 
-use clap::{App, Arg, SubCommand};
 use anyhow::{format_err, Result};
+use clap::{value_t_or_exit, App, Arg, SubCommand};
 use log::error;
 
+use rustfst::DrawingConfig;
+
+use crate::binary_fst_algorithm::BinaryFstAlgorithm;
 use crate::cmds::arcsort::ArcsortAlgorithm;
+use crate::cmds::bench::{BenchCompareCommand, BenchSuiteCommand};
+use crate::cmds::closure::ClosureAlgorithm;
+use crate::cmds::compile::CompileCommand;
+use crate::cmds::compile_strings::CompileStringsCommand;
+use crate::cmds::compose::ComposeAlgorithm;
+use crate::cmds::concat::ConcatAlgorithm;
+use crate::cmds::condense::CondenseAlgorithm;
 use crate::cmds::connect::ConnectAlgorithm;
+use crate::cmds::determinize::DeterminizeAlgorithm;
+use crate::cmds::draw::DrawCommand;
+use crate::cmds::encode::EncodeAlgorithm;
+use crate::cmds::info::InfoCommand;
+use crate::cmds::intersect::IntersectAlgorithm;
 use crate::cmds::invert::InvertAlgorithm;
 use crate::cmds::map::MapAlgorithm;
 use crate::cmds::minimize::MinimizeAlgorithm;
+use crate::cmds::print::PrintCommand;
+use crate::cmds::print_strings::PrintStringsCommand;
 use crate::cmds::project::ProjectFstAlgorithm;
 use crate::cmds::push::PushAlgorithm;
+use crate::cmds::relabel::RelabelAlgorithm;
+use crate::cmds::replace::ReplaceCommand;
 use crate::cmds::reverse::ReverseAlgorithm;
+use crate::cmds::reweight::ReweightAlgorithm;
+use crate::cmds::rm_epsilon::RmEpsilonAlgorithm;
 use crate::cmds::rm_final_epsilon::RmFinalEpsilonAlgorithm;
+use crate::cmds::shortest_distance::ShortestDistanceCommand;
 use crate::cmds::shortest_path::ShortestPathAlgorithm;
+use crate::cmds::state_sort::StateSortAlgorithm;
+use crate::cmds::symbols::{
+    SymbolsCompactAlgorithm, SymbolsConvertCommand, SymbolsExtractCommand, SymbolsRelabelAlgorithm,
+    SymbolsReplaceAlgorithm,
+};
 use crate::cmds::topsort::TopsortAlgorithm;
+use crate::cmds::union::UnionAlgorithm;
+use crate::errors::ErrorCategory;
 use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+use crate::utils::{TokenType, STD_STREAM};
+
+#[macro_use]
+mod macros;
 
+pub mod binary_fst_algorithm;
 pub mod cmds;
+pub mod errors;
 pub mod unary_fst_algorithm;
+pub mod utils;
 
 fn main() {
     let mut app = App::new("rustfst")
         .version("1.0")
         .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
-        .about("Rustfst CLI");
+        .about("Rustfst CLI")
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .multiple(true)
+                .global(true)
+                .help("Logs more information on stderr : -v for info, -vv for debug. Only errors and warnings are logged by default."),
+        );
 
     // Minimization
     let minimize_cmd = SubCommand::with_name("minimize")
@@ -137,17 +181,697 @@ fn main() {
         .arg(Arg::with_name("remove_common_affix").long("remove_common_affix"));
     app = app.subcommand(one_in_one_out_options(push_cmd));
 
+    // Determinize
+    let determinize_cmd = SubCommand::with_name("determinize")
+        .about("Determinization algorithm.")
+        .arg(
+            Arg::with_name("det_type")
+                .long("det_type")
+                .takes_value(true)
+                .possible_values(&["functional", "nonfunctional", "disambiguate"])
+                .default_value("functional")
+                .help("Type of determinization."),
+        );
+    app = app.subcommand(one_in_one_out_options(determinize_cmd));
+
+    // Rm Epsilon
+    let rm_epsilon_cmd = SubCommand::with_name("rmepsilon")
+        .about("RmEpsilon algorithm.")
+        .arg(
+            Arg::with_name("connect")
+                .long("connect")
+                .takes_value(true)
+                .possible_values(&["true", "false"])
+                .default_value("true")
+                .help("Trim output."),
+        )
+        .arg(
+            Arg::with_name("weight_threshold")
+                .long("weight_threshold")
+                .takes_value(true)
+                .help("Weight threshold."),
+        )
+        .arg(
+            Arg::with_name("nstate")
+                .long("nstate")
+                .takes_value(true)
+                .help("State number threshold."),
+        );
+    app = app.subcommand(one_in_one_out_options(rm_epsilon_cmd));
+
+    // Closure
+    let closure_cmd = SubCommand::with_name("closure")
+        .about("Closure algorithm.")
+        .arg(
+            Arg::with_name("closure_type")
+                .long("closure_type")
+                .takes_value(true)
+                .possible_values(&["star", "plus"])
+                .default_value("star")
+                .help("Kleene star or plus closure."),
+        );
+    app = app.subcommand(one_in_one_out_options(closure_cmd));
+
+    // Condense
+    let condense_cmd = SubCommand::with_name("condense")
+        .about("Condenses each strongly connected component into a single state.");
+    app = app.subcommand(one_in_one_out_options(condense_cmd));
+
+    // Relabel
+    let relabel_cmd = SubCommand::with_name("relabel")
+        .about("Relabels the input and/or output labels of an FST.")
+        .arg(
+            Arg::with_name("relabel_ipairs")
+                .long("relabel_ipairs")
+                .takes_value(true)
+                .help("File containing the input relabeling pairs."),
+        )
+        .arg(
+            Arg::with_name("relabel_opairs")
+                .long("relabel_opairs")
+                .takes_value(true)
+                .help("File containing the output relabeling pairs."),
+        );
+    app = app.subcommand(one_in_one_out_options(relabel_cmd));
+
+    // Reweight
+    let reweight_cmd = SubCommand::with_name("reweight")
+        .about("Reweights an FST according to a vector of potentials.")
+        .arg(
+            Arg::with_name("potentials")
+                .long("potentials")
+                .takes_value(true)
+                .required(true)
+                .help("File containing one `state weight` pair per line."),
+        )
+        .arg(
+            Arg::with_name("to_final")
+                .long("to_final")
+                .help("Push/reweight to final (vs. to initial) states."),
+        );
+    app = app.subcommand(one_in_one_out_options(reweight_cmd));
+
+    // State Sort
+    let state_sort_cmd = SubCommand::with_name("statesort")
+        .about("Sorts the states of an FST.")
+        .arg(
+            Arg::with_name("order")
+                .long("order")
+                .takes_value(true)
+                .required(true)
+                .help("File containing the new id of the state `s` on the line `s`."),
+        );
+    app = app.subcommand(one_in_one_out_options(state_sort_cmd));
+
+    // Encode
+    let encode_cmd = SubCommand::with_name("encode")
+        .about("Encodes the labels and/or the weights of an FST.")
+        .arg(
+            Arg::with_name("codex")
+                .long("codex")
+                .takes_value(true)
+                .required(true)
+                .help("Path of the file where the encode table is written."),
+        )
+        .arg(
+            Arg::with_name("encode_labels")
+                .long("encode_labels")
+                .help("Encode output labels."),
+        )
+        .arg(
+            Arg::with_name("encode_weights")
+                .long("encode_weights")
+                .help("Encode weights."),
+        );
+    app = app.subcommand(one_in_one_out_options(encode_cmd));
+
+    // Decode
+    let decode_cmd = SubCommand::with_name("decode")
+        .about("Decodes an FST encoded with the encode command.")
+        .arg(
+            Arg::with_name("codex")
+                .long("codex")
+                .takes_value(true)
+                .required(true)
+                .help("Path of the encode table."),
+        );
+    app = app.subcommand(one_in_one_out_options(decode_cmd));
+
+    // Replace
+    let replace_cmd = SubCommand::with_name("replace")
+        .about("Recursively replaces arcs in the root FST with other FSTs.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("fsts")
+                .help("root.fst root_label [rule.fst rule_label]* out.fst")
+                .multiple(true)
+                .min_values(3)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("epsilon_on_replace")
+                .long("epsilon_on_replace")
+                .help("Call/return arcs are epsilon arcs."),
+        )
+        .arg(
+            Arg::with_name("fst_type")
+                .long("fst_type")
+                .takes_value(true)
+                .possible_values(&["vector", "const"])
+                .default_value("vector")
+                .help("Output FST type."),
+        );
+    app = app.subcommand(replace_cmd);
+
+    // Shortest Distance
+    let shortest_distance_cmd = SubCommand::with_name("shortestdistance")
+        .about("Shortest Distance algorithm.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.fst")
+                .help("Path to input fst file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("distance.txt")
+                .help("Path to output distance file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("reverse")
+                .long("reverse")
+                .help("Perform in the reverse direction."),
+        );
+    app = app.subcommand(shortest_distance_cmd);
+
+    // Compose
+    let compose_cmd = SubCommand::with_name("compose")
+        .about("Compose algorithm.")
+        .arg(
+            Arg::with_name("compose_filter")
+                .long("compose_filter")
+                .takes_value(true)
+                .possible_values(&[
+                    "auto",
+                    "null",
+                    "trivial",
+                    "sequence",
+                    "alt_sequence",
+                    "match",
+                    "no_match",
+                ])
+                .default_value("auto")
+                .help("Composition filter."),
+        )
+        .arg(
+            Arg::with_name("connect")
+                .long("connect")
+                .takes_value(true)
+                .possible_values(&["true", "false"])
+                .default_value("true")
+                .help("Trim output."),
+        );
+    app = app.subcommand(two_in_one_out_options(compose_cmd));
+
+    // Intersect
+    let intersect_cmd = SubCommand::with_name("intersect")
+        .about("Intersection of two acceptors.")
+        .arg(
+            Arg::with_name("connect")
+                .long("connect")
+                .takes_value(true)
+                .possible_values(&["true", "false"])
+                .default_value("true")
+                .help("Trim output."),
+        );
+    app = app.subcommand(two_in_one_out_options(intersect_cmd));
+
+    // Union
+    let union_cmd = SubCommand::with_name("union").about("Union algorithm.");
+    app = app.subcommand(two_in_one_out_options(union_cmd));
+
+    // Concat
+    let concat_cmd = SubCommand::with_name("concat").about("Concatenation algorithm.");
+    app = app.subcommand(two_in_one_out_options(concat_cmd));
+
+    // Compile
+    let compile_cmd = SubCommand::with_name("compile")
+        .about("Compiles an FST from its text representation (AT&T format).")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.txt")
+                .help("Path to input text file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("out.fst")
+                .help("Path to output fst file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("isymbols")
+                .long("isymbols")
+                .takes_value(true)
+                .help("Input label symbol table."),
+        )
+        .arg(
+            Arg::with_name("osymbols")
+                .long("osymbols")
+                .takes_value(true)
+                .help("Output label symbol table."),
+        )
+        .arg(
+            Arg::with_name("acceptor")
+                .long("acceptor")
+                .help("Input in acceptor format."),
+        )
+        .arg(
+            Arg::with_name("keep_isymbols")
+                .long("keep_isymbols")
+                .help("Store input label symbol table with FST."),
+        )
+        .arg(
+            Arg::with_name("keep_osymbols")
+                .long("keep_osymbols")
+                .help("Store output label symbol table with FST."),
+        )
+        .arg(
+            Arg::with_name("arc_type")
+                .long("arc_type")
+                .takes_value(true)
+                .possible_values(&["standard", "log", "log64"])
+                .default_value("standard")
+                .help("Output arc type."),
+        )
+        .arg(
+            Arg::with_name("fst_type")
+                .long("fst_type")
+                .takes_value(true)
+                .possible_values(&["vector", "const"])
+                .default_value("vector")
+                .help("Output FST type."),
+        );
+    app = app.subcommand(compile_cmd);
+
+    // Print
+    let print_cmd = SubCommand::with_name("print")
+        .about("Prints a binary FST in text format (AT&T format).")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.fst")
+                .help("Path to input fst file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("out.txt")
+                .help("Path to output text file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("isymbols")
+                .long("isymbols")
+                .takes_value(true)
+                .help("Input label symbol table. Defaults to the one stored in the FST."),
+        )
+        .arg(
+            Arg::with_name("osymbols")
+                .long("osymbols")
+                .takes_value(true)
+                .help("Output label symbol table. Defaults to the one stored in the FST."),
+        )
+        .arg(
+            Arg::with_name("acceptor")
+                .long("acceptor")
+                .help("Print in acceptor format."),
+        )
+        .arg(
+            Arg::with_name("show_weight_one")
+                .long("show_weight_one")
+                .help("Print weights equal to one."),
+        );
+    app = app.subcommand(print_cmd);
+
+    // Info
+    let info_cmd = SubCommand::with_name("info")
+        .about("Prints out information about an FST.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.fst")
+                .help("Path to input fst file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        );
+    app = app.subcommand(info_cmd);
+
+    // Draw
+    let draw_cmd = SubCommand::with_name("draw")
+        .about("Prints a binary FST in DOT format (GraphViz).")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.fst")
+                .help("Path to input fst file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("out.dot")
+                .help("Path to output dot file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("isymbols")
+                .long("isymbols")
+                .takes_value(true)
+                .help("Input label symbol table. Defaults to the one stored in the FST."),
+        )
+        .arg(
+            Arg::with_name("osymbols")
+                .long("osymbols")
+                .takes_value(true)
+                .help("Output label symbol table. Defaults to the one stored in the FST."),
+        )
+        .arg(
+            Arg::with_name("acceptor")
+                .long("acceptor")
+                .help("Draw in acceptor format."),
+        )
+        .arg(
+            Arg::with_name("show_weight_one")
+                .long("show_weight_one")
+                .help("Draw weights equal to one."),
+        )
+        .arg(
+            Arg::with_name("portrait")
+                .long("portrait")
+                .help("Portrait mode (def: landscape)."),
+        )
+        .arg(
+            Arg::with_name("vertical")
+                .long("vertical")
+                .help("Draw bottom-to-top instead of left-to-right."),
+        )
+        .arg(
+            Arg::with_name("ranksep")
+                .long("ranksep")
+                .takes_value(true)
+                .help("Minimum separation between ranks (see dot documentation)."),
+        )
+        .arg(
+            Arg::with_name("nodesep")
+                .long("nodesep")
+                .takes_value(true)
+                .help("Minimum separation between nodes (see dot documentation)."),
+        )
+        .arg(
+            Arg::with_name("fontsize")
+                .long("fontsize")
+                .takes_value(true)
+                .default_value("14")
+                .help("Font size."),
+        )
+        .arg(
+            Arg::with_name("title")
+                .long("title")
+                .takes_value(true)
+                .default_value("")
+                .help("Figure title."),
+        );
+    app = app.subcommand(draw_cmd);
+
+    // Compile strings
+    let compile_strings_cmd = SubCommand::with_name("compilestrings")
+        .about("Compiles each line of a text file into a linear FST and writes their union. A line containing a tab is compiled into a transducer from the string before the tab to the string after it.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.txt")
+                .help("Path to input text file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("out.fst")
+                .help("Path to output fst file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(token_type_option())
+        .arg(
+            Arg::with_name("symbols")
+                .long("symbols")
+                .takes_value(true)
+                .help("Label symbol table, used with the symbol token type. Without it, the tokens must be labels."),
+        )
+        .arg(
+            Arg::with_name("unknown_symbol")
+                .long("unknown_symbol")
+                .takes_value(true)
+                .help("Symbol used for the tokens missing in the symbol table."),
+        )
+        .arg(
+            Arg::with_name("keep_symbols")
+                .long("keep_symbols")
+                .help("Store the symbol table with FST."),
+        )
+        .arg(
+            Arg::with_name("arc_type")
+                .long("arc_type")
+                .takes_value(true)
+                .possible_values(&["standard", "log", "log64"])
+                .default_value("standard")
+                .help("Output arc type."),
+        )
+        .arg(
+            Arg::with_name("fst_type")
+                .long("fst_type")
+                .takes_value(true)
+                .possible_values(&["vector", "const"])
+                .default_value("vector")
+                .help("Output FST type."),
+        );
+    app = app.subcommand(compile_strings_cmd);
+
+    // Print strings
+    let print_strings_cmd = SubCommand::with_name("printstrings")
+        .about("Prints the paths of an FST as strings followed by their weight.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in.fst")
+                .help("Path to input fst file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(
+            Arg::with_name("out.txt")
+                .help("Path to output text file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        )
+        .arg(token_type_option())
+        .arg(
+            Arg::with_name("isymbols")
+                .long("isymbols")
+                .takes_value(true)
+                .help("Input label symbol table. Defaults to the one stored in the FST."),
+        )
+        .arg(
+            Arg::with_name("osymbols")
+                .long("osymbols")
+                .takes_value(true)
+                .help("Output label symbol table. Defaults to the one stored in the FST."),
+        )
+        .arg(
+            Arg::with_name("nshortest")
+                .long("nshortest")
+                .takes_value(true)
+                .help("Only print the n shortest paths. Required for cyclic FSTs."),
+        )
+        .arg(
+            Arg::with_name("unique")
+                .long("unique")
+                .help("Only print distinct strings with --nshortest."),
+        );
+    app = app.subcommand(print_strings_cmd);
+
+    // Bench
+    let bench_cmd = SubCommand::with_name("bench")
+        .about("Runs suites of benchmarks and compares their results.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .subcommand(
+            SubCommand::with_name("run")
+                .about("Runs a suite of benchmarks described in JSON. Each benchmark is run on each input FST for each set of parameters, every run being a separate process.")
+                .arg(
+                    Arg::with_name("suite.json")
+                        .help("Path to the suite of benchmarks.")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("results")
+                        .help("Path to the output results. Writes to stdout if missing or `-`.")
+                        .default_value(STD_STREAM),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .takes_value(true)
+                        .possible_values(&["json", "csv"])
+                        .default_value("json")
+                        .help("Format of the results."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("compare")
+                .about("Compares two results of `bench run` in JSON format.")
+                .arg(
+                    Arg::with_name("baseline.json")
+                        .help("Path to the reference results.")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("current.json")
+                        .help("Path to the results to compare with the reference.")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("threshold")
+                        .long("threshold")
+                        .takes_value(true)
+                        .default_value("5")
+                        .help("Increase of the mean time or of the peak memory, in percents, above which a benchmark is flagged as a regression."),
+                )
+                .arg(
+                    Arg::with_name("fail_on_regression")
+                        .long("fail_on_regression")
+                        .help("Exit with an error if a regression is found."),
+                ),
+        );
+    app = app.subcommand(bench_cmd);
+
+    // Symbols
+    let symbols_format_option = Arg::with_name("format")
+        .long("format")
+        .takes_value(true)
+        .possible_values(&["text", "binary"])
+        .default_value("text")
+        .help("Format of the output symbol table.");
+    let symbols_cmd = SubCommand::with_name("symbols")
+        .about("Manipulates symbol tables and the symbol tables attached to FSTs. Symbol tables are read in text or binary format.")
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .subcommand(
+            SubCommand::with_name("extract")
+                .about("Writes the input or output symbol table of an FST.")
+                .arg(
+                    Arg::with_name("in.fst")
+                        .help("Path to input fst file. Reads stdin if missing or `-`.")
+                        .default_value(STD_STREAM),
+                )
+                .arg(
+                    Arg::with_name("out.syms")
+                        .help("Path to output symbol table. Writes to stdout if missing or `-`.")
+                        .default_value(STD_STREAM),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .help("Extract the output symbol table (vs. input)."),
+                )
+                .arg(symbols_format_option.clone()),
+        )
+        .subcommand(one_in_one_out_options(
+            SubCommand::with_name("replace")
+                .about("Attaches symbol tables to an FST or removes them, without changing its labels.")
+                .arg(
+                    Arg::with_name("isymbols")
+                        .long("isymbols")
+                        .takes_value(true)
+                        .help("Input symbol table to attach."),
+                )
+                .arg(
+                    Arg::with_name("osymbols")
+                        .long("osymbols")
+                        .takes_value(true)
+                        .help("Output symbol table to attach."),
+                )
+                .arg(
+                    Arg::with_name("clear_isymbols")
+                        .long("clear_isymbols")
+                        .conflicts_with("isymbols")
+                        .help("Remove the input symbol table."),
+                )
+                .arg(
+                    Arg::with_name("clear_osymbols")
+                        .long("clear_osymbols")
+                        .conflicts_with("osymbols")
+                        .help("Remove the output symbol table."),
+                ),
+        ))
+        .subcommand(one_in_one_out_options(
+            SubCommand::with_name("relabel")
+                .about("Relabels an FST to match the symbols of new symbol tables and attaches them. The current symbols are read from the symbol tables of the FST.")
+                .arg(
+                    Arg::with_name("isymbols")
+                        .long("isymbols")
+                        .takes_value(true)
+                        .help("New input symbol table."),
+                )
+                .arg(
+                    Arg::with_name("osymbols")
+                        .long("osymbols")
+                        .takes_value(true)
+                        .help("New output symbol table."),
+                )
+                .arg(
+                    Arg::with_name("unknown_symbol")
+                        .long("unknown_symbol")
+                        .takes_value(true)
+                        .help("Symbol of the new symbol tables used for the symbols missing in them."),
+                ),
+        ))
+        .subcommand(one_in_one_out_options(
+            SubCommand::with_name("compact")
+                .about("Removes the symbols not used by an FST from its symbol tables and relabels it with contiguous labels."),
+        ))
+        .subcommand(
+            SubCommand::with_name("convert")
+                .about("Converts a symbol table between the text and binary formats.")
+                .arg(
+                    Arg::with_name("in.syms")
+                        .help("Path to input symbol table. Reads stdin if missing or `-`.")
+                        .default_value(STD_STREAM),
+                )
+                .arg(
+                    Arg::with_name("out.syms")
+                        .help("Path to output symbol table. Writes to stdout if missing or `-`.")
+                        .default_value(STD_STREAM),
+                )
+                .arg(symbols_format_option),
+        );
+    app = app.subcommand(symbols_cmd);
+
     let matches = app.get_matches();
 
-    let env = env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "debug");
+    let verbosity = matches
+        .subcommand()
+        .1
+        .map_or(0, |m| m.occurrences_of("verbose"))
+        .max(matches.occurrences_of("verbose"));
+    let log_level = match verbosity {
+        0 => "warn",
+        1 => "info",
+        _ => "debug",
+    };
+    let env = env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, log_level);
 
     env_logger::Builder::from_env(env)
         .default_format_timestamp_nanos(true)
         .init();
 
     if let Err(e) = handle(matches) {
-        error!("{:?}", e);
-        process::exit(exitcode::OK)
+        error!("{:#}", e);
+        process::exit(ErrorCategory::of(&e).exit_code())
     }
 }
 
@@ -158,38 +882,45 @@ fn handle(matches: clap::ArgMatches) -> Result<()> {
             m.value_of("in.fst").unwrap(),
             m.is_present("allow_nondet"),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("connect", Some(m)) => ConnectAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("arcsort", Some(m)) => ArcsortAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("sort_type").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("project", Some(m)) => ProjectFstAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.is_present("project_output"),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("invert", Some(m)) => InvertAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("topsort", Some(m)) => TopsortAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("reverse", Some(m)) => ReverseAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("map", Some(m)) => MapAlgorithm::new(
@@ -197,6 +928,7 @@ fn handle(matches: clap::ArgMatches) -> Result<()> {
             m.value_of("map_type").unwrap(),
             m.value_of("weight"),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("shortestpath", Some(m)) => ShortestPathAlgorithm::new(
@@ -204,16 +936,19 @@ fn handle(matches: clap::ArgMatches) -> Result<()> {
             m.is_present("unique"),
             m.value_of("nshortest").unwrap().parse().unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("rmfinalepsilon", Some(m)) => RmFinalEpsilonAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
         )
         .run_cli_or_bench(m),
         ("push", Some(m)) => PushAlgorithm::new(
             m.value_of("in.fst").unwrap(),
             m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
             m.is_present("to_final"),
             m.is_present("push_weights"),
             m.is_present("push_labels"),
@@ -221,41 +956,338 @@ fn handle(matches: clap::ArgMatches) -> Result<()> {
             m.is_present("remove_common_affix"),
         )
         .run_cli_or_bench(m),
+        ("determinize", Some(m)) => DeterminizeAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("det_type").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("rmepsilon", Some(m)) => RmEpsilonAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("connect").unwrap() == "true",
+            m.value_of("weight_threshold"),
+            m.value_of("nstate"),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )?
+        .run_cli_or_bench(m),
+        ("closure", Some(m)) => ClosureAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("closure_type").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("condense", Some(m)) => CondenseAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("relabel", Some(m)) => RelabelAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("relabel_ipairs"),
+            m.value_of("relabel_opairs"),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("reweight", Some(m)) => ReweightAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("potentials").unwrap(),
+            m.is_present("to_final"),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("statesort", Some(m)) => StateSortAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("order").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("encode", Some(m)) => EncodeAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.is_present("encode_labels"),
+            m.is_present("encode_weights"),
+            false,
+            m.value_of("codex").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("decode", Some(m)) => EncodeAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            false,
+            false,
+            true,
+            m.value_of("codex").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("replace", Some(m)) => {
+            let fsts: Vec<_> = m.values_of("fsts").unwrap().collect();
+            let (path_out, fst_labels) = fsts.split_last().unwrap();
+            ReplaceCommand::new(
+                fst_labels,
+                m.is_present("epsilon_on_replace"),
+                path_out,
+                m.value_of("fst_type").unwrap(),
+            )?
+            .run()
+        }
+        ("shortestdistance", Some(m)) => ShortestDistanceCommand::new(
+            m.value_of("in.fst").unwrap(),
+            m.is_present("reverse"),
+            m.value_of("distance.txt").unwrap(),
+        )
+        .run(),
+        ("compose", Some(m)) => ComposeAlgorithm::new(
+            m.value_of("in_1.fst").unwrap(),
+            m.value_of("in_2.fst").unwrap(),
+            m.value_of("compose_filter").unwrap(),
+            m.value_of("connect").unwrap() == "true",
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("intersect", Some(m)) => IntersectAlgorithm::new(
+            m.value_of("in_1.fst").unwrap(),
+            m.value_of("in_2.fst").unwrap(),
+            m.value_of("connect").unwrap() == "true",
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("union", Some(m)) => UnionAlgorithm::new(
+            m.value_of("in_1.fst").unwrap(),
+            m.value_of("in_2.fst").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("concat", Some(m)) => ConcatAlgorithm::new(
+            m.value_of("in_1.fst").unwrap(),
+            m.value_of("in_2.fst").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run_cli_or_bench(m),
+        ("compile", Some(m)) => CompileCommand::new(
+            m.value_of("in.txt").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            m.value_of("isymbols"),
+            m.value_of("osymbols"),
+            m.is_present("acceptor"),
+            m.is_present("keep_isymbols"),
+            m.is_present("keep_osymbols"),
+            m.value_of("arc_type").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run(),
+        ("print", Some(m)) => PrintCommand::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("out.txt").unwrap(),
+            m.value_of("isymbols"),
+            m.value_of("osymbols"),
+            m.is_present("acceptor"),
+            m.is_present("show_weight_one"),
+        )
+        .run(),
+        ("info", Some(m)) => InfoCommand::new(m.value_of("in.fst").unwrap()).run(),
+        ("compilestrings", Some(m)) => CompileStringsCommand::new(
+            m.value_of("in.txt").unwrap(),
+            m.value_of("out.fst").unwrap(),
+            TokenType::from_name(m.value_of("token_type").unwrap())?,
+            m.value_of("symbols"),
+            m.value_of("unknown_symbol"),
+            m.is_present("keep_symbols"),
+            m.value_of("arc_type").unwrap(),
+            m.value_of("fst_type").unwrap(),
+        )
+        .run(),
+        ("printstrings", Some(m)) => PrintStringsCommand::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("out.txt").unwrap(),
+            TokenType::from_name(m.value_of("token_type").unwrap())?,
+            m.value_of("isymbols"),
+            m.value_of("osymbols"),
+            m.value_of("nshortest")
+                .map(|_| value_t_or_exit!(m, "nshortest", usize)),
+            m.is_present("unique"),
+        )
+        .run(),
+        ("bench", Some(m)) => match m.subcommand() {
+            ("run", Some(m)) => BenchSuiteCommand::new(
+                m.value_of("suite.json").unwrap(),
+                m.value_of("results").unwrap(),
+                m.value_of("format").unwrap(),
+            )
+            .run(),
+            ("compare", Some(m)) => BenchCompareCommand::new(
+                m.value_of("baseline.json").unwrap(),
+                m.value_of("current.json").unwrap(),
+                value_t_or_exit!(m, "threshold", f64),
+                m.is_present("fail_on_regression"),
+            )
+            .run(),
+            (s, _) => Err(format_err!("Unknown bench subcommand {}.", s)),
+        },
+        ("symbols", Some(m)) => match m.subcommand() {
+            ("extract", Some(m)) => SymbolsExtractCommand::new(
+                m.value_of("in.fst").unwrap(),
+                m.value_of("out.syms").unwrap(),
+                m.is_present("output"),
+                m.value_of("format").unwrap(),
+            )
+            .run(),
+            ("replace", Some(m)) => SymbolsReplaceAlgorithm::new(
+                m.value_of("in.fst").unwrap(),
+                m.value_of("isymbols"),
+                m.value_of("osymbols"),
+                m.is_present("clear_isymbols"),
+                m.is_present("clear_osymbols"),
+                m.value_of("out.fst").unwrap(),
+                m.value_of("fst_type").unwrap(),
+            )
+            .run_cli_or_bench(m),
+            ("relabel", Some(m)) => SymbolsRelabelAlgorithm::new(
+                m.value_of("in.fst").unwrap(),
+                m.value_of("isymbols"),
+                m.value_of("osymbols"),
+                m.value_of("unknown_symbol"),
+                m.value_of("out.fst").unwrap(),
+                m.value_of("fst_type").unwrap(),
+            )
+            .run_cli_or_bench(m),
+            ("compact", Some(m)) => SymbolsCompactAlgorithm::new(
+                m.value_of("in.fst").unwrap(),
+                m.value_of("out.fst").unwrap(),
+                m.value_of("fst_type").unwrap(),
+            )
+            .run_cli_or_bench(m),
+            ("convert", Some(m)) => SymbolsConvertCommand::new(
+                m.value_of("in.syms").unwrap(),
+                m.value_of("out.syms").unwrap(),
+                m.value_of("format").unwrap(),
+            )
+            .run(),
+            (s, _) => Err(format_err!("Unknown symbols subcommand {}.", s)),
+        },
+        ("draw", Some(m)) => {
+            let config = DrawingConfig {
+                vertical: m.is_present("vertical"),
+                size: None,
+                title: m.value_of("title").unwrap().to_string(),
+                portrait: m.is_present("portrait"),
+                ranksep: m
+                    .value_of("ranksep")
+                    .map(|_| value_t_or_exit!(m, "ranksep", f32)),
+                nodesep: m
+                    .value_of("nodesep")
+                    .map(|_| value_t_or_exit!(m, "nodesep", f32)),
+                fontsize: value_t_or_exit!(m, "fontsize", u32),
+                acceptor: m.is_present("acceptor"),
+                show_weight_one: m.is_present("show_weight_one"),
+                print_weight: true,
+            };
+            DrawCommand::new(
+                m.value_of("in.fst").unwrap(),
+                m.value_of("out.dot").unwrap(),
+                m.value_of("isymbols"),
+                m.value_of("osymbols"),
+                config,
+            )
+            .run()
+        }
         (s, _) => Err(format_err!("Unknown subcommand {}.", s)),
     }
     .map_err(|e| e.into())
 }
 
 fn one_in_one_out_options<'a, 'b>(command: clap::App<'a, 'b>) -> clap::App<'a, 'b> {
-    command
+    let command = command
         .version("1.0")
         .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
         .arg(
             Arg::with_name("in.fst")
-                .help("Path to input fst file.")
-                .required(true),
+                .help("Path to input fst file. Reads stdin if missing or `-`.")
+                .default_value(STD_STREAM),
         )
         .arg(
             Arg::with_name("out.fst")
-                .help("Path to output fst file.")
+                .help("Path to output fst file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        );
+    algorithm_options(command)
+}
+
+fn two_in_one_out_options<'a, 'b>(command: clap::App<'a, 'b>) -> clap::App<'a, 'b> {
+    let command = command
+        .version("1.0")
+        .author("Alexandre Caulier <alexandre.caulier@protonmail.com>")
+        .arg(
+            Arg::with_name("in_1.fst")
+                .help("Path to the first input fst file. `-` reads stdin.")
                 .required(true),
-        ).arg(
+        )
+        .arg(
+            Arg::with_name("in_2.fst")
+                .help("Path to the second input fst file. `-` reads stdin.")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("out.fst")
+                .help("Path to output fst file. Writes to stdout if missing or `-`.")
+                .default_value(STD_STREAM),
+        );
+    algorithm_options(command)
+}
+
+/// Option selecting how strings are split into labels.
+fn token_type_option<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("token_type")
+        .long("token_type")
+        .takes_value(true)
+        .possible_values(&["symbol", "byte", "utf8"])
+        .default_value("symbol")
+        .help("How strings are split into labels : whitespace separated symbols, bytes or UTF-8 characters.")
+}
+
+/// Options shared by all the algorithms : output FST type and benchmark.
+fn algorithm_options<'a, 'b>(command: clap::App<'a, 'b>) -> clap::App<'a, 'b> {
+    command
+        .arg(
+            Arg::with_name("fst_type")
+                .long("fst_type")
+                .takes_value(true)
+                .possible_values(&["vector", "const"])
+                .default_value("vector")
+                .help("Output FST type."),
+        )
+        .arg(
             Arg::with_name("bench")
                 .long("bench")
-                .help("Whether to run multiple times the algorithm in order to have a reliable time measurement.")
-        ).arg(
+                .help("Whether to run multiple times the algorithm in order to have a reliable time measurement."),
+        )
+        .arg(
             Arg::with_name("n_iters")
                 .long("n_iters")
                 .default_value("10")
-                .help("Number of iterations to run for the benchmark.")
-        ).arg(
+                .help("Number of iterations to run for the benchmark."),
+        )
+        .arg(
             Arg::with_name("n_warm_ups")
                 .long("n_warm_ups")
                 .default_value("3")
-                .help("Number of warm ups run before the actual benchmark.")
-        ).arg(
-        Arg::with_name("export-markdown")
-            .long("export-markdown")
-            .takes_value(true)
-    )
+                .help("Number of warm ups run before the actual benchmark."),
+        )
+        .arg(
+            Arg::with_name("export-markdown")
+                .long("export-markdown")
+                .takes_value(true),
+        )
 }
@@ -2,18 +2,23 @@ use std::fs::File;
 use std::io::Write;
 use std::time::{Duration, Instant};
 
+use anyhow::{format_err, Result};
 use clap::ArgMatches;
 use colored::Colorize;
-use anyhow::Result;
 use log::{debug, info};
 
 use rustfst::prelude::*;
 
-fn duration_to_seconds(duration: &Duration) -> f64 {
+use crate::errors::{Categorize, ErrorCategory};
+use crate::utils::{
+    parse_vector_fst, read_fst_types, read_input, write_fst, CliWeight, STD_STREAM,
+};
+
+pub fn duration_to_seconds(duration: &Duration) -> f64 {
     duration.as_secs() as f64 + duration.subsec_nanos() as f64 * 1.0e-9
 }
 
-fn standard_deviation(data: &[f64]) -> f64 {
+pub fn standard_deviation(data: &[f64]) -> f64 {
     let sum: f64 = data.iter().sum();
     let mean: f64 = sum / data.len() as f64;
 
@@ -22,161 +27,215 @@ fn standard_deviation(data: &[f64]) -> f64 {
     b.sqrt()
 }
 
+/// Parses, runs and serializes an FST once, returning the duration of each step.
+pub fn timed_run<I, W: CliWeight>(
+    read: impl FnOnce() -> Result<I>,
+    run_algorithm: impl FnOnce(I) -> Result<VectorFst<W>>,
+    write: impl FnOnce(VectorFst<W>) -> Result<()>,
+) -> Result<(Duration, Duration, Duration)> {
+    // Parsing
+    let parsing_start = Instant::now();
+    let input = read()?;
+    let duration_parsing = parsing_start.elapsed();
+
+    // Algorithm
+    let algo_start = Instant::now();
+    let fst = run_algorithm(input).categorize(ErrorCategory::Algorithm)?;
+    let duration_algo = algo_start.elapsed();
+
+    // Serialization
+    let serialization_start = Instant::now();
+    write(fst)?;
+    let duration_serialization = serialization_start.elapsed();
+
+    Ok((duration_parsing, duration_algo, duration_serialization))
+}
+
 pub trait UnaryFstAlgorithm {
     fn get_path_in(&self) -> &str;
     fn get_path_out(&self) -> &str;
+    fn get_fst_type(&self) -> &str;
     fn get_algorithm_name(&self) -> String;
 
-    fn read(&self) -> Result<VectorFst<TropicalWeight>> {
-        VectorFst::<TropicalWeight>::read(self.get_path_in())
+    fn read<W: CliWeight>(&self, data: &[u8]) -> Result<VectorFst<W>> {
+        parse_vector_fst(data)
     }
 
-    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Result<VectorFst<TropicalWeight>>;
+    fn run_algorithm<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<VectorFst<W>>;
 
-    fn write(&self, fst: &VectorFst<TropicalWeight>) -> Result<()> {
-        fst.write(self.get_path_out())
+    fn write<W: CliWeight>(&self, fst: VectorFst<W>) -> Result<()> {
+        write_fst(fst, self.get_fst_type(), self.get_path_out())
     }
 
     fn run_cli_or_bench(&self, m: &ArgMatches) -> Result<()> {
-        if m.is_present("bench") {
-            // Run bench
-            self.run_bench(
-                m.value_of("n_warm_ups").unwrap().parse().unwrap(),
-                m.value_of("n_iters").unwrap().parse().unwrap(),
-                m.value_of("export-markdown"),
-            )
-        } else {
-            // Run cli
-            self.run_cli()
+        if m.is_present("bench") && self.get_path_out() == STD_STREAM {
+            return Err(format_err!(
+                "The output FST can't be written to stdout when benchmarking"
+            ))
+            .categorize(ErrorCategory::Io);
         }
+        let data = read_input(self.get_path_in())?;
+        let (_, arc_type) = read_fst_types(&data)?;
+        dispatch_weight_type!(arc_type.as_str(), W => {
+            if m.is_present("bench") {
+                // Run bench
+                self.run_bench::<W>(
+                    &data,
+                    m.value_of("n_warm_ups").unwrap().parse().unwrap(),
+                    m.value_of("n_iters").unwrap().parse().unwrap(),
+                    m.value_of("export-markdown"),
+                )
+            } else {
+                // Run cli
+                self.run_cli::<W>(&data)
+            }
+        })
     }
 
-    fn run_cli(&self) -> Result<()> {
+    fn run_cli<W: CliWeight>(&self, data: &[u8]) -> Result<()> {
         info!("Running {} algorithm", self.get_algorithm_name().blue());
-        // Parsing
-        debug!("Parsing...");
-        let parsing_start = Instant::now();
-        let mut fst = self.read()?;
-        let duration_parsing = parsing_start.elapsed();
+        let (duration_parsing, duration_algo, duration_serialization) = timed_run(
+            || self.read::<W>(data),
+            |fst| self.run_algorithm(fst),
+            |fst| self.write(fst),
+        )?;
         debug!("Duration parsing : {:?}", &duration_parsing);
-
-        // Algorithm
-        debug!("Running algorithm...");
-        let algo_start = Instant::now();
-        fst = self.run_algorithm(fst)?;
-        let duration_algo = algo_start.elapsed();
         debug!("Duration running algorithm : {:?}", &duration_algo);
-
-        // Serialization
-        debug!("Serialization...");
-        let serialization_start = Instant::now();
-        self.write(&fst)?;
-        let duration_serialization = serialization_start.elapsed();
         debug!("Duration serialization : {:?}", &duration_serialization);
 
         Ok(())
     }
 
-    fn run_bench(
+    fn run_bench<W: CliWeight>(
         &self,
+        data: &[u8],
         n_warm_ups: usize,
         n_iters: usize,
         path_markdown_report: Option<&str>,
     ) -> Result<()> {
-        println!(
+        eprintln!(
             "Running benchmark for algorithm {}",
             self.get_algorithm_name().blue()
         );
-        let mut avg_parsing_time = Duration::default();
-        let mut avg_algo_time = Duration::default();
-        let mut avg_serialization_time = Duration::default();
-
-        let mut parsing_times = vec![];
-        let mut algo_times = vec![];
-        let mut serialization_times = vec![];
-        let mut cli_times = vec![];
+        let mut results = BenchResults::new(n_warm_ups, n_iters);
 
         for i in 0..(n_warm_ups + n_iters) {
-            // Parsing
-            let parsing_start = Instant::now();
-            let mut fst = self.read()?;
-            let duration_parsing = parsing_start.elapsed();
-
-            // Algorithm
-            let algo_start = Instant::now();
-            fst = self.run_algorithm(fst)?;
-            let duration_algo = algo_start.elapsed();
-
-            // Serialization
-            let serialization_start = Instant::now();
-            self.write(&fst)?;
-            let duration_serialization = serialization_start.elapsed();
-
-            if i >= n_warm_ups {
-                println!(
-                    "Run #{}/{}: \t{} \t{} \t{}",
-                    format!("{}", i + 1 - n_warm_ups).yellow(),
-                    format!("{}", n_iters).yellow(),
-                    format!("{:.6}s", duration_to_seconds(&duration_parsing)).blue(),
-                    format!("{:.6}s", duration_to_seconds(&duration_algo)).magenta(),
-                    format!("{:.6}s", duration_to_seconds(&duration_serialization)).cyan(),
-                );
-
-                avg_parsing_time = avg_parsing_time.checked_add(duration_parsing).unwrap();
-                avg_algo_time = avg_algo_time.checked_add(duration_algo).unwrap();
-                avg_serialization_time = avg_serialization_time
-                    .checked_add(duration_serialization)
-                    .unwrap();
-
-                parsing_times.push(duration_to_seconds(&duration_parsing));
-                algo_times.push(duration_to_seconds(&duration_algo));
-                serialization_times.push(duration_to_seconds(&duration_serialization));
-                cli_times.push(
-                    duration_to_seconds(&duration_parsing)
-                        + duration_to_seconds(&duration_algo)
-                        + duration_to_seconds(&duration_serialization),
-                )
-            } else {
-                println!(
-                    "Warmup #{}/{}: \t{} \t{} \t{}",
-                    format!("{}", i + 1).yellow(),
-                    format!("{}", n_warm_ups).yellow(),
-                    format!("{:.6}s", duration_to_seconds(&duration_parsing)).blue(),
-                    format!("{:.6}s", duration_to_seconds(&duration_algo)).magenta(),
-                    format!("{:.6}s", duration_to_seconds(&duration_serialization)).cyan(),
-                );
-            }
+            let (duration_parsing, duration_algo, duration_serialization) = timed_run(
+                || self.read::<W>(data),
+                |fst| self.run_algorithm(fst),
+                |fst| self.write(fst),
+            )?;
+            results.add_run(i, duration_parsing, duration_algo, duration_serialization);
         }
 
-        avg_parsing_time = avg_parsing_time.checked_div(n_iters as u32).unwrap();
-        avg_algo_time = avg_algo_time.checked_div(n_iters as u32).unwrap();
-        avg_serialization_time = avg_serialization_time.checked_div(n_iters as u32).unwrap();
+        results.report(path_markdown_report)
+    }
+}
+
+/// Timings of the runs of a benchmark.
+pub struct BenchResults {
+    n_warm_ups: usize,
+    n_iters: usize,
+    parsing_times: Vec<Duration>,
+    algo_times: Vec<Duration>,
+    serialization_times: Vec<Duration>,
+}
+
+impl BenchResults {
+    pub fn new(n_warm_ups: usize, n_iters: usize) -> Self {
+        Self {
+            n_warm_ups,
+            n_iters,
+            parsing_times: vec![],
+            algo_times: vec![],
+            serialization_times: vec![],
+        }
+    }
+
+    /// Prints the timings of the `i`-th run and stores them if it is not a warmup.
+    pub fn add_run(
+        &mut self,
+        i: usize,
+        duration_parsing: Duration,
+        duration_algo: Duration,
+        duration_serialization: Duration,
+    ) {
+        let n_warm_ups = self.n_warm_ups;
+        let (run_type, run_idx, n_runs) = if i >= n_warm_ups {
+            ("Run", i + 1 - n_warm_ups, self.n_iters)
+        } else {
+            ("Warmup", i + 1, n_warm_ups)
+        };
+        eprintln!(
+            "{} #{}/{}: \t{} \t{} \t{}",
+            run_type,
+            format!("{}", run_idx).yellow(),
+            format!("{}", n_runs).yellow(),
+            format!("{:.6}s", duration_to_seconds(&duration_parsing)).blue(),
+            format!("{:.6}s", duration_to_seconds(&duration_algo)).magenta(),
+            format!("{:.6}s", duration_to_seconds(&duration_serialization)).cyan(),
+        );
+
+        if i >= n_warm_ups {
+            self.parsing_times.push(duration_parsing);
+            self.algo_times.push(duration_algo);
+            self.serialization_times.push(duration_serialization);
+        }
+    }
+
+    /// Prints the mean timings and optionally appends them to a Markdown report.
+    pub fn report(&self, path_markdown_report: Option<&str>) -> Result<()> {
+        let n_iters = self.n_iters;
+        let to_seconds = |durations: &[Duration]| -> Vec<f64> {
+            durations.iter().map(duration_to_seconds).collect()
+        };
+        let mean = |durations: &[Duration]| -> Duration {
+            durations
+                .iter()
+                .fold(Duration::default(), |acc, d| acc.checked_add(*d).unwrap())
+                .checked_div(n_iters as u32)
+                .unwrap()
+        };
+
+        let parsing_times = to_seconds(&self.parsing_times);
+        let algo_times = to_seconds(&self.algo_times);
+        let serialization_times = to_seconds(&self.serialization_times);
+        let cli_times: Vec<f64> = parsing_times
+            .iter()
+            .zip(algo_times.iter())
+            .zip(serialization_times.iter())
+            .map(|((p, a), s)| p + a + s)
+            .collect();
+
+        let avg_parsing_time = mean(&self.parsing_times);
+        let avg_algo_time = mean(&self.algo_times);
+        let avg_serialization_time = mean(&self.serialization_times);
 
         let s = format!(
             "Bench results (Warmups = {}, Iterations = {}):",
-            n_warm_ups, n_iters
+            self.n_warm_ups, n_iters
         );
-        println!("{}", s.bold().underline());
+        eprintln!("{}", s.bold().underline());
 
         let s = format!(
             "\t Mean {} : \t\t{}",
             "parsing time".blue(),
             format!("{:.6}s", duration_to_seconds(&avg_parsing_time)).blue()
         );
-        println!("{}", s.bold());
+        eprintln!("{}", s.bold());
         let s = format!(
             "\t Mean {} : \t\t{}",
             "algorithm time".magenta(),
             format!("{:.6}s", duration_to_seconds(&avg_algo_time)).magenta()
         );
-        println!("{}", s.bold());
+        eprintln!("{}", s.bold());
 
         let s = format!(
             "\t Mean {} : \t{}",
             "serialization time".cyan(),
             format!("{:.6}s", duration_to_seconds(&avg_serialization_time)).cyan()
         );
-        println!("{}", s.bold());
+        eprintln!("{}", s.bold());
 
         let mean_total_time = avg_parsing_time + avg_algo_time + avg_serialization_time;
         let s = format!(
@@ -184,7 +243,7 @@ pub trait UnaryFstAlgorithm {
             "CLI time".red(),
             format!("{:.6}s", duration_to_seconds(&mean_total_time)).red()
         );
-        println!("{}", s.bold());
+        eprintln!("{}", s.bold());
 
         if let Some(_path) = path_markdown_report {
             let mut file = File::create(_path)?;
@@ -0,0 +1,437 @@
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::rc::Rc;
+
+use anyhow::{bail, ensure, format_err, Context, Result};
+
+use rustfst::prelude::*;
+use rustfst::utils::peek_fst_types;
+use rustfst::{Label, StateId, SymbolTable};
+
+use crate::errors::{Categorize, ErrorCategory};
+
+/// Weights the CLI algorithms can run on, i.e. the weights of the supported arc types.
+pub trait CliWeight:
+    SerializableSemiring<ReverseWeight = Self>
+    + WeaklyDivisibleSemiring
+    + WeightQuantize
+    + ReverseBack<Self>
+    + 'static
+{
+}
+
+impl<W> CliWeight for W where
+    W: SerializableSemiring<ReverseWeight = W>
+        + WeaklyDivisibleSemiring
+        + WeightQuantize
+        + ReverseBack<W>
+        + 'static
+{
+}
+
+/// Path used to designate the standard input or output.
+pub const STD_STREAM: &str = "-";
+
+/// Reads the whole content of a file, or of the standard input if the path is `-`.
+pub fn read_input(path: &str) -> Result<Vec<u8>> {
+    let mut data = vec![];
+    if path == STD_STREAM {
+        io::stdin()
+            .read_to_end(&mut data)
+            .context("Can't read from stdin")
+            .categorize(ErrorCategory::Io)?;
+    } else {
+        File::open(path)
+            .and_then(|mut f| f.read_to_end(&mut data))
+            .with_context(|| format!("Can't read file {:?}", path))
+            .categorize(ErrorCategory::Io)?;
+    }
+    Ok(data)
+}
+
+/// Same as `read_input` but the content is expected to be valid UTF-8.
+pub fn read_input_string(path: &str) -> Result<String> {
+    let data = read_input(path)?;
+    String::from_utf8(data)
+        .with_context(|| format!("{:?} is not valid UTF-8", path))
+        .categorize(ErrorCategory::Parse)
+}
+
+/// Creates a buffered writer over a file, or over the standard output if the path is `-`.
+pub fn create_output(path: &str) -> Result<Box<dyn Write>> {
+    if path == STD_STREAM {
+        Ok(Box::new(BufWriter::new(io::stdout())))
+    } else {
+        let file = File::create(path)
+            .with_context(|| format!("Can't create file {:?}", path))
+            .categorize(ErrorCategory::Io)?;
+        Ok(Box::new(BufWriter::new(file)))
+    }
+}
+
+/// Returns the `fst_type` and the `arc_type` stored in the header of a binary FST.
+pub fn read_fst_types(data: &[u8]) -> Result<(String, String)> {
+    peek_fst_types(data).categorize(ErrorCategory::Parse)
+}
+
+/// Loads a symbol table in text or binary format if a path is provided.
+pub fn read_symbol_table(path: Option<&str>) -> Result<Option<Rc<SymbolTable>>> {
+    path.map(|p| read_symbol_table_any_format(p).map(Rc::new))
+        .transpose()
+}
+
+/// Returns the input and output symbol tables to use for an FST. Symbol tables passed on the
+/// command line take precedence over the ones stored in the FST.
+pub fn resolve_symbol_tables<F: Fst>(
+    fst: &F,
+    isymbols: Option<&str>,
+    osymbols: Option<&str>,
+) -> Result<(Option<Rc<SymbolTable>>, Option<Rc<SymbolTable>>)> {
+    let isymt = read_symbol_table(isymbols)?.or_else(|| fst.input_symbols());
+    let osymt = read_symbol_table(osymbols)?.or_else(|| fst.output_symbols());
+    Ok((isymt, osymt))
+}
+
+/// Loads a symbol table, the format being detected from the magic number of the binary
+/// format.
+pub fn read_symbol_table_any_format(path: &str) -> Result<SymbolTable> {
+    let data = read_input(path)?;
+    let symt = if SymbolTable::is_binary(&data) {
+        SymbolTable::from_binary_bytes(&data)
+    } else {
+        std::str::from_utf8(&data)
+            .map_err(|e| e.into())
+            .and_then(SymbolTable::from_text_string)
+    };
+    symt.with_context(|| format!("Can't read symbol table {:?}", path))
+        .categorize(ErrorCategory::Parse)
+}
+
+/// Returns the whitespace separated fields of the non empty lines of a text file along with
+/// their line number.
+fn read_fields(path: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let text = read_input_string(path)?;
+    Ok(text
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            (
+                idx + 1,
+                line.split_whitespace().map(|f| f.to_string()).collect(),
+            )
+        })
+        .filter(|(_, fields): &(usize, Vec<String>)| !fields.is_empty())
+        .collect())
+}
+
+/// Loads a list of label pairs, one `old_label new_label` pair per line.
+pub fn read_label_pairs(path: &str) -> Result<Vec<(Label, Label)>> {
+    read_fields(path)?
+        .into_iter()
+        .map(|(line, fields)| {
+            ensure!(
+                fields.len() == 2,
+                "{}:{}: expected a pair of labels",
+                path,
+                line
+            );
+            Ok((
+                fields[0]
+                    .parse()
+                    .with_context(|| format!("{}:{}: invalid label", path, line))?,
+                fields[1]
+                    .parse()
+                    .with_context(|| format!("{}:{}: invalid label", path, line))?,
+            ))
+        })
+        .collect::<Result<_>>()
+        .categorize(ErrorCategory::Parse)
+}
+
+/// Loads a vector of potentials, one `state weight` pair per line. The potentials of the
+/// missing states are set to `zero`. The states must be lower than `num_states`.
+pub fn read_potentials<W: SerializableSemiring>(path: &str, num_states: usize) -> Result<Vec<W>> {
+    let mut potentials = vec![];
+    for (line, fields) in read_fields(path)? {
+        parse_potential(path, line, &fields, num_states, &mut potentials)
+            .categorize(ErrorCategory::Parse)?;
+    }
+    Ok(potentials)
+}
+
+fn parse_potential<W: SerializableSemiring>(
+    path: &str,
+    line: usize,
+    fields: &[String],
+    num_states: usize,
+    potentials: &mut Vec<W>,
+) -> Result<()> {
+    ensure!(
+        fields.len() == 2,
+        "{}:{}: expected a state and a weight",
+        path,
+        line
+    );
+    let state: StateId = fields[0]
+        .parse()
+        .with_context(|| format!("{}:{}: invalid state", path, line))?;
+    ensure!(
+        state < num_states,
+        "{}:{}: state {} is out of range, the FST has {} states",
+        path,
+        line,
+        state,
+        num_states
+    );
+    let weight =
+        parse_weight(&fields[1]).with_context(|| format!("{}:{}: invalid weight", path, line))?;
+    if state >= potentials.len() {
+        potentials.resize(state + 1, W::zero());
+    }
+    potentials[state] = weight;
+    Ok(())
+}
+
+/// Loads a permutation of the states, the line `s` containing the new id of the state `s`.
+pub fn read_state_order(path: &str) -> Result<Vec<StateId>> {
+    read_fields(path)?
+        .into_iter()
+        .map(|(line, fields)| {
+            ensure!(fields.len() == 1, "{}:{}: expected a state", path, line);
+            fields[0]
+                .parse()
+                .with_context(|| format!("{}:{}: invalid state", path, line))
+        })
+        .collect::<Result<_>>()
+        .categorize(ErrorCategory::Parse)
+}
+
+/// Parses a weight in text format.
+pub fn parse_weight<W: SerializableSemiring>(s: &str) -> Result<W> {
+    match W::parse_text(s) {
+        Ok(("", weight)) => Ok(weight),
+        _ => Err(format_err!("Invalid {} weight : {:?}", W::weight_type(), s))
+            .categorize(ErrorCategory::Parse),
+    }
+}
+
+/// Parses a binary FST of any supported `fst_type` into a `VectorFst`.
+pub fn parse_vector_fst<W: CliWeight>(data: &[u8]) -> Result<VectorFst<W>> {
+    let (fst_type, _) = read_fst_types(data)?;
+    let parsing_error = || format_err!("Error while parsing binary {} fst", fst_type);
+    match fst_type.as_str() {
+        "vector" => {
+            let (_, fst) = VectorFst::<W>::parse_binary(data)
+                .map_err(|_| parsing_error())
+                .categorize(ErrorCategory::Parse)?;
+            Ok(fst)
+        }
+        "const" => {
+            let (_, fst) = ConstFst::<W>::parse_binary(data)
+                .map_err(|_| parsing_error())
+                .categorize(ErrorCategory::Parse)?;
+            Ok(fst_convert(fst))
+        }
+        _ => Err(format_err!("Unsupported FST type {}", fst_type)).categorize(ErrorCategory::Parse),
+    }
+}
+
+/// Writes an FST in binary format, converted to the requested `fst_type`.
+pub fn write_fst<W: CliWeight>(fst: VectorFst<W>, fst_type: &str, path: &str) -> Result<()> {
+    let mut output = create_output(path)?;
+    match fst_type {
+        "vector" => fst.write_binary(&mut output),
+        "const" => ConstFst::from(fst).write_binary(&mut output),
+        _ => bail!("Unsupported FST type {}", fst_type),
+    }
+    .and_then(|_| Ok(output.flush()?))
+    .with_context(|| format!("Can't write FST to {:?}", path))
+    .categorize(ErrorCategory::Io)
+}
+
+/// How a string is split into labels. Same as the `--token_type` of OpenFST's string tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    /// Whitespace separated symbols, looked up in a symbol table. Without symbol table, the
+    /// symbols are the labels themselves.
+    Symbol,
+    /// One label per byte.
+    Byte,
+    /// One label per Unicode code point.
+    Utf8,
+}
+
+impl TokenType {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "symbol" => Ok(TokenType::Symbol),
+            "byte" => Ok(TokenType::Byte),
+            "utf8" => Ok(TokenType::Utf8),
+            _ => bail!("Unknown token type : {}", name),
+        }
+    }
+}
+
+/// Splits a string into labels. With the `Symbol` token type, tokens missing in the symbol
+/// table are replaced by `unknown_symbol` if provided.
+pub fn string_to_labels(
+    s: &str,
+    token_type: TokenType,
+    symt: Option<&SymbolTable>,
+    unknown_symbol: Option<&str>,
+) -> Result<Vec<Label>> {
+    match token_type {
+        TokenType::Symbol => s
+            .split_whitespace()
+            .map(|token| match symt {
+                Some(symt) => symt
+                    .get_label(token)
+                    .or_else(|| unknown_symbol.and_then(|unk| symt.get_label(unk)))
+                    .ok_or_else(|| format_err!("Symbol {:?} not found in the symbol table", token)),
+                None => token
+                    .parse()
+                    .with_context(|| format!("Invalid label {:?}", token)),
+            })
+            .collect(),
+        TokenType::Byte => Ok(s.bytes().map(Label::from).collect()),
+        TokenType::Utf8 => Ok(s.chars().map(|c| c as Label).collect()),
+    }
+}
+
+/// Joins labels into a string, the inverse of `string_to_labels`.
+pub fn labels_to_string(
+    labels: &[Label],
+    token_type: TokenType,
+    symt: Option<&SymbolTable>,
+) -> Result<String> {
+    match token_type {
+        TokenType::Symbol => Ok(labels
+            .iter()
+            .map(|label| match symt {
+                Some(symt) => symt
+                    .get_symbol(*label)
+                    .map(|s| s.to_string())
+                    .ok_or_else(|| format_err!("Label {} not found in the symbol table", label)),
+                None => Ok(label.to_string()),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(" ")),
+        TokenType::Byte => {
+            let bytes = labels
+                .iter()
+                .map(|label| {
+                    u8::try_from(*label).map_err(|_| format_err!("Label {} is not a byte", label))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        TokenType::Utf8 => labels
+            .iter()
+            .map(|label| {
+                u32::try_from(*label)
+                    .ok()
+                    .and_then(std::char::from_u32)
+                    .ok_or_else(|| format_err!("Label {} is not a Unicode code point", label))
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rustfst::utils::acceptor;
+
+    #[test]
+    fn test_read_fst_types() -> Result<()> {
+        let fst: VectorFst<LogWeight> = acceptor(&[1, 2], LogWeight::one());
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+        assert_eq!(
+            read_fst_types(&data)?,
+            ("vector".to_string(), "log".to_string())
+        );
+
+        let fst: ConstFst<LogWeight> = fst.into();
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+        assert_eq!(
+            read_fst_types(&data)?,
+            ("const".to_string(), "log".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_fst_types_invalid() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+
+        // Truncated in the middle of the fst_type.
+        let err = read_fst_types(&data[..6]).unwrap_err();
+        assert!(err.to_string().contains("Truncated"));
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::Parse);
+        assert!(read_fst_types(&[]).is_err());
+
+        data[..4].copy_from_slice(&0i32.to_le_bytes());
+        let err = read_fst_types(&data).unwrap_err();
+        assert!(err.to_string().contains("wrong magic number"));
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::Parse);
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_to_labels_symbol() -> Result<()> {
+        let mut symt = SymbolTable::new();
+        let a = symt.add_symbol("a");
+        let b = symt.add_symbol("b");
+        let unk = symt.add_symbol("<unk>");
+
+        let labels = string_to_labels(" a  b a ", TokenType::Symbol, Some(&symt), None)?;
+        assert_eq!(labels, vec![a, b, a]);
+        assert_eq!(
+            labels_to_string(&labels, TokenType::Symbol, Some(&symt))?,
+            "a b a"
+        );
+
+        assert!(string_to_labels("a c", TokenType::Symbol, Some(&symt), None).is_err());
+        assert_eq!(
+            string_to_labels("a c", TokenType::Symbol, Some(&symt), Some("<unk>"))?,
+            vec![a, unk]
+        );
+        assert!(labels_to_string(&[a, 42], TokenType::Symbol, Some(&symt)).is_err());
+
+        let labels = string_to_labels("3 1 2", TokenType::Symbol, None, None)?;
+        assert_eq!(labels, vec![3, 1, 2]);
+        assert_eq!(labels_to_string(&labels, TokenType::Symbol, None)?, "3 1 2");
+        assert!(string_to_labels("3 a", TokenType::Symbol, None, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_to_labels_byte() -> Result<()> {
+        let labels = string_to_labels("aé", TokenType::Byte, None, None)?;
+        assert_eq!(labels, vec![97, 0xC3, 0xA9]);
+        assert_eq!(labels_to_string(&labels, TokenType::Byte, None)?, "aé");
+
+        let err = labels_to_string(&[97, 256], TokenType::Byte, None).unwrap_err();
+        assert!(err.to_string().contains("not a byte"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_to_labels_utf8() -> Result<()> {
+        let labels = string_to_labels("aé€", TokenType::Utf8, None, None)?;
+        assert_eq!(labels, vec![97, 0xE9, 0x20AC]);
+        assert_eq!(labels_to_string(&labels, TokenType::Utf8, None)?, "aé€");
+
+        for label in &[0xD800, 0x11_0000] {
+            let err = labels_to_string(&[97, *label], TokenType::Utf8, None).unwrap_err();
+            assert!(err.to_string().contains("not a Unicode code point"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+/// Runs the CLI with `args`, feeding `input` on its standard input.
+fn run_cli(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustfst-cli"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Can't run rustfst-cli");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input)
+        .expect("Can't write to the standard input of rustfst-cli");
+    child.wait_with_output().unwrap()
+}
+
+/// Writes `data` to a file of the temporary directory of the tests and returns its path.
+fn write_tmp_file(name: &str, data: &[u8]) -> String {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::write(&path, data).expect("Can't write temporary file");
+    path.to_str().unwrap().to_string()
+}
+
+/// Compiles an FST in text format and returns the path of the binary FST.
+fn compile_tmp_fst(name: &str, text: &str) -> String {
+    let compiled = run_cli(&["compile"], text.as_bytes());
+    assert!(compiled.status.success());
+    write_tmp_file(name, &compiled.stdout)
+}
+
+#[test]
+fn test_compile_print_round_trip() {
+    let text = "0\t1\t1\t2\t0.5\n0\t2\t3\t3\n1\t2\t4\t5\t1.25\n1\t0.5\n2\n";
+
+    let compiled = run_cli(&["compile"], text.as_bytes());
+    assert!(compiled.status.success());
+    assert!(!compiled.stdout.is_empty());
+
+    let printed = run_cli(&["print"], &compiled.stdout);
+    assert!(printed.status.success());
+    assert_eq!(String::from_utf8(printed.stdout).unwrap(), text);
+}
+
+#[test]
+fn test_rm_epsilon_invalid_nstate() {
+    let compiled = run_cli(&["compile"], b"0\t1\t0\t0\n1\n");
+    assert!(compiled.status.success());
+
+    let output = run_cli(
+        &["rmepsilon", "-", "-", "--nstate", "abc"],
+        &compiled.stdout,
+    );
+    assert_eq!(output.status.code(), Some(65));
+}
+
+#[test]
+fn test_state_sort_invalid_order() {
+    let fst = compile_tmp_fst("state_sort_in.fst", "0\t1\t1\t1\n1\t2\t2\t2\n2\n");
+
+    for (name, order) in &[
+        ("state_sort_out_of_range.txt", "0\n7\n2\n"),
+        ("state_sort_repeated.txt", "0\n0\n1\n"),
+        ("state_sort_too_short.txt", "0\n1\n"),
+    ] {
+        let order = write_tmp_file(name, order.as_bytes());
+        let output = run_cli(&["statesort", &fst, "-", "--order", &order], b"");
+        assert_eq!(output.status.code(), Some(65));
+    }
+
+    let order = write_tmp_file("state_sort_valid.txt", b"2\n0\n1\n");
+    let output = run_cli(&["statesort", &fst, "-", "--order", &order], b"");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_reweight_invalid_potentials() {
+    let fst = compile_tmp_fst("reweight_in.fst", "0\t1\t1\t1\n1\n");
+
+    for (name, potentials) in &[
+        ("reweight_huge_state.txt", "18446744073709551615\t1\n"),
+        ("reweight_out_of_range.txt", "2\t1\n"),
+    ] {
+        let potentials = write_tmp_file(name, potentials.as_bytes());
+        let output = run_cli(&["reweight", &fst, "-", "--potentials", &potentials], b"");
+        assert_eq!(output.status.code(), Some(65));
+    }
+
+    let potentials = write_tmp_file("reweight_valid.txt", b"0\t1\n1\t2\n");
+    let output = run_cli(&["reweight", &fst, "-", "--potentials", &potentials], b"");
+    assert!(output.status.success());
+}
+
+/// Compiles the two inputs of the binary algorithms tests, prefixing their file names.
+fn compile_binary_inputs(prefix: &str) -> (String, String) {
+    (
+        compile_tmp_fst(&format!("{}_1.fst", prefix), "0\t1\t1\t2\n1\n"),
+        compile_tmp_fst(&format!("{}_2.fst", prefix), "0\t1\t2\t3\t0.5\n1\n"),
+    )
+}
+
+fn run_binary_algorithm(algorithm: &str, fst_1: &str, fst_2: &str, input: &[u8]) -> String {
+    let output = run_cli(&[algorithm, fst_1, fst_2], input);
+    assert!(output.status.success());
+    let printed = run_cli(&["print"], &output.stdout);
+    assert!(printed.status.success());
+    String::from_utf8(printed.stdout).unwrap()
+}
+
+#[test]
+fn test_binary_algorithms() {
+    let (fst_1, fst_2) = compile_binary_inputs("binary_algorithms");
+
+    assert_eq!(
+        run_binary_algorithm("compose", &fst_1, &fst_2, b""),
+        "0\t1\t1\t3\t0.5\n1\n"
+    );
+    assert_eq!(
+        run_binary_algorithm("union", &fst_1, &fst_2, b""),
+        "0\t1\t1\t2\n0\t2\t0\t0\n1\n2\t3\t2\t3\t0.5\n3\n"
+    );
+    assert_eq!(
+        run_binary_algorithm("concat", &fst_1, &fst_2, b""),
+        "0\t1\t1\t2\n1\t2\t0\t0\n2\t3\t2\t3\t0.5\n3\n"
+    );
+
+    // One of the inputs can be read from stdin.
+    let data_1 = fs::read(&fst_1).unwrap();
+    assert_eq!(
+        run_binary_algorithm("compose", "-", &fst_2, &data_1),
+        "0\t1\t1\t3\t0.5\n1\n"
+    );
+}
+
+#[test]
+fn test_binary_algorithm_two_stdin_inputs() {
+    let (fst_1, _) = compile_binary_inputs("two_stdin_inputs");
+    let data_1 = fs::read(&fst_1).unwrap();
+
+    let output = run_cli(&["union", "-", "-"], &data_1);
+    assert_eq!(output.status.code(), Some(74));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Only one of the input FSTs can be read from stdin"));
+}
+
+#[test]
+fn test_binary_algorithm_arc_type_mismatch() {
+    let (fst_1, _) = compile_binary_inputs("arc_type_mismatch");
+    let compiled = run_cli(&["compile", "--arc_type", "log"], b"0\t1\t2\t3\n1\n");
+    assert!(compiled.status.success());
+    let fst_log = write_tmp_file("arc_type_mismatch_log.fst", &compiled.stdout);
+
+    let output = run_cli(&["compose", &fst_1, &fst_log], b"");
+    assert_eq!(output.status.code(), Some(70));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Input FSTs must have the same arc type"));
+}
@@ -16,6 +16,7 @@ enum DfsStateColor {
     Black,
 }
 
+/// Callbacks invoked while traversing an FST in depth-first order with `dfs_visit`.
 pub trait Visitor<'a, F: Fst> {
     /// Invoked before DFS visit.
     fn init_visit(&mut self, fst: &'a F);
@@ -91,6 +92,9 @@ impl<I: Iterator> OpenFstIterator<I> {
     }
 }
 
+/// Performs a depth-first visit of the states of an FST reachable through the arcs accepted
+/// by `arc_filter`. If `access_only` is not set, the states not accessible from the start
+/// state are also visited.
 pub fn dfs_visit<'a, F: Fst + ExpandedFst, V: Visitor<'a, F>, A: ArcFilter<F::W>>(
     fst: &'a F,
     visitor: &mut V,
@@ -1,11 +1,15 @@
 use std::cell::RefCell;
 use std::collections::hash_map::{Entry, HashMap};
+use std::fmt::Write as FmtWrite;
+use std::fs::{read_to_string, File};
+use std::io::Write;
+use std::path::Path;
 
-use anyhow::{Result, Context};
+use anyhow::{Context, Result};
 
 use crate::algorithms::{rm_final_epsilon, ArcMapper, FinalArc, MapFinalAction};
 use crate::fst_traits::{ExpandedFst, MutableFst};
-use crate::semirings::Semiring;
+use crate::semirings::{Semiring, SerializableSemiring};
 use crate::Arc;
 use crate::Label;
 use crate::EPS_LABEL;
@@ -86,6 +90,82 @@ impl<W: Semiring> EncodeTableMut<W> {
     }
 }
 
+impl<W: SerializableSemiring> EncodeTable<W> {
+    /// Writes the text representation of the table into a String. The first line contains
+    /// the `encode_labels` and `encode_weights` flags. Then, the n-th following line contains
+    /// the `ilabel`, `olabel` and `weight` encoded by the key n.
+    pub fn text(&self) -> Result<String> {
+        let table = self.0.borrow();
+        let mut res = String::new();
+        writeln!(
+            res,
+            "{}\t{}",
+            table.encode_labels as u8, table.encode_weights as u8
+        )?;
+        for tuple in table.id_to_tuple.iter() {
+            writeln!(res, "{}\t{}\t{}", tuple.ilabel, tuple.olabel, tuple.weight)?;
+        }
+        Ok(res)
+    }
+
+    /// Serializes the table as a text file.
+    pub fn write_text<P: AsRef<Path>>(&self, path_output: P) -> Result<()> {
+        let mut file = File::create(path_output.as_ref())?;
+        write!(file, "{}", self.text()?)?;
+        Ok(())
+    }
+
+    /// Loads a table from its text representation.
+    pub fn from_text_string(table_string: &str) -> Result<Self> {
+        let mut lines = table_string.lines();
+        let flags: Vec<_> = lines
+            .next()
+            .ok_or_else(|| format_err!("Empty encode table"))?
+            .split('\t')
+            .collect();
+        ensure!(
+            flags.len() == 2,
+            "Encode table : expected two flags on the first line, found {}",
+            flags.len()
+        );
+        let parse_flag = |flag: &str| -> Result<bool> {
+            match flag {
+                "0" => Ok(false),
+                "1" => Ok(true),
+                _ => bail!("Encode table : invalid flag {:?}", flag),
+            }
+        };
+        let mut table = EncodeTableMut::new(parse_flag(flags[0])?, parse_flag(flags[1])?);
+        for line in lines {
+            let fields: Vec<_> = line.split('\t').collect();
+            ensure!(
+                fields.len() == 3,
+                "Encode table : expected three fields, found {} : {:?}",
+                fields.len(),
+                line
+            );
+            let weight = match W::parse_text(fields[2]) {
+                Ok(("", weight)) => weight,
+                _ => bail!("Encode table : invalid weight {:?}", fields[2]),
+            };
+            table.encode(EncodeTuple {
+                ilabel: fields[0].parse()?,
+                olabel: fields[1].parse()?,
+                weight,
+            });
+        }
+        Ok(EncodeTable(RefCell::new(table)))
+    }
+
+    /// Deserializes a table in text format from a path.
+    pub fn read_text<P: AsRef<Path>>(path_text_table: P) -> Result<Self> {
+        let table_string = read_to_string(path_text_table.as_ref()).with_context(|| {
+            format_err!("Can't open encode table {:?}", path_text_table.as_ref())
+        })?;
+        Self::from_text_string(&table_string)
+    }
+}
+
 impl<W: Semiring> Default for EncodeTableMut<W> {
     fn default() -> Self {
         Self::new(true, true)
@@ -217,3 +297,29 @@ where
     rm_final_epsilon(fst)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::SerializableFst;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_encode_table_text_round_trip() -> Result<()> {
+        let fst_ref = VectorFst::<TropicalWeight>::from_text_string(
+            "0\t1\t1\t2\t0.5\n0\t1\t3\t2\n1\t2\t1\t2\t0.5\n2\t1.5\n",
+        )?;
+        let mut fst = fst_ref.clone();
+        let encode_table = encode(&mut fst, true, true)?;
+
+        let text = encode_table.text()?;
+        let loaded_table = EncodeTable::<TropicalWeight>::from_text_string(&text)?;
+        assert_eq!(loaded_table.text()?, text);
+
+        decode(&mut fst, loaded_table)?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+}
@@ -74,7 +74,7 @@ pub use self::{
     condense::condense,
     connect::connect,
     determinize::{determinize, determinize_with_distance, DeterminizeType},
-    encode::{decode, encode},
+    encode::{decode, encode, EncodeTable},
     fst_convert::{fst_convert, fst_convert_from_ref},
     inversion::invert,
     isomorphic::isomorphic,
@@ -86,14 +86,15 @@ pub use self::{
     replace::{replace, ReplaceFst},
     reverse::reverse,
     reweight::{reweight, ReweightType},
-    rm_epsilon::{rm_epsilon, RmEpsilonFst},
+    rm_epsilon::{rm_epsilon, rm_epsilon_with_config, RmEpsilonConfig, RmEpsilonFst},
     rm_final_epsilon::rm_final_epsilon,
     shortest_distance::shortest_distance,
     shortest_path::shortest_path,
     state_sort::state_sort,
     top_sort::top_sort,
     union::{union, UnionFst},
-    weight_convert::{weight_convert, WeightConverter},
+    visitors::scc_stats,
+    weight_convert::{weight_convert, weight_convert_to, WeightConverter},
 };
 
 pub use self::factor_weight::{
@@ -2,7 +2,7 @@ use std::borrow::Borrow;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use unsafe_unwrap::UnsafeUnwrap;
 
 use crate::algorithms::arc_filters::{ArcFilter, EpsilonArcFilter};
@@ -212,7 +212,7 @@ where
     }
 
     if weight_threshold != F::W::zero() || state_threshold != None {
-        todo!("Implement Prune!")
+        bail!("Pruning with a weight or state threshold is not implemented yet")
     }
 
     if connect && weight_threshold == F::W::zero() && state_threshold == None {
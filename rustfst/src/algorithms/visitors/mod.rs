@@ -1,2 +1,3 @@
 mod scc_visitors;
+pub use scc_visitors::scc_stats;
 pub(crate) use scc_visitors::SccVisitor;
@@ -1,4 +1,7 @@
-use crate::algorithms::dfs_visit::Visitor;
+use anyhow::Result;
+
+use crate::algorithms::arc_filters::AnyArcFilter;
+use crate::algorithms::dfs_visit::{dfs_visit, Visitor};
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst};
 use crate::Arc;
 use crate::{StateId, NO_STATE_ID};
@@ -6,9 +9,14 @@ use crate::{StateId, NO_STATE_ID};
 use crate::fst_properties::FstProperties;
 use unsafe_unwrap::UnsafeUnwrap;
 
+/// Visitor computing the strongly connected components of an FST as well as its
+/// accessible and coaccessible states. Also computes the cycle related properties.
 pub struct SccVisitor<'a, F: Fst> {
+    /// SCC index of each state if requested, numbered in topological order.
     pub scc: Option<Vec<i32>>,
+    /// Whether each state is accessible, if requested.
     pub access: Option<Vec<bool>>,
+    /// Whether each state is coaccessible.
     pub coaccess: Vec<bool>,
     start: StateId,
     fst: &'a F,
@@ -17,11 +25,15 @@ pub struct SccVisitor<'a, F: Fst> {
     lowlink: Vec<i32>,
     onstack: Vec<bool>,
     scc_stack: Vec<StateId>,
+    /// Number of SCCs.
     pub nscc: i32,
+    /// Properties computed during the visit.
     pub props: FstProperties,
 }
 
 impl<'a, F: 'a + Fst + ExpandedFst> SccVisitor<'a, F> {
+    /// Creates a visitor for `fst`. The `scc` and `access` vectors are only filled if
+    /// respectively `compute_scc` and `compute_acess` are set.
     pub fn new(fst: &'a F, compute_scc: bool, compute_acess: bool) -> Self {
         let n = fst.num_states();
         let mut props = FstProperties::empty();
@@ -171,3 +183,43 @@ impl<'a, F: 'a + ExpandedFst> Visitor<'a, F> for SccVisitor<'a, F> {
         }
     }
 }
+
+/// Returns the number of strongly connected components of an FST, along with whether each
+/// state is accessible and whether it is coaccessible.
+pub fn scc_stats<F: ExpandedFst>(fst: &F) -> Result<(usize, Vec<bool>, Vec<bool>)> {
+    let mut visitor = SccVisitor::new(fst, false, true);
+    dfs_visit(fst, &mut visitor, &AnyArcFilter {}, false);
+    // Safe to unwrap as the accessibility has been requested.
+    let access = visitor.access.unwrap();
+    Ok((visitor.nscc as usize, access, visitor.coaccess))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_scc_stats() -> Result<()> {
+        // 0 -> 1 <-> 2 -> 3 (final), 4 is not accessible and 2 -> 5 is not coaccessible.
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(6);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, TropicalWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(3, 3, TropicalWeight::one(), 1))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::one(), 3))?;
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::one(), 5))?;
+        fst.add_arc(4, Arc::new(6, 6, TropicalWeight::one(), 3))?;
+
+        let (nscc, access, coaccess) = scc_stats(&fst)?;
+        assert_eq!(nscc, 5);
+        assert_eq!(access, vec![true, true, true, true, false, true]);
+        assert_eq!(coaccess, vec![true, true, true, true, true, false]);
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
+use std::convert::TryFrom;
+
 use anyhow::Result;
 
+use crate::algorithms::weight_converters::StandardWeightConverter;
 use crate::algorithms::{FinalArc, MapFinalAction};
+use crate::fst_impls::VectorFst;
 use crate::fst_traits::{AllocableFst, ExpandedFst, MutableFst};
 use crate::semirings::Semiring;
 use crate::{Arc, EPS_LABEL};
@@ -124,3 +128,31 @@ where
 
     Ok(fst_out)
 }
+
+/// Convert an FST to the semiring `W` using the standard conversion between the two semirings
+/// (see `StandardWeightConverter`).
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::algorithms::weight_convert_to;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{LogWeight, Semiring, TropicalWeight};
+/// # use rustfst::utils::acceptor;
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(0.5));
+/// let fst_log = weight_convert_to::<LogWeight, _>(&fst)?;
+///
+/// assert_eq!(fst_log, acceptor(&[1, 2, 3], LogWeight::new(0.5)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn weight_convert_to<W, F>(fst_in: &F) -> Result<VectorFst<W>>
+where
+    F: ExpandedFst,
+    W: Semiring + TryFrom<F::W> + 'static,
+    <W as TryFrom<F::W>>::Error: Into<anyhow::Error>,
+{
+    weight_convert(fst_in, &mut StandardWeightConverter {})
+}
@@ -1,7 +1,15 @@
 mod from_gallic_mapper;
+mod precision_converter;
+mod product_projection_converter;
+mod signed_log_converter;
 mod simple_weight_converter;
+mod standard_weight_converter;
 mod to_gallic_converter;
 
 pub use self::from_gallic_mapper::FromGallicConverter;
+pub use self::precision_converter::PrecisionConverter;
+pub use self::product_projection_converter::{ProductFirstConverter, ProductSecondConverter};
+pub use self::signed_log_converter::SignedLogConverter;
 pub use self::simple_weight_converter::SimpleWeightConverter;
+pub use self::standard_weight_converter::StandardWeightConverter;
 pub use self::to_gallic_converter::ToGallicConverter;
@@ -0,0 +1,62 @@
+use anyhow::Result;
+
+use crate::algorithms::{FinalArc, MapFinalAction, WeightConverter};
+use crate::semirings::Semiring;
+use crate::Arc;
+
+/// Mapper that leaves labels and nextstate unchanged and converts the weights between the
+/// single and double precision versions of a semiring (e.g `TropicalWeight` and
+/// `TropicalWeight64`) using the `From` trait.
+pub struct PrecisionConverter {}
+
+impl<SI, SO> WeightConverter<SI, SO> for PrecisionConverter
+where
+    SI: Semiring,
+    SO: Semiring + From<SI>,
+{
+    fn arc_map(&mut self, arc: &Arc<SI>) -> Result<Arc<SO>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            SO::from(arc.weight.clone()),
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(&mut self, final_arc: &FinalArc<SI>) -> Result<FinalArc<SO>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: SO::from(final_arc.weight.clone()),
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::weight_convert;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::{LogWeight, LogWeight64, TropicalWeight, TropicalWeight64};
+    use crate::utils::acceptor;
+
+    #[test]
+    fn test_precision_converter() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(1.5));
+        let fst_64: VectorFst<TropicalWeight64> = weight_convert(&fst, &mut PrecisionConverter {})?;
+        assert_eq!(fst_64, acceptor(&[1, 2, 3], TropicalWeight64::new(1.5)));
+        let fst_32: VectorFst<TropicalWeight> =
+            weight_convert(&fst_64, &mut PrecisionConverter {})?;
+        assert_eq!(fst_32, fst);
+
+        let fst: VectorFst<LogWeight64> = acceptor(&[1], LogWeight64::new(0.25));
+        let fst_32: VectorFst<LogWeight> = weight_convert(&fst, &mut PrecisionConverter {})?;
+        assert_eq!(fst_32, acceptor(&[1], LogWeight::new(0.25)));
+        Ok(())
+    }
+}
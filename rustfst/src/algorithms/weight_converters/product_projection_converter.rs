@@ -0,0 +1,98 @@
+use anyhow::Result;
+
+use crate::algorithms::{FinalArc, MapFinalAction, WeightConverter};
+use crate::semirings::{ProductWeight, Semiring};
+use crate::Arc;
+
+/// Mapper that leaves labels and nextstate unchanged and keeps the first component of a
+/// `ProductWeight`.
+pub struct ProductFirstConverter {}
+
+/// Mapper that leaves labels and nextstate unchanged and keeps the second component of a
+/// `ProductWeight`.
+pub struct ProductSecondConverter {}
+
+impl<W1, W2> WeightConverter<ProductWeight<W1, W2>, W1> for ProductFirstConverter
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn arc_map(&mut self, arc: &Arc<ProductWeight<W1, W2>>) -> Result<Arc<W1>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            arc.weight.value1().clone(),
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(
+        &mut self,
+        final_arc: &FinalArc<ProductWeight<W1, W2>>,
+    ) -> Result<FinalArc<W1>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: final_arc.weight.value1().clone(),
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+impl<W1, W2> WeightConverter<ProductWeight<W1, W2>, W2> for ProductSecondConverter
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn arc_map(&mut self, arc: &Arc<ProductWeight<W1, W2>>) -> Result<Arc<W2>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            arc.weight.value2().clone(),
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(
+        &mut self,
+        final_arc: &FinalArc<ProductWeight<W1, W2>>,
+    ) -> Result<FinalArc<W2>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: final_arc.weight.value2().clone(),
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::weight_convert;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::{LogWeight, TropicalWeight};
+    use crate::utils::acceptor;
+
+    #[test]
+    fn test_product_projection_converters() -> Result<()> {
+        let fst: VectorFst<ProductWeight<TropicalWeight, LogWeight>> = acceptor(
+            &[1, 2],
+            ProductWeight::new((TropicalWeight::new(1.0), LogWeight::new(2.0))),
+        );
+
+        let fst_1: VectorFst<TropicalWeight> = weight_convert(&fst, &mut ProductFirstConverter {})?;
+        assert_eq!(fst_1, acceptor(&[1, 2], TropicalWeight::new(1.0)));
+
+        let fst_2: VectorFst<LogWeight> = weight_convert(&fst, &mut ProductSecondConverter {})?;
+        assert_eq!(fst_2, acceptor(&[1, 2], LogWeight::new(2.0)));
+        Ok(())
+    }
+}
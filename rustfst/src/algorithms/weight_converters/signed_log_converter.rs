@@ -0,0 +1,94 @@
+use std::convert::TryFrom;
+
+use anyhow::Result;
+
+use crate::algorithms::{FinalArc, MapFinalAction, WeightConverter};
+use crate::semirings::{LogWeight, SignedLogWeight};
+use crate::Arc;
+
+/// Mapper that leaves labels and nextstate unchanged and converts the weights between
+/// `LogWeight` and `SignedLogWeight`. The conversion to `LogWeight` fails on negative weights.
+pub struct SignedLogConverter {}
+
+impl WeightConverter<LogWeight, SignedLogWeight> for SignedLogConverter {
+    fn arc_map(&mut self, arc: &Arc<LogWeight>) -> Result<Arc<SignedLogWeight>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            SignedLogWeight::from(arc.weight),
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(
+        &mut self,
+        final_arc: &FinalArc<LogWeight>,
+    ) -> Result<FinalArc<SignedLogWeight>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: SignedLogWeight::from(final_arc.weight),
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+impl WeightConverter<SignedLogWeight, LogWeight> for SignedLogConverter {
+    fn arc_map(&mut self, arc: &Arc<SignedLogWeight>) -> Result<Arc<LogWeight>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            LogWeight::try_from(arc.weight)?,
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(
+        &mut self,
+        final_arc: &FinalArc<SignedLogWeight>,
+    ) -> Result<FinalArc<LogWeight>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: LogWeight::try_from(final_arc.weight)?,
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::weight_convert;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::Semiring;
+    use crate::utils::acceptor;
+
+    #[test]
+    fn test_signed_log_converter() -> Result<()> {
+        let fst: VectorFst<LogWeight> = acceptor(&[1, 2], LogWeight::new(0.5));
+        let fst_signed: VectorFst<SignedLogWeight> =
+            weight_convert(&fst, &mut SignedLogConverter {})?;
+        assert_eq!(
+            fst_signed,
+            acceptor(&[1, 2], SignedLogWeight::from_sign_and_log(true, 0.5))
+        );
+        let fst_log: VectorFst<LogWeight> =
+            weight_convert(&fst_signed, &mut SignedLogConverter {})?;
+        assert_eq!(fst_log, fst);
+
+        let fst_negative: VectorFst<SignedLogWeight> =
+            acceptor(&[1], SignedLogWeight::from_sign_and_log(false, 0.5));
+        let res: Result<VectorFst<LogWeight>> =
+            weight_convert(&fst_negative, &mut SignedLogConverter {});
+        assert!(res.is_err());
+        Ok(())
+    }
+}
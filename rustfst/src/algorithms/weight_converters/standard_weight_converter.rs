@@ -0,0 +1,123 @@
+use std::convert::TryFrom;
+
+use anyhow::Result;
+
+use crate::algorithms::{FinalArc, MapFinalAction, WeightConverter};
+use crate::semirings::Semiring;
+use crate::Arc;
+
+/// Mapper that leaves labels and nextstate unchanged and converts the weights using the
+/// standard conversion between the two semirings, i.e their `From` or `TryFrom`
+/// implementation.
+///
+/// The built-in semirings provide the following conversions :
+/// - between `TropicalWeight`, `LogWeight` and `ProbabilityWeight`.
+/// - between `TropicalWeight` and `TropicalWeight64` and between `LogWeight` and `LogWeight64`.
+/// - between `LogWeight` and `SignedLogWeight`, failing on negative weights.
+/// - from any numerical semiring to `BooleanWeight`, all the non-zero weights being `true`.
+///
+/// Use `ProductFirstConverter` and `ProductSecondConverter` to project a `ProductWeight` on one
+/// of its components.
+pub struct StandardWeightConverter {}
+
+impl<SI, SO> WeightConverter<SI, SO> for StandardWeightConverter
+where
+    SI: Semiring,
+    SO: Semiring + TryFrom<SI>,
+    <SO as TryFrom<SI>>::Error: Into<anyhow::Error>,
+{
+    fn arc_map(&mut self, arc: &Arc<SI>) -> Result<Arc<SO>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            SO::try_from(arc.weight.clone()).map_err(|e| e.into())?,
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(&mut self, final_arc: &FinalArc<SI>) -> Result<FinalArc<SO>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: SO::try_from(final_arc.weight.clone()).map_err(|e| e.into())?,
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::{weight_convert, weight_convert_to};
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::{
+        BooleanWeight, LogWeight, LogWeight64, ProbabilityWeight, SignedLogWeight, TropicalWeight,
+        TropicalWeight64,
+    };
+    use crate::utils::acceptor;
+
+    #[test]
+    fn test_standard_weight_converter() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::new(2f32.ln()));
+
+        let fst_log: VectorFst<LogWeight> = weight_convert(&fst, &mut StandardWeightConverter {})?;
+        assert_eq!(fst_log, acceptor(&[1, 2], LogWeight::new(2f32.ln())));
+
+        let fst_proba: VectorFst<ProbabilityWeight> =
+            weight_convert(&fst_log, &mut StandardWeightConverter {})?;
+        assert_eq!(fst_proba, acceptor(&[1, 2], ProbabilityWeight::new(0.5)));
+
+        let fst_back: VectorFst<TropicalWeight> =
+            weight_convert(&fst_proba, &mut StandardWeightConverter {})?;
+        assert_eq!(fst_back, fst);
+
+        let fst_64: VectorFst<TropicalWeight64> =
+            weight_convert(&fst, &mut StandardWeightConverter {})?;
+        assert_eq!(
+            fst_64,
+            acceptor(&[1, 2], TropicalWeight64::new(f64::from(2f32.ln())))
+        );
+
+        let fst_bool: VectorFst<BooleanWeight> =
+            weight_convert(&fst, &mut StandardWeightConverter {})?;
+        assert_eq!(fst_bool, acceptor(&[1, 2], BooleanWeight::one()));
+
+        let fst_negative: VectorFst<SignedLogWeight> =
+            acceptor(&[1], SignedLogWeight::from_sign_and_log(false, 0.5));
+        let res: Result<VectorFst<LogWeight>> =
+            weight_convert(&fst_negative, &mut StandardWeightConverter {});
+        assert!(res.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_standard_weight_converter_precision() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(1.5));
+        let fst_64: VectorFst<TropicalWeight64> = weight_convert_to(&fst)?;
+        assert_eq!(fst_64, acceptor(&[1, 2, 3], TropicalWeight64::new(1.5)));
+        let fst_32: VectorFst<TropicalWeight> = weight_convert_to(&fst_64)?;
+        assert_eq!(fst_32, fst);
+
+        let fst: VectorFst<LogWeight64> = acceptor(&[1], LogWeight64::new(0.25));
+        let fst_32: VectorFst<LogWeight> = weight_convert_to(&fst)?;
+        assert_eq!(fst_32, acceptor(&[1], LogWeight::new(0.25)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_standard_weight_converter_signed_log() -> Result<()> {
+        let fst: VectorFst<LogWeight> = acceptor(&[1, 2], LogWeight::new(0.5));
+        let fst_signed: VectorFst<SignedLogWeight> = weight_convert_to(&fst)?;
+        assert_eq!(
+            fst_signed,
+            acceptor(&[1, 2], SignedLogWeight::from_sign_and_log(true, 0.5))
+        );
+        let fst_log: VectorFst<LogWeight> = weight_convert_to(&fst_signed)?;
+        assert_eq!(fst_log, fst);
+        Ok(())
+    }
+}
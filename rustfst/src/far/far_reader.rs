@@ -0,0 +1,238 @@
+use std::convert::TryFrom;
+use std::fs::read;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use nom::number::complete::{le_i32, le_i64};
+use nom::IResult;
+
+use crate::far::{
+    FarType, STLIST_FILE_VERSION, STLIST_MAGIC_NUMBER, STTABLE_FILE_VERSION, STTABLE_MAGIC_NUMBER,
+};
+use crate::fst_traits::SerializableFst;
+use crate::parsers::bin_fst::fst_header::OpenFstString;
+use crate::semirings::SerializableSemiring;
+
+/// Reads the FSTs stored in an archive. Compatible with OpenFST's `FarReader`.
+///
+/// The whole archive is loaded in memory but the FSTs are only parsed when they are accessed.
+/// Random access by key is done with a binary search for `STTable` archives and with a linear
+/// search for `STList` archives.
+#[derive(Debug)]
+pub struct FarReader<F: SerializableFst>
+where
+    F::W: SerializableSemiring,
+{
+    data: Vec<u8>,
+    far_type: FarType,
+    entries: Vec<FarEntry>,
+    fst_type: PhantomData<F>,
+}
+
+#[derive(Debug)]
+struct FarEntry {
+    key: String,
+    start: usize,
+    end: usize,
+}
+
+impl<F: SerializableFst> FarReader<F>
+where
+    F::W: SerializableSemiring,
+{
+    /// Opens the archive stored at `path`. The type of the archive is detected automatically.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = read(path.as_ref())
+            .with_context(|| format!("Can't open FAR file : {:?}", path.as_ref()))?;
+        Self::from_bytes(data)
+    }
+
+    /// Loads an archive from its binary representation.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let (_, (magic_number, version)) =
+            parse_far_header(&data).map_err(|_| format_err!("Error while parsing FAR header"))?;
+        let (far_type, entries) = if magic_number == STTABLE_MAGIC_NUMBER {
+            ensure!(
+                version == STTABLE_FILE_VERSION,
+                "Unsupported STTable version : {}",
+                version
+            );
+            (FarType::STTable, sttable_entries(&data)?)
+        } else if magic_number == STLIST_MAGIC_NUMBER {
+            ensure!(
+                version == STLIST_FILE_VERSION,
+                "Unsupported STList version : {}",
+                version
+            );
+            (FarType::STList, stlist_entries::<F>(&data)?)
+        } else {
+            bail!("Unknown FAR magic number : {}", magic_number)
+        };
+        Ok(Self {
+            data,
+            far_type,
+            entries,
+            fst_type: PhantomData,
+        })
+    }
+
+    /// Type of the archive.
+    pub fn far_type(&self) -> FarType {
+        self.far_type
+    }
+
+    /// Number of FSTs in the archive.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the archive doesn't contain any FST.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the keys in the order they are stored in the archive.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.key.as_str())
+    }
+
+    /// Returns true if an FST is stored with the key `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Parses the FST stored with the key `key`. Returns `None` if there is no such key.
+    pub fn get(&self, key: &str) -> Result<Option<F>> {
+        match self.find(key) {
+            Some(entry) => Ok(Some(self.parse_entry(entry)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Iterates over the keys and the FSTs in the order they are stored in the archive.
+    pub fn iter(&self) -> FarIterator<'_, F> {
+        FarIterator {
+            reader: self,
+            idx: 0,
+        }
+    }
+
+    fn find(&self, key: &str) -> Option<&FarEntry> {
+        match self.far_type {
+            FarType::STTable => self
+                .entries
+                .binary_search_by(|entry| entry.key.as_str().cmp(key))
+                .ok()
+                .map(|idx| &self.entries[idx]),
+            FarType::STList => self.entries.iter().find(|entry| entry.key == key),
+        }
+    }
+
+    fn parse_entry(&self, entry: &FarEntry) -> Result<F> {
+        let (_, fst) = F::parse_binary(&self.data[entry.start..entry.end]).map_err(|_| {
+            format_err!(
+                "Error while parsing the fst with key {:?} of the FAR",
+                entry.key
+            )
+        })?;
+        Ok(fst)
+    }
+}
+
+/// Iterator over the keys and the FSTs of an archive. Created with `FarReader::iter`.
+pub struct FarIterator<'a, F: SerializableFst>
+where
+    F::W: SerializableSemiring,
+{
+    reader: &'a FarReader<F>,
+    idx: usize,
+}
+
+impl<'a, F: SerializableFst> Iterator for FarIterator<'a, F>
+where
+    F::W: SerializableSemiring,
+{
+    type Item = Result<(&'a str, F)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.reader.entries.get(self.idx)?;
+        self.idx += 1;
+        Some(
+            self.reader
+                .parse_entry(entry)
+                .map(|fst| (entry.key.as_str(), fst)),
+        )
+    }
+}
+
+fn parse_far_header(i: &[u8]) -> IResult<&[u8], (i32, i32)> {
+    let (i, magic_number) = le_i32(i)?;
+    let (i, version) = le_i32(i)?;
+    Ok((i, (magic_number, version)))
+}
+
+fn parse_key(data: &[u8], start: usize) -> Result<(String, usize)> {
+    let i = data
+        .get(start..)
+        .ok_or_else(|| format_err!("Invalid FAR entry position : {}", start))?;
+    let (rest, key) =
+        OpenFstString::parse(i).map_err(|_| format_err!("Error while parsing a FAR key"))?;
+    Ok((key.into(), data.len() - rest.len()))
+}
+
+/// The index of an STTable is a vector of positions (size + elements) followed by its size.
+fn sttable_entries(data: &[u8]) -> Result<Vec<FarEntry>> {
+    let parse_i64 = |pos: usize| -> Result<i64> {
+        let (_, v) = le_i64::<()>(&data[pos..]).map_err(|_| format_err!("Truncated STTable"))?;
+        Ok(v)
+    };
+
+    ensure!(data.len() >= 8 + 16, "Truncated STTable");
+    let num_keys = parse_i64(data.len() - 8)?;
+    // Size of the index and of the size stored after it.
+    let index_len = usize::try_from(num_keys)
+        .ok()
+        .and_then(|n| n.checked_add(2))
+        .and_then(|n| n.checked_mul(8))
+        .filter(|len| len.checked_add(8).is_some_and(|len| len <= data.len()))
+        .ok_or_else(|| format_err!("Invalid number of keys in STTable : {}", num_keys))?;
+    let num_keys = num_keys as usize;
+    let index_start = data.len() - index_len;
+
+    let positions = (0..num_keys)
+        .map(|idx| parse_i64(index_start + 8 * (idx + 1)).map(|pos| pos as usize))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut entries = Vec::with_capacity(num_keys);
+    for (idx, pos) in positions.iter().enumerate() {
+        let (key, start) = parse_key(data, *pos)?;
+        let end = positions.get(idx + 1).cloned().unwrap_or(index_start);
+        ensure!(start <= end, "Invalid FAR entry position : {}", pos);
+        entries.push(FarEntry { key, start, end });
+    }
+    Ok(entries)
+}
+
+/// The FSTs of an STList are stored one after the other and terminated by an empty key.
+/// They need to be parsed to find where each of them ends.
+fn stlist_entries<F: SerializableFst>(data: &[u8]) -> Result<Vec<FarEntry>>
+where
+    F::W: SerializableSemiring,
+{
+    let mut entries = vec![];
+    let mut pos = 8;
+    loop {
+        let (key, start) = parse_key(data, pos)?;
+        if key.is_empty() {
+            break;
+        }
+        let (rest, _) = F::parse_binary(&data[start..]).map_err(|_| {
+            format_err!("Error while parsing the fst with key {:?} of the FAR", key)
+        })?;
+        let end = data.len() - rest.len();
+        entries.push(FarEntry { key, start, end });
+        pos = end;
+    }
+    Ok(entries)
+}
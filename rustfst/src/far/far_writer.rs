@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::far::{
+    FarType, STLIST_FILE_VERSION, STLIST_MAGIC_NUMBER, STTABLE_FILE_VERSION, STTABLE_MAGIC_NUMBER,
+};
+use crate::fst_traits::SerializableFst;
+use crate::parsers::bin_fst::fst_header::OpenFstString;
+use crate::parsers::bin_fst::utils_serialization::{write_bin_i32, write_bin_i64};
+use crate::semirings::SerializableSemiring;
+
+/// Writes FSTs sequentially in an archive. Compatible with OpenFST's `FarWriter`.
+///
+/// The archive is only valid once `close` has been called. If the writer is dropped without
+/// being closed, it is closed and the errors are ignored.
+pub struct FarWriter<F: SerializableFst>
+where
+    F::W: SerializableSemiring,
+{
+    file: BufWriter<File>,
+    far_type: FarType,
+    pos: usize,
+    positions: Vec<usize>,
+    last_key: Option<String>,
+    closed: bool,
+    fst_type: PhantomData<F>,
+}
+
+impl<F: SerializableFst> FarWriter<F>
+where
+    F::W: SerializableSemiring,
+{
+    /// Creates an empty archive of type `far_type` at `path`.
+    pub fn create<P: AsRef<Path>>(path: P, far_type: FarType) -> Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+        match far_type {
+            FarType::STTable => {
+                write_bin_i32(&mut file, STTABLE_MAGIC_NUMBER)?;
+                write_bin_i32(&mut file, STTABLE_FILE_VERSION)?;
+            }
+            FarType::STList => {
+                write_bin_i32(&mut file, STLIST_MAGIC_NUMBER)?;
+                write_bin_i32(&mut file, STLIST_FILE_VERSION)?;
+            }
+        };
+        Ok(Self {
+            file,
+            far_type,
+            pos: 8,
+            positions: vec![],
+            last_key: None,
+            closed: false,
+            fst_type: PhantomData,
+        })
+    }
+
+    /// Type of the archive being written.
+    pub fn far_type(&self) -> FarType {
+        self.far_type
+    }
+
+    /// Appends `fst` to the archive. For `STTable` archives, the keys must be added in strictly
+    /// increasing order.
+    pub fn add(&mut self, key: &str, fst: &F) -> Result<()> {
+        ensure!(
+            !self.closed,
+            "FarWriter : the archive has already been closed"
+        );
+        ensure!(!key.is_empty(), "FarWriter : the key can't be empty");
+        if self.far_type == FarType::STTable {
+            if let Some(last_key) = &self.last_key {
+                ensure!(
+                    key > last_key.as_str(),
+                    "FarWriter : keys must be added in increasing order. {:?} added after {:?}",
+                    key,
+                    last_key
+                );
+            }
+        }
+
+        let mut buffer = vec![];
+        OpenFstString::new(key).write(&mut buffer)?;
+        fst.write_binary(&mut buffer)?;
+        self.file.write_all(&buffer)?;
+
+        self.positions.push(self.pos);
+        self.pos += buffer.len();
+        self.last_key = Some(key.to_string());
+        Ok(())
+    }
+
+    /// Writes the end of the archive and flushes it.
+    pub fn close(mut self) -> Result<()> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        match self.far_type {
+            FarType::STTable => {
+                // The index is stored as a vector of positions followed by its size.
+                write_bin_i64(&mut self.file, self.positions.len() as i64)?;
+                for pos in &self.positions {
+                    write_bin_i64(&mut self.file, *pos as i64)?;
+                }
+                write_bin_i64(&mut self.file, self.positions.len() as i64)?;
+            }
+            FarType::STList => {
+                // An empty key marks the end of the list.
+                OpenFstString::new("").write(&mut self.file)?;
+            }
+        };
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+impl<F: SerializableFst> Drop for FarWriter<F>
+where
+    F::W: SerializableSemiring,
+{
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
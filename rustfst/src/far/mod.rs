@@ -0,0 +1,57 @@
+//! An FST archive (FAR) stores a collection of FSTs indexed by a string key. Two formats
+//! compatible with OpenFST are supported :
+//!
+//! - `STTable` : the keys must be added in increasing order and an index is stored at the end
+//!   of the archive, allowing random access to the FSTs.
+//! - `STList` : the FSTs are stored sequentially, in any order, without index.
+//!
+//! The FSTs are stored using the binary format of `SerializableFst`.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use tempfile::tempdir;
+//! # use rustfst::far::{FarReader, FarType, FarWriter};
+//! # use rustfst::fst_impls::VectorFst;
+//! # use rustfst::semirings::{Semiring, TropicalWeight};
+//! # use rustfst::utils::acceptor;
+//! # fn main() -> Result<()> {
+//! # let dir = tempdir()?;
+//! # let path = dir.path().join("archive.far");
+//! let fst_1: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+//! let fst_2: VectorFst<TropicalWeight> = acceptor(&[3], TropicalWeight::one());
+//!
+//! let mut writer = FarWriter::create(&path, FarType::STTable)?;
+//! writer.add("a", &fst_1)?;
+//! writer.add("b", &fst_2)?;
+//! writer.close()?;
+//!
+//! let reader = FarReader::<VectorFst<TropicalWeight>>::open(&path)?;
+//! assert_eq!(reader.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+//! assert_eq!(reader.get("b")?, Some(fst_2));
+//! assert_eq!(reader.get("c")?, None);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use self::far_reader::{FarIterator, FarReader};
+pub use self::far_writer::FarWriter;
+
+mod far_reader;
+mod far_writer;
+mod test;
+
+/// Format of an FST archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FarType {
+    /// Sorted keys with an index allowing random access. Default format of OpenFST.
+    STTable,
+    /// Sequential list of FSTs.
+    STList,
+}
+
+pub(crate) static STTABLE_MAGIC_NUMBER: i32 = 2_125_656_924;
+pub(crate) static STTABLE_FILE_VERSION: i32 = 1;
+pub(crate) static STLIST_MAGIC_NUMBER: i32 = 5_656_924;
+pub(crate) static STLIST_FILE_VERSION: i32 = 1;
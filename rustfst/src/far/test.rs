@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use std::fs::read;
+
+    use anyhow::Result;
+    use tempfile::tempdir;
+
+    use crate::far::{FarReader, FarType, FarWriter};
+    use crate::fst_impls::{CompactAcceptorFst, VectorFst};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::acceptor;
+
+    fn fsts() -> Vec<(String, VectorFst<TropicalWeight>)> {
+        vec![
+            ("a".to_string(), acceptor(&[1, 2], TropicalWeight::one())),
+            ("b".to_string(), acceptor(&[3], TropicalWeight::new(2.0))),
+            ("c".to_string(), acceptor(&[], TropicalWeight::one())),
+        ]
+    }
+
+    fn check_roundtrip(far_type: FarType) -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("archive.far");
+        let fsts = fsts();
+
+        let mut writer = FarWriter::create(&path, far_type)?;
+        for (key, fst) in &fsts {
+            writer.add(key, fst)?;
+        }
+        writer.close()?;
+
+        let reader = FarReader::<VectorFst<TropicalWeight>>::open(&path)?;
+        assert_eq!(reader.far_type(), far_type);
+        assert_eq!(reader.len(), 3);
+        assert_eq!(reader.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        for (key, fst) in &fsts {
+            assert!(reader.contains_key(key));
+            assert_eq!(reader.get(key)?.as_ref(), Some(fst));
+        }
+        assert_eq!(reader.get("d")?, None);
+
+        let read_fsts = reader
+            .iter()
+            .map(|r| r.map(|(key, fst)| (key.to_string(), fst)))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(read_fsts, fsts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_sttable() -> Result<()> {
+        check_roundtrip(FarType::STTable)
+    }
+
+    #[test]
+    fn test_far_stlist() -> Result<()> {
+        check_roundtrip(FarType::STList)
+    }
+
+    #[test]
+    fn test_far_sttable_layout() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("archive.far");
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1], TropicalWeight::one());
+
+        let mut writer = FarWriter::create(&path, FarType::STTable)?;
+        writer.add("k", &fst)?;
+        writer.close()?;
+
+        let data = read(&path)?;
+        assert_eq!(&data[0..4], &2_125_656_924i32.to_le_bytes());
+        assert_eq!(&data[4..8], &1i32.to_le_bytes());
+        // Key of the first entry.
+        assert_eq!(&data[8..12], &1i32.to_le_bytes());
+        assert_eq!(&data[12..13], b"k");
+        // Index : [size, position of the first entry] followed by the number of keys.
+        let mut index = vec![];
+        for v in &[1i64, 8, 1] {
+            index.extend_from_slice(&v.to_le_bytes());
+        }
+        assert!(data.ends_with(&index));
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_sttable_unsorted_keys() -> Result<()> {
+        let dir = tempdir()?;
+        let fsts = fsts();
+
+        let mut writer = FarWriter::create(dir.path().join("table.far"), FarType::STTable)?;
+        writer.add("b", &fsts[0].1)?;
+        assert!(writer.add("a", &fsts[1].1).is_err());
+        assert!(writer.add("b", &fsts[1].1).is_err());
+
+        let mut writer = FarWriter::create(dir.path().join("list.far"), FarType::STList)?;
+        writer.add("b", &fsts[0].1)?;
+        writer.add("a", &fsts[1].1)?;
+        writer.close()?;
+        let reader = FarReader::<VectorFst<TropicalWeight>>::open(dir.path().join("list.far"))?;
+        assert_eq!(reader.keys().collect::<Vec<_>>(), vec!["b", "a"]);
+        assert_eq!(reader.get("a")?.as_ref(), Some(&fsts[1].1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_closed_on_drop() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("archive.far");
+        {
+            let mut writer = FarWriter::create(&path, FarType::STTable)?;
+            writer.add("a", &fsts()[0].1)?;
+        }
+        let reader = FarReader::<VectorFst<TropicalWeight>>::open(&path)?;
+        assert_eq!(reader.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_compact_fst() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("archive.far");
+        let compact_fsts = fsts()
+            .into_iter()
+            .map(|(key, fst)| Ok((key, CompactAcceptorFst::from_fst(&fst)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut writer = FarWriter::create(&path, FarType::STTable)?;
+        for (key, fst) in &compact_fsts {
+            writer.add(key, fst)?;
+        }
+        writer.close()?;
+
+        let reader = FarReader::<CompactAcceptorFst<TropicalWeight>>::open(&path)?;
+        assert_eq!(reader.get("b")?.as_ref(), Some(&compact_fsts[1].1));
+
+        // The members have the compact fst type.
+        assert!(FarReader::<VectorFst<TropicalWeight>>::open(&path)?
+            .get("b")
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_empty() -> Result<()> {
+        let dir = tempdir()?;
+        for far_type in &[FarType::STTable, FarType::STList] {
+            let path = dir.path().join("archive.far");
+            FarWriter::<VectorFst<TropicalWeight>>::create(&path, *far_type)?.close()?;
+            let reader = FarReader::<VectorFst<TropicalWeight>>::open(&path)?;
+            assert!(reader.is_empty());
+            assert_eq!(reader.far_type(), *far_type);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_invalid() -> Result<()> {
+        let data = vec![0u8; 32];
+        assert!(FarReader::<VectorFst<TropicalWeight>>::from_bytes(data).is_err());
+
+        let dir = tempdir()?;
+        let path = dir.path().join("archive.far");
+        let mut writer = FarWriter::create(&path, FarType::STTable)?;
+        for (key, fst) in &fsts() {
+            writer.add(key, fst)?;
+        }
+        writer.close()?;
+        let data = read(&path)?;
+        let num_keys_pos = data.len() - 8;
+        for num_keys in &[-1i64, 1 << 40, i64::MAX / 4, i64::MAX] {
+            let mut data = data.clone();
+            data[num_keys_pos..].copy_from_slice(&num_keys.to_le_bytes());
+            let err = FarReader::<VectorFst<TropicalWeight>>::from_bytes(data).unwrap_err();
+            assert!(err.to_string().contains("Invalid number of keys"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use std::fmt::Debug;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::number::complete::le_i32;
+use nom::IResult;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_i32;
+use crate::semirings::{Semiring, SerializableSemiring};
+use crate::{Arc, Label, StateId, NO_LABEL, NO_STATE_ID};
+
+/// A compactor defines how the arcs of a `CompactFst` are stored in memory.
+///
+/// Each state is represented by a list of `Element`s. The final weight of a state, if any,
+/// is stored as the first element of the list and is obtained by compacting the arc
+/// `Arc(NO_LABEL, NO_LABEL, final_weight, NO_STATE_ID)`.
+pub trait Compactor<W: Semiring>: Debug {
+    /// Compact representation of an arc.
+    type Element: Debug + Clone + PartialEq;
+
+    /// String identifying the compactor. Used to build the `fst_type` of the `CompactFst`.
+    fn compactor_type() -> String;
+
+    /// Number of elements used to represent each state if it is fixed, `None` otherwise.
+    fn size() -> Option<usize>;
+
+    /// Turns an arc leaving the state `s` into its compact representation. Fails if
+    /// the arc can't be represented with this compactor.
+    fn compact(s: StateId, arc: &Arc<W>) -> Result<Self::Element>;
+
+    /// Turns back an element stored in the state `s` into an arc.
+    fn expand(s: StateId, element: &Self::Element) -> Arc<W>;
+
+    /// Parses an element in binary format. Compatible with OpenFST.
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element>
+    where
+        W: SerializableSemiring;
+
+    /// Writes an element in binary format. Compatible with OpenFST.
+    fn write_element<F: Write>(element: &Self::Element, file: &mut F) -> Result<()>
+    where
+        W: SerializableSemiring;
+}
+
+#[inline]
+fn is_final_arc<W>(arc: &Arc<W>) -> bool {
+    arc.ilabel == NO_LABEL
+}
+
+#[inline]
+fn parse_label(i: &[u8]) -> IResult<&[u8], Label> {
+    let (i, label) = le_i32(i)?;
+    Ok((i, if label < 0 { NO_LABEL } else { label as Label }))
+}
+
+#[inline]
+fn write_label<F: Write>(file: &mut F, label: Label) -> Result<()> {
+    if label == NO_LABEL {
+        write_bin_i32(file, -1)
+    } else {
+        write_bin_i32(file, label as i32)
+    }
+}
+
+#[inline]
+fn parse_state(i: &[u8]) -> IResult<&[u8], StateId> {
+    let (i, state) = le_i32(i)?;
+    Ok((
+        i,
+        if state < 0 {
+            NO_STATE_ID
+        } else {
+            state as StateId
+        },
+    ))
+}
+
+#[inline]
+fn write_state<F: Write>(file: &mut F, state: StateId) -> Result<()> {
+    if state == NO_STATE_ID {
+        write_bin_i32(file, -1)
+    } else {
+        write_bin_i32(file, state as i32)
+    }
+}
+
+fn check_acceptor<W: Semiring>(arc: &Arc<W>) -> Result<()> {
+    if arc.ilabel != arc.olabel {
+        bail!("Compactor requires an acceptor. Arc = {:?}", arc)
+    }
+    Ok(())
+}
+
+fn check_unweighted<W: Semiring>(arc: &Arc<W>) -> Result<()> {
+    if !arc.weight.is_one() {
+        bail!("Compactor requires an unweighted Fst. Arc = {:?}", arc)
+    }
+    Ok(())
+}
+
+fn check_string<W: Semiring>(s: StateId, arc: &Arc<W>) -> Result<()> {
+    if !is_final_arc(arc) && arc.nextstate != s + 1 {
+        bail!(
+            "Compactor requires a string Fst with states in path order. State = {}, Arc = {:?}",
+            s,
+            arc
+        )
+    }
+    Ok(())
+}
+
+/// Compactor for unweighted string FSTs: each state stores a single label.
+/// Equivalent to OpenFST's `StringCompactor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StringCompactor {}
+
+impl<W: Semiring> Compactor<W> for StringCompactor {
+    type Element = Label;
+
+    fn compactor_type() -> String {
+        "string".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+
+    fn compact(s: StateId, arc: &Arc<W>) -> Result<Self::Element> {
+        check_acceptor(arc)?;
+        check_unweighted(arc)?;
+        check_string(s, arc)?;
+        Ok(arc.ilabel)
+    }
+
+    fn expand(s: StateId, element: &Self::Element) -> Arc<W> {
+        let nextstate = if *element != NO_LABEL {
+            s + 1
+        } else {
+            NO_STATE_ID
+        };
+        Arc::new(*element, *element, W::one(), nextstate)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element>
+    where
+        W: SerializableSemiring,
+    {
+        parse_label(i)
+    }
+
+    fn write_element<F: Write>(element: &Self::Element, file: &mut F) -> Result<()>
+    where
+        W: SerializableSemiring,
+    {
+        write_label(file, *element)
+    }
+}
+
+/// Compactor for weighted string FSTs: each state stores a label and a weight.
+/// Equivalent to OpenFST's `WeightedStringCompactor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedStringCompactor {}
+
+impl<W: Semiring> Compactor<W> for WeightedStringCompactor {
+    type Element = (Label, W);
+
+    fn compactor_type() -> String {
+        "weighted_string".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        Some(1)
+    }
+
+    fn compact(s: StateId, arc: &Arc<W>) -> Result<Self::Element> {
+        check_acceptor(arc)?;
+        check_string(s, arc)?;
+        Ok((arc.ilabel, arc.weight.clone()))
+    }
+
+    fn expand(s: StateId, element: &Self::Element) -> Arc<W> {
+        let nextstate = if element.0 != NO_LABEL {
+            s + 1
+        } else {
+            NO_STATE_ID
+        };
+        Arc::new(element.0, element.0, element.1.clone(), nextstate)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element>
+    where
+        W: SerializableSemiring,
+    {
+        let (i, label) = parse_label(i)?;
+        let (i, weight) = W::parse_binary(i)?;
+        Ok((i, (label, weight)))
+    }
+
+    fn write_element<F: Write>(element: &Self::Element, file: &mut F) -> Result<()>
+    where
+        W: SerializableSemiring,
+    {
+        write_label(file, element.0)?;
+        element.1.write_binary(file)
+    }
+}
+
+/// Compactor for weighted acceptors: each arc is stored as a label, a weight and a nextstate.
+/// Equivalent to OpenFST's `AcceptorCompactor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AcceptorCompactor {}
+
+impl<W: Semiring> Compactor<W> for AcceptorCompactor {
+    type Element = ((Label, W), StateId);
+
+    fn compactor_type() -> String {
+        "acceptor".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        None
+    }
+
+    fn compact(_s: StateId, arc: &Arc<W>) -> Result<Self::Element> {
+        check_acceptor(arc)?;
+        Ok(((arc.ilabel, arc.weight.clone()), arc.nextstate))
+    }
+
+    fn expand(_s: StateId, element: &Self::Element) -> Arc<W> {
+        let ((label, weight), nextstate) = element;
+        Arc::new(*label, *label, weight.clone(), *nextstate)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element>
+    where
+        W: SerializableSemiring,
+    {
+        let (i, label) = parse_label(i)?;
+        let (i, weight) = W::parse_binary(i)?;
+        let (i, nextstate) = parse_state(i)?;
+        Ok((i, ((label, weight), nextstate)))
+    }
+
+    fn write_element<F: Write>(element: &Self::Element, file: &mut F) -> Result<()>
+    where
+        W: SerializableSemiring,
+    {
+        let ((label, weight), nextstate) = element;
+        write_label(file, *label)?;
+        weight.write_binary(file)?;
+        write_state(file, *nextstate)
+    }
+}
+
+/// Compactor for unweighted transducers: each arc is stored as an input label, an output label
+/// and a nextstate. Equivalent to OpenFST's `UnweightedCompactor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnweightedCompactor {}
+
+impl<W: Semiring> Compactor<W> for UnweightedCompactor {
+    type Element = ((Label, Label), StateId);
+
+    fn compactor_type() -> String {
+        "unweighted".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        None
+    }
+
+    fn compact(_s: StateId, arc: &Arc<W>) -> Result<Self::Element> {
+        check_unweighted(arc)?;
+        Ok(((arc.ilabel, arc.olabel), arc.nextstate))
+    }
+
+    fn expand(_s: StateId, element: &Self::Element) -> Arc<W> {
+        let ((ilabel, olabel), nextstate) = element;
+        Arc::new(*ilabel, *olabel, W::one(), *nextstate)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element>
+    where
+        W: SerializableSemiring,
+    {
+        let (i, ilabel) = parse_label(i)?;
+        let (i, olabel) = parse_label(i)?;
+        let (i, nextstate) = parse_state(i)?;
+        Ok((i, ((ilabel, olabel), nextstate)))
+    }
+
+    fn write_element<F: Write>(element: &Self::Element, file: &mut F) -> Result<()>
+    where
+        W: SerializableSemiring,
+    {
+        let ((ilabel, olabel), nextstate) = element;
+        write_label(file, *ilabel)?;
+        write_label(file, *olabel)?;
+        write_state(file, *nextstate)
+    }
+}
+
+/// Compactor for unweighted acceptors: each arc is stored as a label and a nextstate.
+/// Equivalent to OpenFST's `UnweightedAcceptorCompactor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnweightedAcceptorCompactor {}
+
+impl<W: Semiring> Compactor<W> for UnweightedAcceptorCompactor {
+    type Element = (Label, StateId);
+
+    fn compactor_type() -> String {
+        "unweighted_acceptor".to_string()
+    }
+
+    fn size() -> Option<usize> {
+        None
+    }
+
+    fn compact(_s: StateId, arc: &Arc<W>) -> Result<Self::Element> {
+        check_acceptor(arc)?;
+        check_unweighted(arc)?;
+        Ok((arc.ilabel, arc.nextstate))
+    }
+
+    fn expand(_s: StateId, element: &Self::Element) -> Arc<W> {
+        let (label, nextstate) = element;
+        Arc::new(*label, *label, W::one(), *nextstate)
+    }
+
+    fn parse_element(i: &[u8]) -> IResult<&[u8], Self::Element>
+    where
+        W: SerializableSemiring,
+    {
+        let (i, label) = parse_label(i)?;
+        let (i, nextstate) = parse_state(i)?;
+        Ok((i, (label, nextstate)))
+    }
+
+    fn write_element<F: Write>(element: &Self::Element, file: &mut F) -> Result<()>
+    where
+        W: SerializableSemiring,
+    {
+        let (label, nextstate) = element;
+        write_label(file, *label)?;
+        write_state(file, *nextstate)
+    }
+}
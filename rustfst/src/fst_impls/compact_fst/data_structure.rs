@@ -0,0 +1,264 @@
+use std::cell::OnceCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::fst_impls::compact_fst::compactors::Compactor;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{Arc, StateId, SymbolTable, NO_LABEL, NO_STATE_ID};
+
+/// Immutable FST whose arcs are stored in a compact form described by a `Compactor`.
+///
+/// The compact elements of all the states are stored in a single array. If the compactor uses
+/// a variable number of elements per state, an additional array stores the offset of the first
+/// element of each state.
+///
+/// `num_arcs`, `expanded_arcs`, `expanded_final_weight` and `fst_into_iter` decode the
+/// elements on the fly. The `Fst` traits hand out references to arcs and final weights, so
+/// `arcs_iter`, `final_weight` and `fst_iter` expand the states they visit into a cache, which
+/// is only allocated on first use and holds at most one entry per state. `clear_cache` releases
+/// it.
+pub struct CompactFst<W: Semiring, C: Compactor<W>> {
+    pub(crate) states: Option<Vec<usize>>,
+    pub(crate) compacts: Vec<C::Element>,
+    pub(crate) start: Option<StateId>,
+    pub(crate) num_states: usize,
+    pub(crate) num_arcs: usize,
+    pub(crate) isymt: Option<Rc<SymbolTable>>,
+    pub(crate) osymt: Option<Rc<SymbolTable>>,
+    pub(crate) cache: OnceCell<Box<[OnceCell<CompactCacheState<W>>]>>,
+    compactor: PhantomData<C>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CompactCacheState<W> {
+    pub(crate) final_weight: Option<W>,
+    pub(crate) arcs: Vec<Arc<W>>,
+}
+
+impl<W: Semiring, C: Compactor<W>> CompactFst<W, C> {
+    pub(crate) fn from_parts(
+        states: Option<Vec<usize>>,
+        compacts: Vec<C::Element>,
+        start: Option<StateId>,
+        num_states: usize,
+        num_arcs: usize,
+        isymt: Option<Rc<SymbolTable>>,
+        osymt: Option<Rc<SymbolTable>>,
+    ) -> Self {
+        Self {
+            states,
+            compacts,
+            start,
+            num_states,
+            num_arcs,
+            isymt,
+            osymt,
+            cache: OnceCell::new(),
+            compactor: PhantomData,
+        }
+    }
+
+    /// Builds a `CompactFst` from any `ExpandedFst`. Fails if one of the arcs or final weights
+    /// can't be represented with the compactor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use rustfst::fst_impls::{CompactFst, StringCompactor, VectorFst};
+    /// # use rustfst::fst_traits::{CoreFst, ExpandedFst};
+    /// # use rustfst::semirings::{Semiring, TropicalWeight};
+    /// # use rustfst::utils::acceptor;
+    /// # fn main() -> Result<()> {
+    /// let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::one());
+    /// let compact_fst = CompactFst::<_, StringCompactor>::from_fst(&fst)?;
+    ///
+    /// assert_eq!(compact_fst.num_states(), 4);
+    /// assert_eq!(compact_fst.num_arcs(0)?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_fst<F: ExpandedFst<W = W>>(fst: &F) -> Result<Self> {
+        let num_states = fst.num_states();
+        let mut compacts = vec![];
+        let mut states = vec![];
+        let mut num_arcs = 0;
+        for s in 0..num_states {
+            let pos = compacts.len();
+            states.push(pos);
+            if let Some(final_weight) = fst.final_weight(s)? {
+                let final_arc = Arc::new(NO_LABEL, NO_LABEL, final_weight.clone(), NO_STATE_ID);
+                compacts.push(C::compact(s, &final_arc)?);
+            }
+            for arc in fst.arcs_iter(s)? {
+                compacts.push(C::compact(s, arc)?);
+                num_arcs += 1;
+            }
+            if let Some(size) = C::size() {
+                if compacts.len() - pos != size {
+                    bail!(
+                        "Compactor incompatible with Fst : state {} must have exactly {} element(s)",
+                        s,
+                        size
+                    );
+                }
+            }
+        }
+        states.push(compacts.len());
+
+        let states = if C::size().is_some() {
+            None
+        } else {
+            Some(states)
+        };
+
+        Ok(Self::from_parts(
+            states,
+            compacts,
+            fst.start(),
+            num_states,
+            num_arcs,
+            fst.input_symbols(),
+            fst.output_symbols(),
+        ))
+    }
+
+    /// Removes all the expanded states from the cache.
+    pub fn clear_cache(&mut self) {
+        self.cache = OnceCell::new();
+    }
+
+    /// Returns the arcs leaving a state, decoded on the fly without going through the cache.
+    pub fn expanded_arcs(&self, state: StateId) -> Result<impl Iterator<Item = Arc<W>> + '_> {
+        self.check_state(state)?;
+        let range = self.compacts_range(state);
+        let skip = usize::from(self.has_final_element(&range, state));
+        Ok(self.compacts[range]
+            .iter()
+            .skip(skip)
+            .map(move |element| C::expand(state, element)))
+    }
+
+    /// Returns the final weight of a state, decoded on the fly without going through the cache.
+    pub fn expanded_final_weight(&self, state: StateId) -> Result<Option<W>> {
+        self.check_state(state)?;
+        let range = self.compacts_range(state);
+        Ok(self.compacts[range]
+            .first()
+            .map(|element| C::expand(state, element))
+            .filter(|arc| arc.ilabel == NO_LABEL)
+            .map(|arc| arc.weight))
+    }
+
+    fn check_state(&self, state: StateId) -> Result<()> {
+        if state >= self.num_states {
+            bail!("State {:?} doesn't exist", state);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn compacts_range(&self, state: StateId) -> Range<usize> {
+        match &self.states {
+            Some(states) => states[state]..states[state + 1],
+            None => {
+                // Safe to unwrap : the offsets are only missing for fixed size compactors.
+                let size = C::size().unwrap();
+                state * size..(state + 1) * size
+            }
+        }
+    }
+
+    /// The final weight, if any, is stored as the first element of the state.
+    fn has_final_element(&self, range: &Range<usize>, state: StateId) -> bool {
+        self.compacts[range.clone()]
+            .first()
+            .is_some_and(|element| C::expand(state, element).ilabel == NO_LABEL)
+    }
+
+    /// Number of arcs leaving a state, computed without expanding them.
+    /// The state must exist.
+    pub(crate) fn num_arcs_unchecked(&self, state: StateId) -> usize {
+        let range = self.compacts_range(state);
+        range.len() - usize::from(self.has_final_element(&range, state))
+    }
+
+    pub(crate) fn num_arcs_checked(&self, state: StateId) -> Result<usize> {
+        self.check_state(state)?;
+        Ok(self.num_arcs_unchecked(state))
+    }
+
+    pub(crate) fn expand_state(&self, state: StateId) -> CompactCacheState<W> {
+        let mut final_weight = None;
+        let mut arcs = vec![];
+        for element in &self.compacts[self.compacts_range(state)] {
+            let arc = C::expand(state, element);
+            if arc.ilabel == NO_LABEL {
+                final_weight = Some(arc.weight);
+            } else {
+                arcs.push(arc);
+            }
+        }
+        CompactCacheState { final_weight, arcs }
+    }
+
+    /// Returns the expanded state, computing it if it is not in the cache.
+    /// The state must exist.
+    pub(crate) fn cached_state_unchecked(&self, state: StateId) -> &CompactCacheState<W> {
+        // Cached states are never modified once set, only dropped by `clear_cache` which
+        // requires a mutable borrow, so the references handed out stay valid.
+        let cache = self
+            .cache
+            .get_or_init(|| (0..self.num_states).map(|_| OnceCell::new()).collect());
+        cache[state].get_or_init(|| self.expand_state(state))
+    }
+
+    pub(crate) fn cached_state(&self, state: StateId) -> Result<&CompactCacheState<W>> {
+        self.check_state(state)?;
+        Ok(self.cached_state_unchecked(state))
+    }
+}
+
+impl<W: Semiring, C: Compactor<W>> Clone for CompactFst<W, C> {
+    fn clone(&self) -> Self {
+        Self::from_parts(
+            self.states.clone(),
+            self.compacts.clone(),
+            self.start,
+            self.num_states,
+            self.num_arcs,
+            self.isymt.clone(),
+            self.osymt.clone(),
+        )
+    }
+}
+
+impl<W: Semiring, C: Compactor<W>> PartialEq for CompactFst<W, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.states == other.states
+            && self.compacts == other.compacts
+            && self.start == other.start
+            && self.num_states == other.num_states
+            && self.num_arcs == other.num_arcs
+            && self.isymt == other.isymt
+            && self.osymt == other.osymt
+    }
+}
+
+impl<W: Semiring, C: Compactor<W>> fmt::Debug for CompactFst<W, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompactFst")
+            .field("states", &self.states)
+            .field("compacts", &self.compacts)
+            .field("start", &self.start)
+            .field("num_states", &self.num_states)
+            .field("num_arcs", &self.num_arcs)
+            .field("isymt", &self.isymt)
+            .field("osymt", &self.osymt)
+            .finish()
+    }
+}
@@ -0,0 +1,10 @@
+use crate::fst_impls::compact_fst::compactors::Compactor;
+use crate::fst_impls::CompactFst;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+
+impl<W: Semiring + 'static, C: Compactor<W> + 'static> ExpandedFst for CompactFst<W, C> {
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+}
@@ -0,0 +1,59 @@
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::fst_impls::compact_fst::compactors::Compactor;
+use crate::fst_impls::CompactFst;
+use crate::fst_traits::{CoreFst, Fst};
+use crate::semirings::Semiring;
+use crate::{StateId, SymbolTable};
+
+impl<W: Semiring + 'static, C: Compactor<W> + 'static> Fst for CompactFst<W, C> {
+    fn input_symbols(&self) -> Option<Rc<SymbolTable>> {
+        self.isymt.clone()
+    }
+
+    fn output_symbols(&self) -> Option<Rc<SymbolTable>> {
+        self.osymt.clone()
+    }
+
+    fn set_input_symbols(&mut self, symt: Rc<SymbolTable>) {
+        self.isymt = Some(Rc::clone(&symt))
+    }
+
+    fn set_output_symbols(&mut self, symt: Rc<SymbolTable>) {
+        self.osymt = Some(Rc::clone(&symt));
+    }
+
+    fn unset_input_symbols(&mut self) -> Option<Rc<SymbolTable>> {
+        self.isymt.take()
+    }
+
+    fn unset_output_symbols(&mut self) -> Option<Rc<SymbolTable>> {
+        self.osymt.take()
+    }
+}
+
+impl<W: Semiring, C: Compactor<W>> CoreFst for CompactFst<W, C> {
+    type W = W;
+
+    fn start(&self) -> Option<StateId> {
+        self.start
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<&Self::W>> {
+        Ok(self.cached_state(state_id)?.final_weight.as_ref())
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<&Self::W> {
+        self.cached_state_unchecked(state_id).final_weight.as_ref()
+    }
+
+    fn num_arcs(&self, s: StateId) -> Result<usize> {
+        self.num_arcs_checked(s)
+    }
+
+    unsafe fn num_arcs_unchecked(&self, s: StateId) -> usize {
+        CompactFst::num_arcs_unchecked(self, s)
+    }
+}
@@ -0,0 +1,78 @@
+use std::iter::{repeat, Map, Repeat, Zip};
+use std::ops::Range;
+use std::slice;
+
+use anyhow::Result;
+use itertools::izip;
+
+use crate::fst_impls::compact_fst::compactors::Compactor;
+use crate::fst_impls::CompactFst;
+use crate::fst_traits::FstIterData;
+use crate::fst_traits::{ArcIterator, FstIntoIterator, FstIterator, StateIterator};
+use crate::semirings::Semiring;
+use crate::Arc;
+use crate::StateId;
+
+impl<'a, W: Semiring, C: Compactor<W>> StateIterator<'a> for CompactFst<W, C> {
+    type Iter = Range<StateId>;
+    fn states_iter(&'a self) -> Self::Iter {
+        0..self.num_states
+    }
+}
+
+impl<'a, W: Semiring + 'static, C: Compactor<W>> ArcIterator<'a> for CompactFst<W, C> {
+    type Iter = slice::Iter<'a, Arc<W>>;
+    fn arcs_iter(&'a self, state_id: StateId) -> Result<Self::Iter> {
+        Ok(self.cached_state(state_id)?.arcs.iter())
+    }
+
+    unsafe fn arcs_iter_unchecked(&'a self, state_id: StateId) -> Self::Iter {
+        self.cached_state_unchecked(state_id).arcs.iter()
+    }
+}
+
+impl<W: Semiring, C: Compactor<W>> FstIntoIterator for CompactFst<W, C>
+where
+    W: 'static,
+    C: 'static,
+{
+    type ArcsIter = std::vec::IntoIter<Arc<W>>;
+
+    // TODO: Change this to impl once the feature has been stabilized
+    // #![feature(type_alias_impl_trait)]
+    // https://github.com/rust-lang/rust/issues/63063)
+    type FstIter = Box<dyn Iterator<Item = FstIterData<W, Self::ArcsIter>>>;
+
+    fn fst_into_iter(self) -> Self::FstIter {
+        Box::new((0..self.num_states).map(move |state_id| {
+            let cache_state = self.expand_state(state_id);
+            FstIterData {
+                state_id,
+                num_arcs: cache_state.arcs.len(),
+                arcs: cache_state.arcs.into_iter(),
+                final_weight: cache_state.final_weight,
+            }
+        }))
+    }
+}
+
+impl<'a, W: Semiring + 'static, C: Compactor<W> + 'a> FstIterator<'a> for CompactFst<W, C> {
+    type ArcsIter = slice::Iter<'a, Arc<W>>;
+    type FstIter = Map<
+        Zip<Range<StateId>, Repeat<&'a Self>>,
+        Box<dyn FnMut((StateId, &'a Self)) -> FstIterData<&'a W, Self::ArcsIter>>,
+    >;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        let it = repeat(self);
+        izip!(self.states_iter(), it).map(Box::new(|(state_id, p): (StateId, &'a Self)| {
+            let cache_state = p.cached_state_unchecked(state_id);
+            FstIterData {
+                state_id,
+                arcs: cache_state.arcs.iter(),
+                final_weight: cache_state.final_weight.as_ref(),
+                num_arcs: cache_state.arcs.len(),
+            }
+        }))
+    }
+}
@@ -0,0 +1,15 @@
+use std::fmt;
+
+use crate::fst_impls::compact_fst::compactors::Compactor;
+use crate::fst_impls::CompactFst;
+use crate::fst_traits::{ArcIterator, CoreFst, FinalStatesIterator, StateIterator};
+use crate::semirings::SerializableSemiring;
+
+impl<W: 'static + SerializableSemiring, C: Compactor<W> + 'static> fmt::Display
+    for CompactFst<W, C>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_fst!(self, f, true);
+        Ok(())
+    }
+}
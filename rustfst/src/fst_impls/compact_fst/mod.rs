@@ -0,0 +1,26 @@
+pub use self::compactors::{
+    AcceptorCompactor, Compactor, StringCompactor, UnweightedAcceptorCompactor,
+    UnweightedCompactor, WeightedStringCompactor,
+};
+pub use self::data_structure::CompactFst;
+
+mod compactors;
+mod data_structure;
+mod expanded_fst;
+mod fst;
+mod iterators;
+mod misc;
+mod serializable_fst;
+mod test;
+
+/// `CompactFst` storing an unweighted string. Equivalent to OpenFST's `compact_string`.
+pub type CompactStringFst<W> = CompactFst<W, StringCompactor>;
+/// `CompactFst` storing a weighted string. Equivalent to OpenFST's `compact_weighted_string`.
+pub type CompactWeightedStringFst<W> = CompactFst<W, WeightedStringCompactor>;
+/// `CompactFst` storing a weighted acceptor. Equivalent to OpenFST's `compact_acceptor`.
+pub type CompactAcceptorFst<W> = CompactFst<W, AcceptorCompactor>;
+/// `CompactFst` storing an unweighted transducer. Equivalent to OpenFST's `compact_unweighted`.
+pub type CompactUnweightedFst<W> = CompactFst<W, UnweightedCompactor>;
+/// `CompactFst` storing an unweighted acceptor. Equivalent to OpenFST's
+/// `compact_unweighted_acceptor`.
+pub type CompactUnweightedAcceptorFst<W> = CompactFst<W, UnweightedAcceptorCompactor>;
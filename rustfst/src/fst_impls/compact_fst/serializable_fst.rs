@@ -0,0 +1,162 @@
+use std::convert::TryFrom;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::bytes::complete::take;
+use nom::error::ErrorKind;
+use nom::multi::count;
+use nom::number::complete::le_u32;
+use nom::IResult;
+
+use crate::fst_impls::compact_fst::compactors::Compactor;
+use crate::fst_impls::{CompactFst, VectorFst};
+use crate::fst_traits::{ExpandedFst, Fst, SerializableFst};
+use crate::parsers::bin_fst::fst_header::{FstFlags, FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::bin_fst::utils_parsing::parse_start_state;
+use crate::parsers::bin_fst::utils_serialization::write_bin_u32;
+use crate::parsers::text_fst::ParsedTextFst;
+use crate::semirings::SerializableSemiring;
+use crate::Arc;
+
+impl<W, C> SerializableFst for CompactFst<W, C>
+where
+    W: SerializableSemiring + 'static,
+    C: Compactor<W> + 'static,
+{
+    fn fst_type() -> String {
+        format!("compact_{}", C::compactor_type())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        parse_compact_fst(i)
+    }
+
+    fn write_binary<O: Write>(&self, file: &mut O) -> Result<()> {
+        let mut flags = FstFlags::empty();
+        if self.input_symbols().is_some() {
+            flags |= FstFlags::HAS_ISYMBOLS;
+        }
+        if self.output_symbols().is_some() {
+            flags |= FstFlags::HAS_OSYMBOLS;
+        }
+
+        let hdr = FstHeader {
+            magic_number: FST_MAGIC_NUMBER,
+            fst_type: OpenFstString::new(Self::fst_type()),
+            arc_type: OpenFstString::new(Arc::<W>::arc_type()),
+            version: COMPACT_FILE_VERSION,
+            flags,
+            // TODO: Once the properties are stored, need to read them. kExpanded
+            properties: 1u64,
+            start: self.start.map(|v| v as i64).unwrap_or(-1),
+            num_states: self.num_states() as i64,
+            num_arcs: self.num_arcs as i64,
+            isymt: self.input_symbols(),
+            osymt: self.output_symbols(),
+        };
+        hdr.write(file)?;
+
+        if let Some(states) = &self.states {
+            for pos in states {
+                write_bin_u32(file, *pos as u32)?;
+            }
+        }
+
+        for element in &self.compacts {
+            C::write_element(element, file)?;
+        }
+
+        Ok(())
+    }
+
+    fn from_parsed_fst_text(parsed_fst_text: ParsedTextFst<W>) -> Result<Self> {
+        let fst = VectorFst::from_parsed_fst_text(parsed_fst_text)?;
+        Self::from_fst(&fst)
+    }
+}
+
+static COMPACT_MIN_FILE_VERSION: i32 = 1;
+static COMPACT_FILE_VERSION: i32 = 2;
+static COMPACT_ARCH_ALIGNMENT: usize = 16;
+
+/// Skips the padding bytes inserted by OpenFST to align the data.
+fn align_input(i: &[u8], stream_len: usize) -> IResult<&[u8], &[u8]> {
+    let pos = stream_len - i.len();
+    let padding = (COMPACT_ARCH_ALIGNMENT - pos % COMPACT_ARCH_ALIGNMENT) % COMPACT_ARCH_ALIGNMENT;
+    take(padding)(i)
+}
+
+/// Error returned when the header or the state offsets are inconsistent with the data.
+fn invalid_data(i: &[u8]) -> nom::Err<(&[u8], ErrorKind)> {
+    nom::Err::Error((i, ErrorKind::Verify))
+}
+
+fn parse_compact_fst<W, C>(i: &[u8]) -> IResult<&[u8], CompactFst<W, C>>
+where
+    W: SerializableSemiring + 'static,
+    C: Compactor<W> + 'static,
+{
+    let stream_len = i.len();
+
+    let (mut i, hdr) = FstHeader::parse(
+        i,
+        COMPACT_MIN_FILE_VERSION,
+        CompactFst::<W, C>::fst_type(),
+        Arc::<W>::arc_type(),
+    )?;
+    let aligned = hdr.flags.contains(FstFlags::IS_ALIGNED);
+    let num_states = usize::try_from(hdr.num_states).map_err(|_| invalid_data(i))?;
+    let start = parse_start_state(hdr.start);
+    if start.is_some_and(|s| s >= num_states) {
+        return Err(invalid_data(i));
+    }
+
+    let states = if C::size().is_none() {
+        if aligned {
+            i = align_input(i, stream_len)?.0;
+        }
+        // Each offset takes 4 bytes, checked before allocating the offsets.
+        let num_offsets = num_states
+            .checked_add(1)
+            .filter(|n| *n <= i.len() / 4)
+            .ok_or_else(|| invalid_data(i))?;
+        let (i_states, states) = count(le_u32, num_offsets)(i)?;
+        let states = states.into_iter().map(|v| v as usize).collect::<Vec<_>>();
+        if states.windows(2).any(|w| w[0] > w[1]) {
+            return Err(invalid_data(i));
+        }
+        i = i_states;
+        Some(states)
+    } else {
+        None
+    };
+
+    let num_compacts = match &states {
+        Some(states) => Some(states[num_states]),
+        // Safe to unwrap as the compactor has a fixed size.
+        None => num_states.checked_mul(C::size().unwrap()),
+    }
+    .ok_or_else(|| invalid_data(i))?;
+
+    if aligned && num_compacts > 0 {
+        i = align_input(i, stream_len)?.0;
+    }
+    // Each element takes at least one byte.
+    if num_compacts > i.len() {
+        return Err(invalid_data(i));
+    }
+    let (i, compacts) = count(C::parse_element, num_compacts)(i)?;
+
+    Ok((
+        i,
+        CompactFst::from_parts(
+            states,
+            compacts,
+            start,
+            num_states,
+            hdr.num_arcs as usize,
+            hdr.isymt,
+            hdr.osymt,
+        ),
+    ))
+}
@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod tests {
+    use std::fs::read;
+
+    use anyhow::Result;
+    use tempfile::tempdir;
+
+    use crate::algorithms::fst_convert_from_ref;
+    use crate::fst_impls::compact_fst::Compactor;
+    use crate::fst_impls::{
+        CompactAcceptorFst, CompactFst, CompactStringFst, CompactUnweightedAcceptorFst,
+        CompactUnweightedFst, CompactWeightedStringFst, VectorFst,
+    };
+    use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst, MutableFst, SerializableFst};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::{acceptor, transducer};
+    use crate::Arc;
+
+    fn check_roundtrip<C: Compactor<TropicalWeight> + 'static>(
+        fst: &VectorFst<TropicalWeight>,
+    ) -> Result<()> {
+        let compact_fst = CompactFst::<TropicalWeight, C>::from_fst(fst)?;
+        let expanded: VectorFst<TropicalWeight> = fst_convert_from_ref(&compact_fst);
+        assert_eq!(&expanded, fst);
+
+        let dir = tempdir()?;
+        let path = dir.path().join("compact.fst");
+        compact_fst.write(&path)?;
+        let read_fst = CompactFst::<TropicalWeight, C>::read(&path)?;
+        assert_eq!(read_fst, compact_fst);
+        let expanded: VectorFst<TropicalWeight> = fst_convert_from_ref(&read_fst);
+        assert_eq!(&expanded, fst);
+
+        let text_fst = CompactFst::<TropicalWeight, C>::from_text_string(&compact_fst.text()?)?;
+        assert_eq!(text_fst, compact_fst);
+        Ok(())
+    }
+
+    fn weighted_acceptor() -> Result<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.add_arc(s0, Arc::new(1, 1, 0.5, s1))?;
+        fst.add_arc(s0, Arc::new(2, 2, 1.5, s2))?;
+        fst.add_arc(s1, Arc::new(3, 3, 2.5, s0))?;
+        fst.set_final(s1, 0.7)?;
+        fst.set_final(s2, 3.2)?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_compact_string_fst() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::one());
+        check_roundtrip::<crate::fst_impls::StringCompactor>(&fst)?;
+
+        let compact_fst = CompactStringFst::from_fst(&fst)?;
+        assert_eq!(compact_fst.num_states(), 4);
+        assert_eq!(compact_fst.compacts, vec![1, 2, 3, crate::NO_LABEL]);
+        assert!(compact_fst.states.is_none());
+        assert_eq!(compact_fst.final_weight(3)?, Some(&TropicalWeight::one()));
+        assert_eq!(compact_fst.final_weight(2)?, None);
+        assert!(compact_fst.final_weight(4).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_string_fst_binary_layout() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let compact_fst = CompactStringFst::from_fst(&fst)?;
+
+        let dir = tempdir()?;
+        let path = dir.path().join("compact_string.fst");
+        compact_fst.write(&path)?;
+        let data = read(&path)?;
+
+        // The body is made of one label per state, the final state being marked with -1.
+        let mut body = vec![];
+        for label in &[1i32, 2, -1] {
+            body.extend_from_slice(&label.to_le_bytes());
+        }
+        assert!(data.ends_with(&body));
+        assert!(data
+            .windows(b"compact_string".len())
+            .any(|w| w == b"compact_string"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_string_fst_rejects_non_string() -> Result<()> {
+        let fst = weighted_acceptor()?;
+        assert!(CompactStringFst::from_fst(&fst).is_err());
+
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(2.0));
+        assert!(CompactStringFst::from_fst(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_weighted_string_fst() -> Result<()> {
+        let mut fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(2.0));
+        fst.add_arc(1, Arc::new(4, 4, 1.0, 2))?;
+        assert!(CompactWeightedStringFst::from_fst(&fst).is_err());
+
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(2.0));
+        check_roundtrip::<crate::fst_impls::WeightedStringCompactor>(&fst)
+    }
+
+    #[test]
+    fn test_compact_acceptor_fst() -> Result<()> {
+        let fst = weighted_acceptor()?;
+        check_roundtrip::<crate::fst_impls::AcceptorCompactor>(&fst)?;
+
+        let compact_fst = CompactAcceptorFst::from_fst(&fst)?;
+        assert_eq!(compact_fst.states, Some(vec![0, 2, 4, 5]));
+
+        let fst: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3, 4], TropicalWeight::one());
+        assert!(CompactAcceptorFst::from_fst(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_unweighted_fst() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3, 4], TropicalWeight::one());
+        check_roundtrip::<crate::fst_impls::UnweightedCompactor>(&fst)?;
+
+        let fst = weighted_acceptor()?;
+        assert!(CompactUnweightedFst::from_fst(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_unweighted_acceptor_fst() -> Result<()> {
+        let mut fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::one(), 0))?;
+        check_roundtrip::<crate::fst_impls::UnweightedAcceptorCompactor>(&fst)?;
+
+        let fst: VectorFst<TropicalWeight> = transducer(&[1, 2], &[3, 4], TropicalWeight::one());
+        assert!(CompactUnweightedAcceptorFst::from_fst(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_fst_expanded_without_cache() -> Result<()> {
+        let fst = weighted_acceptor()?;
+        let mut compact_fst = CompactAcceptorFst::from_fst(&fst)?;
+        for s in 0..fst.num_states() {
+            let arcs: Vec<_> = fst.arcs_iter(s)?.cloned().collect();
+            assert_eq!(compact_fst.expanded_arcs(s)?.collect::<Vec<_>>(), arcs);
+            assert_eq!(compact_fst.num_arcs(s)?, arcs.len());
+            assert_eq!(
+                compact_fst.expanded_final_weight(s)?.as_ref(),
+                fst.final_weight(s)?
+            );
+        }
+        assert!(compact_fst.cache.get().is_none());
+        assert!(compact_fst.expanded_arcs(3).is_err());
+        assert!(compact_fst.expanded_final_weight(3).is_err());
+        assert!(compact_fst.num_arcs(3).is_err());
+
+        assert_eq!(compact_fst.final_weight(1)?, fst.final_weight(1)?);
+        assert!(compact_fst.cache.get().is_some());
+        compact_fst.clear_cache();
+        assert!(compact_fst.cache.get().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_fst_empty() -> Result<()> {
+        let fst = VectorFst::<TropicalWeight>::new();
+        check_roundtrip::<crate::fst_impls::AcceptorCompactor>(&fst)?;
+        check_roundtrip::<crate::fst_impls::StringCompactor>(&fst)
+    }
+
+    /// Serializes the FST and returns the data along with the offset of the `start` field
+    /// of the header, followed by `num_states` and `num_arcs`.
+    fn binary_with_start_offset<C: Compactor<TropicalWeight> + 'static>(
+        fst: &CompactFst<TropicalWeight, C>,
+    ) -> Result<(Vec<u8>, usize)> {
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+        let fst_type_len = CompactFst::<TropicalWeight, C>::fst_type().len();
+        let arc_type_len = Arc::<TropicalWeight>::arc_type().len();
+        // magic number, fst_type, arc_type, version, flags and properties.
+        let offset = 4 + (4 + fst_type_len) + (4 + arc_type_len) + 4 + 4 + 8;
+        Ok((data, offset))
+    }
+
+    fn set_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn test_compact_fst_parse_invalid_header() -> Result<()> {
+        let fst = CompactAcceptorFst::from_fst(&weighted_acceptor()?)?;
+        let (data, start_offset) = binary_with_start_offset(&fst)?;
+        let num_states_offset = start_offset + 8;
+        assert_eq!(
+            CompactAcceptorFst::<TropicalWeight>::parse_binary(&data)
+                .unwrap()
+                .1,
+            fst
+        );
+
+        for num_states in &[-1, 4, i64::MAX] {
+            let mut data = data.clone();
+            set_i64(&mut data, num_states_offset, *num_states);
+            assert!(CompactAcceptorFst::<TropicalWeight>::parse_binary(&data).is_err());
+        }
+        for start in &[-2, 3, i64::MAX] {
+            let mut data = data.clone();
+            set_i64(&mut data, start_offset, *start);
+            assert!(CompactAcceptorFst::<TropicalWeight>::parse_binary(&data).is_err());
+        }
+
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let fst = CompactStringFst::from_fst(&fst)?;
+        let (data, start_offset) = binary_with_start_offset(&fst)?;
+        for num_states in &[-1, 4, i64::MAX] {
+            let mut data = data.clone();
+            set_i64(&mut data, start_offset + 8, *num_states);
+            assert!(CompactStringFst::<TropicalWeight>::parse_binary(&data).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_fst_parse_invalid_offsets() -> Result<()> {
+        let fst = CompactAcceptorFst::from_fst(&weighted_acceptor()?)?;
+        let (data, start_offset) = binary_with_start_offset(&fst)?;
+        // No symbol table, the state offsets follow the header.
+        let states_offset = start_offset + 3 * 8;
+        let num_offsets = fst.num_states() + 1;
+        let offset_value = |data: &[u8], s: usize| {
+            let pos = states_offset + 4 * s;
+            u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+        };
+        assert_eq!(offset_value(&data, 0), 0);
+
+        // Decreasing offsets.
+        let mut invalid = data.clone();
+        let pos = states_offset + 4;
+        invalid[pos..pos + 4].copy_from_slice(&(offset_value(&data, 2) + 1).to_le_bytes());
+        assert!(CompactAcceptorFst::<TropicalWeight>::parse_binary(&invalid).is_err());
+
+        // Last offset past the end of the data.
+        let mut invalid = data;
+        let pos = states_offset + 4 * (num_offsets - 1);
+        invalid[pos..pos + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(CompactAcceptorFst::<TropicalWeight>::parse_binary(&invalid).is_err());
+        Ok(())
+    }
+}
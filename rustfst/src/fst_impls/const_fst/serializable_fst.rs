@@ -1,9 +1,6 @@
-use std::fs::{read, File};
-use std::io::BufWriter;
-use std::path::Path;
+use std::io::Write;
 
 use anyhow::Result;
-use anyhow::Context;
 use itertools::Itertools;
 use nom::bytes::complete::take;
 use nom::multi::count;
@@ -27,23 +24,11 @@ impl<W: 'static + SerializableSemiring> SerializableFst for ConstFst<W> {
         "const".to_string()
     }
 
-    fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
-        let data = read(path_bin_fst.as_ref()).with_context(|| {
-            format!(
-                "Can't open ConstFst binary file : {:?}",
-                path_bin_fst.as_ref()
-            )
-        })?;
-
-        let (_, parsed_fst) = parse_const_fst(&data)
-            .map_err(|_| format_err!("Error while parsing binary ConstFst"))?;
-
-        Ok(parsed_fst)
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        parse_const_fst(i)
     }
 
-    fn write<P: AsRef<Path>>(&self, path_bin_fst: P) -> Result<()> {
-        let mut file = BufWriter::new(File::create(path_bin_fst)?);
-
+    fn write_binary<O: Write>(&self, file: &mut O) -> Result<()> {
         let mut flags = FstFlags::empty();
         if self.input_symbols().is_some() {
             flags |= FstFlags::HAS_ISYMBOLS;
@@ -67,24 +52,24 @@ impl<W: 'static + SerializableSemiring> SerializableFst for ConstFst<W> {
             isymt: self.input_symbols(),
             osymt: self.output_symbols(),
         };
-        hdr.write(&mut file)?;
+        hdr.write(file)?;
 
         let zero = W::zero();
         for const_state in &self.states {
             let f_weight = const_state.final_weight.as_ref().unwrap_or_else(|| &zero);
-            f_weight.write_binary(&mut file)?;
+            f_weight.write_binary(file)?;
 
-            write_bin_i32(&mut file, const_state.pos as i32)?;
-            write_bin_i32(&mut file, const_state.narcs as i32)?;
-            write_bin_i32(&mut file, const_state.niepsilons as i32)?;
-            write_bin_i32(&mut file, const_state.noepsilons as i32)?;
+            write_bin_i32(file, const_state.pos as i32)?;
+            write_bin_i32(file, const_state.narcs as i32)?;
+            write_bin_i32(file, const_state.niepsilons as i32)?;
+            write_bin_i32(file, const_state.noepsilons as i32)?;
         }
 
         for arc in &self.arcs {
-            write_bin_i32(&mut file, arc.ilabel as i32)?;
-            write_bin_i32(&mut file, arc.olabel as i32)?;
-            arc.weight.write_binary(&mut file)?;
-            write_bin_i32(&mut file, arc.nextstate as i32)?;
+            write_bin_i32(file, arc.ilabel as i32)?;
+            write_bin_i32(file, arc.olabel as i32)?;
+            arc.weight.write_binary(file)?;
+            write_bin_i32(file, arc.nextstate as i32)?;
         }
 
         Ok(())
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::fst_impls::vector_fst::VectorFstState;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst, Fst};
+use crate::{StateId, SymbolTable};
+
+/// Mutable FST built on top of an immutable `ExpandedFst`. Equivalent to OpenFST's `EditFst`.
+///
+/// The wrapped FST is never modified : the first time a state is edited, it is copied in a
+/// sparse overlay and all the following reads and writes on this state are done on the copy.
+/// The states that have never been edited are read directly from the wrapped FST, which is
+/// shared through an `Rc` and can thus be used by several `EditFst` at the same time.
+///
+/// Deleting a subset of the states renumbers them and therefore copies the whole FST in the
+/// overlay.
+///
+/// # Example
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use anyhow::Result;
+/// # use rustfst::fst_impls::{ConstFst, EditFst, VectorFst};
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst, MutableFst};
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::acceptor;
+/// # use rustfst::Arc;
+/// # fn main() -> Result<()> {
+/// let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::one());
+/// let const_fst = Rc::new(ConstFst::from(fst));
+///
+/// let mut edit_fst = EditFst::from_wrapped(Rc::clone(&const_fst));
+/// edit_fst.add_arc(0, Arc::new(4, 4, TropicalWeight::new(2.0), 1))?;
+///
+/// assert_eq!(edit_fst.num_arcs(0)?, 2);
+/// assert_eq!(const_fst.num_arcs(0)?, 1);
+/// assert_eq!(edit_fst.num_edited_states(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EditFst<F: ExpandedFst> {
+    pub(crate) wrapped: Option<Rc<F>>,
+    pub(crate) num_wrapped_states: usize,
+    pub(crate) edits: HashMap<StateId, VectorFstState<F::W>>,
+    pub(crate) num_states: usize,
+    pub(crate) start: Option<StateId>,
+    pub(crate) isymt: Option<Rc<SymbolTable>>,
+    pub(crate) osymt: Option<Rc<SymbolTable>>,
+}
+
+impl<F: ExpandedFst> EditFst<F>
+where
+    F::W: 'static,
+{
+    /// Creates an `EditFst` on top of `fst`. No state is copied until it is edited.
+    pub fn from_wrapped(fst: Rc<F>) -> Self {
+        let num_states = fst.num_states();
+        Self {
+            start: fst.start(),
+            isymt: fst.input_symbols(),
+            osymt: fst.output_symbols(),
+            wrapped: Some(fst),
+            num_wrapped_states: num_states,
+            edits: HashMap::new(),
+            num_states,
+        }
+    }
+
+    /// Returns the wrapped FST if it is still used to store some of the states.
+    pub fn wrapped(&self) -> Option<&Rc<F>> {
+        self.wrapped.as_ref()
+    }
+
+    /// Returns the number of states stored in the overlay, i.e edited or added states.
+    pub fn num_edited_states(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Converts the `EditFst` to a `VectorFst` containing both the wrapped and the edited states.
+    pub fn to_vector_fst(&self) -> VectorFst<F::W> {
+        let states = (0..self.num_states)
+            .map(|s| unsafe { self.copy_state_unchecked(s) })
+            .collect();
+        VectorFst {
+            states,
+            start_state: self.start,
+            isymt: self.isymt.clone(),
+            osymt: self.osymt.clone(),
+        }
+    }
+
+    /// Builds an `EditFst` whose states are all stored in the overlay.
+    pub(crate) fn from_vector_fst(fst: VectorFst<F::W>) -> Self {
+        Self {
+            wrapped: None,
+            num_wrapped_states: 0,
+            num_states: fst.states.len(),
+            edits: fst.states.into_iter().enumerate().collect(),
+            start: fst.start_state,
+            isymt: fst.isymt,
+            osymt: fst.osymt,
+        }
+    }
+
+    /// Returns the wrapped FST. Must only be called for states that have never been edited.
+    #[inline]
+    pub(crate) unsafe fn wrapped_unchecked(&self) -> &F {
+        self.wrapped.as_ref().unwrap()
+    }
+
+    unsafe fn copy_state_unchecked(&self, state: StateId) -> VectorFstState<F::W> {
+        match self.edits.get(&state) {
+            Some(fst_state) => fst_state.clone(),
+            None => {
+                let fst = self.wrapped_unchecked();
+                VectorFstState {
+                    final_weight: fst.final_weight_unchecked(state).cloned(),
+                    arcs: fst.arcs_iter_unchecked(state).cloned().collect(),
+                }
+            }
+        }
+    }
+
+    /// Returns a mutable reference to the state in the overlay, copying it from the wrapped
+    /// FST if it has never been edited. The state must exist.
+    pub(crate) unsafe fn edited_state_unchecked(
+        &mut self,
+        state: StateId,
+    ) -> &mut VectorFstState<F::W> {
+        if !self.edits.contains_key(&state) {
+            let fst_state = self.copy_state_unchecked(state);
+            self.edits.insert(state, fst_state);
+        }
+        self.edits.get_mut(&state).unwrap()
+    }
+
+    pub(crate) fn edited_state(&mut self, state: StateId) -> Result<&mut VectorFstState<F::W>> {
+        if state >= self.num_states {
+            bail!("State {:?} doesn't exist", state);
+        }
+        Ok(unsafe { self.edited_state_unchecked(state) })
+    }
+
+    /// Copies all the states in the overlay and releases the wrapped FST.
+    pub(crate) fn edit_all_states(&mut self) {
+        for s in 0..self.num_wrapped_states {
+            unsafe { self.edited_state_unchecked(s) };
+        }
+        self.wrapped = None;
+        self.num_wrapped_states = 0;
+    }
+}
+
+impl<F: ExpandedFst + 'static> PartialEq for EditFst<F>
+where
+    F::W: 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.num_states == other.num_states
+            && self.start == other.start
+            && self.isymt == other.isymt
+            && self.osymt == other.osymt
+            && (0..self.num_states).all(|s| unsafe {
+                self.final_weight_unchecked(s) == other.final_weight_unchecked(s)
+                    && self.arcs_iter_unchecked(s).eq(other.arcs_iter_unchecked(s))
+            })
+    }
+}
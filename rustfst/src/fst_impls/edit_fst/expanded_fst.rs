@@ -0,0 +1,11 @@
+use crate::fst_impls::EditFst;
+use crate::fst_traits::ExpandedFst;
+
+impl<F: ExpandedFst + 'static> ExpandedFst for EditFst<F>
+where
+    F::W: 'static,
+{
+    fn num_states(&self) -> usize {
+        self.num_states
+    }
+}
@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use anyhow::Result;
+
+use crate::fst_impls::EditFst;
+use crate::fst_traits::{CoreFst, ExpandedFst, Fst};
+use crate::{StateId, SymbolTable};
+
+impl<F: ExpandedFst + 'static> Fst for EditFst<F>
+where
+    F::W: 'static,
+{
+    fn input_symbols(&self) -> Option<Rc<SymbolTable>> {
+        self.isymt.clone()
+    }
+
+    fn output_symbols(&self) -> Option<Rc<SymbolTable>> {
+        self.osymt.clone()
+    }
+
+    fn set_input_symbols(&mut self, symt: Rc<SymbolTable>) {
+        self.isymt = Some(Rc::clone(&symt))
+    }
+
+    fn set_output_symbols(&mut self, symt: Rc<SymbolTable>) {
+        self.osymt = Some(Rc::clone(&symt));
+    }
+
+    fn unset_input_symbols(&mut self) -> Option<Rc<SymbolTable>> {
+        self.isymt.take()
+    }
+
+    fn unset_output_symbols(&mut self) -> Option<Rc<SymbolTable>> {
+        self.osymt.take()
+    }
+}
+
+impl<F: ExpandedFst> CoreFst for EditFst<F>
+where
+    F::W: 'static,
+{
+    type W = F::W;
+
+    fn start(&self) -> Option<StateId> {
+        self.start
+    }
+
+    fn final_weight(&self, state_id: StateId) -> Result<Option<&Self::W>> {
+        if state_id >= self.num_states {
+            bail!("State {:?} doesn't exist", state_id);
+        }
+        Ok(unsafe { self.final_weight_unchecked(state_id) })
+    }
+
+    unsafe fn final_weight_unchecked(&self, state_id: StateId) -> Option<&Self::W> {
+        match self.edits.get(&state_id) {
+            Some(fst_state) => fst_state.final_weight.as_ref(),
+            None => self.wrapped_unchecked().final_weight_unchecked(state_id),
+        }
+    }
+
+    fn num_arcs(&self, s: StateId) -> Result<usize> {
+        if s >= self.num_states {
+            bail!("State {:?} doesn't exist", s);
+        }
+        Ok(unsafe { self.num_arcs_unchecked(s) })
+    }
+
+    unsafe fn num_arcs_unchecked(&self, s: StateId) -> usize {
+        match self.edits.get(&s) {
+            Some(fst_state) => fst_state.num_arcs(),
+            None => self.wrapped_unchecked().num_arcs_unchecked(s),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::iter::{repeat, Map, Repeat, Zip};
+use std::ops::Range;
+use std::slice;
+
+use anyhow::Result;
+use itertools::{izip, Either};
+
+use crate::fst_impls::EditFst;
+use crate::fst_traits::FstIterData;
+use crate::fst_traits::{
+    ArcIterator, CoreFst, ExpandedFst, FstIntoIterator, FstIterator, FstIteratorMut,
+    MutableArcIterator, StateIterator,
+};
+use crate::Arc;
+use crate::StateId;
+
+impl<'a, F: ExpandedFst> StateIterator<'a> for EditFst<F> {
+    type Iter = Range<StateId>;
+    fn states_iter(&'a self) -> Self::Iter {
+        0..self.num_states
+    }
+}
+
+impl<'a, F: ExpandedFst + 'static> ArcIterator<'a> for EditFst<F>
+where
+    F::W: 'static,
+{
+    type Iter = Either<slice::Iter<'a, Arc<F::W>>, <F as ArcIterator<'a>>::Iter>;
+
+    fn arcs_iter(&'a self, state_id: StateId) -> Result<Self::Iter> {
+        if state_id >= self.num_states {
+            bail!("State {:?} doesn't exist", state_id);
+        }
+        Ok(unsafe { self.arcs_iter_unchecked(state_id) })
+    }
+
+    unsafe fn arcs_iter_unchecked(&'a self, state_id: StateId) -> Self::Iter {
+        match self.edits.get(&state_id) {
+            Some(fst_state) => Either::Left(fst_state.arcs.iter()),
+            None => Either::Right(self.wrapped_unchecked().arcs_iter_unchecked(state_id)),
+        }
+    }
+}
+
+impl<'a, F: ExpandedFst + 'static> MutableArcIterator<'a> for EditFst<F>
+where
+    F::W: 'static,
+{
+    type IterMut = slice::IterMut<'a, Arc<F::W>>;
+
+    fn arcs_iter_mut(&'a mut self, state_id: StateId) -> Result<Self::IterMut> {
+        Ok(self.edited_state(state_id)?.arcs.iter_mut())
+    }
+
+    unsafe fn arcs_iter_unchecked_mut(&'a mut self, state_id: StateId) -> Self::IterMut {
+        self.edited_state_unchecked(state_id).arcs.iter_mut()
+    }
+}
+
+impl<F: ExpandedFst + 'static> FstIntoIterator for EditFst<F>
+where
+    F::W: 'static,
+{
+    type ArcsIter = std::vec::IntoIter<Arc<F::W>>;
+
+    // TODO: Change this to impl once the feature has been stabilized
+    // #![feature(type_alias_impl_trait)]
+    // https://github.com/rust-lang/rust/issues/63063)
+    type FstIter = Box<dyn Iterator<Item = FstIterData<F::W, Self::ArcsIter>>>;
+
+    fn fst_into_iter(mut self) -> Self::FstIter {
+        self.edit_all_states();
+        let mut edits = self.edits;
+        Box::new((0..self.num_states).map(move |state_id| {
+            let fst_state = edits.remove(&state_id).unwrap();
+            FstIterData {
+                state_id,
+                num_arcs: fst_state.arcs.len(),
+                arcs: fst_state.arcs.into_iter(),
+                final_weight: fst_state.final_weight,
+            }
+        }))
+    }
+}
+
+impl<'a, F: ExpandedFst + 'static> FstIterator<'a> for EditFst<F>
+where
+    F::W: 'static,
+{
+    type ArcsIter = <Self as ArcIterator<'a>>::Iter;
+    type FstIter = Map<
+        Zip<Range<StateId>, Repeat<&'a Self>>,
+        Box<dyn FnMut((StateId, &'a Self)) -> FstIterData<&'a F::W, Self::ArcsIter> + 'a>,
+    >;
+
+    fn fst_iter(&'a self) -> Self::FstIter {
+        let it = repeat(self);
+        izip!(self.states_iter(), it).map(Box::new(|(state_id, p): (StateId, &'a Self)| unsafe {
+            FstIterData {
+                state_id,
+                arcs: p.arcs_iter_unchecked(state_id),
+                final_weight: p.final_weight_unchecked(state_id),
+                num_arcs: p.num_arcs_unchecked(state_id),
+            }
+        }))
+    }
+}
+
+impl<'a, F: ExpandedFst + 'static> FstIteratorMut<'a> for EditFst<F>
+where
+    F::W: 'static,
+{
+    type ArcsIter = slice::IterMut<'a, Arc<F::W>>;
+    type FstIter = Box<dyn Iterator<Item = FstIterData<&'a mut F::W, Self::ArcsIter>> + 'a>;
+
+    fn fst_iter_mut(&'a mut self) -> Self::FstIter {
+        // All the states can be modified : they all need to be stored in the overlay.
+        self.edit_all_states();
+        let mut fst_states: Vec<_> = self.edits.iter_mut().collect();
+        fst_states.sort_by_key(|(state_id, _)| **state_id);
+        Box::new(
+            fst_states
+                .into_iter()
+                .map(|(state_id, fst_state)| FstIterData {
+                    state_id: *state_id,
+                    num_arcs: fst_state.arcs.len(),
+                    arcs: fst_state.arcs.iter_mut(),
+                    final_weight: fst_state.final_weight.as_mut(),
+                }),
+        )
+    }
+}
@@ -0,0 +1,15 @@
+use std::fmt;
+
+use crate::fst_impls::EditFst;
+use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst, FinalStatesIterator, StateIterator};
+use crate::semirings::{Semiring, SerializableSemiring};
+
+impl<F: ExpandedFst + 'static> fmt::Display for EditFst<F>
+where
+    F::W: 'static + SerializableSemiring,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_fst!(self, f, true);
+        Ok(())
+    }
+}
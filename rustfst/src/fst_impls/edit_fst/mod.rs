@@ -0,0 +1,10 @@
+pub use self::data_structure::EditFst;
+
+mod data_structure;
+mod expanded_fst;
+mod fst;
+mod iterators;
+mod misc;
+mod mutable_fst;
+mod serializable_fst;
+mod test;
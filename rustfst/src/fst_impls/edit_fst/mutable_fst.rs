@@ -0,0 +1,172 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::fst_impls::vector_fst::{sum_arcs, unique_arcs, VectorFstState};
+use crate::fst_impls::{EditFst, VectorFst};
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::{Arc, StateId};
+
+impl<F: ExpandedFst + 'static> MutableFst for EditFst<F>
+where
+    F::W: 'static,
+{
+    fn new() -> Self {
+        EditFst {
+            wrapped: None,
+            num_wrapped_states: 0,
+            edits: HashMap::new(),
+            num_states: 0,
+            start: None,
+            isymt: None,
+            osymt: None,
+        }
+    }
+
+    fn set_start(&mut self, state_id: StateId) -> Result<()> {
+        ensure!(
+            state_id < self.num_states,
+            "The state {:?} doesn't exist",
+            state_id
+        );
+        self.start = Some(state_id);
+        Ok(())
+    }
+
+    unsafe fn set_start_unchecked(&mut self, state_id: StateId) {
+        self.start = Some(state_id);
+    }
+
+    fn set_final<S: Into<Self::W>>(&mut self, state_id: StateId, final_weight: S) -> Result<()> {
+        self.edited_state(state_id)?.final_weight = Some(final_weight.into());
+        Ok(())
+    }
+
+    unsafe fn set_final_unchecked<S: Into<Self::W>>(&mut self, state_id: StateId, final_weight: S) {
+        self.edited_state_unchecked(state_id).final_weight = Some(final_weight.into());
+    }
+
+    fn add_state(&mut self) -> StateId {
+        let id = self.num_states;
+        self.edits.insert(id, VectorFstState::new());
+        self.num_states += 1;
+        id
+    }
+
+    fn add_states(&mut self, n: usize) {
+        for _ in 0..n {
+            self.add_state();
+        }
+    }
+
+    fn del_state(&mut self, state_id: StateId) -> Result<()> {
+        ensure!(
+            state_id < self.num_states,
+            "State id {:?} doesn't exist",
+            state_id
+        );
+        self.del_states(vec![state_id])
+    }
+
+    fn del_states<T: IntoIterator<Item = StateId>>(&mut self, states: T) -> Result<()> {
+        // The states are renumbered : all of them need to be stored in the overlay.
+        self.edit_all_states();
+        let mut fst = VectorFst {
+            states: (0..self.num_states)
+                .map(|s| self.edits.remove(&s).unwrap())
+                .collect(),
+            start_state: self.start,
+            isymt: None,
+            osymt: None,
+        };
+        fst.del_states(states)?;
+
+        self.num_states = fst.states.len();
+        self.start = fst.start_state;
+        self.edits = fst.states.into_iter().enumerate().collect();
+        Ok(())
+    }
+
+    fn del_all_states(&mut self) {
+        self.wrapped = None;
+        self.num_wrapped_states = 0;
+        self.edits.clear();
+        self.num_states = 0;
+        self.start = None;
+    }
+
+    unsafe fn del_arcs_id_sorted_unchecked(&mut self, state: StateId, to_del: &Vec<usize>) {
+        let arcs = &mut self.edited_state_unchecked(state).arcs;
+        for i in to_del.iter().rev() {
+            arcs.remove(*i);
+        }
+    }
+
+    fn add_arc(&mut self, source: StateId, arc: Arc<Self::W>) -> Result<()> {
+        self.edited_state(source)?.arcs.push(arc);
+        Ok(())
+    }
+
+    unsafe fn add_arc_unchecked(&mut self, source: StateId, arc: Arc<Self::W>) {
+        self.edited_state_unchecked(source).arcs.push(arc)
+    }
+
+    unsafe fn set_arcs_unchecked(&mut self, source: StateId, arcs: Vec<Arc<Self::W>>) {
+        self.edited_state_unchecked(source).arcs = arcs
+    }
+
+    fn delete_final_weight(&mut self, source: StateId) -> Result<()> {
+        self.edited_state(source)?.final_weight = None;
+        Ok(())
+    }
+
+    unsafe fn delete_final_weight_unchecked(&mut self, source: StateId) {
+        self.edited_state_unchecked(source).final_weight = None;
+    }
+
+    fn delete_arcs(&mut self, source: StateId) -> Result<()> {
+        self.edited_state(source)?.arcs.clear();
+        Ok(())
+    }
+
+    fn pop_arcs(&mut self, source: StateId) -> Result<Vec<Arc<Self::W>>> {
+        Ok(self.edited_state(source)?.arcs.drain(..).collect())
+    }
+
+    unsafe fn pop_arcs_unchecked(&mut self, source: StateId) -> Vec<Arc<Self::W>> {
+        self.edited_state_unchecked(source).arcs.drain(..).collect()
+    }
+
+    fn final_weight_mut(&mut self, state_id: StateId) -> Result<Option<&mut Self::W>> {
+        Ok(self.edited_state(state_id)?.final_weight.as_mut())
+    }
+
+    unsafe fn final_weight_unchecked_mut(&mut self, state_id: StateId) -> Option<&mut Self::W> {
+        self.edited_state_unchecked(state_id).final_weight.as_mut()
+    }
+
+    fn take_final_weight(&mut self, state_id: StateId) -> Result<Option<Self::W>> {
+        Ok(self.edited_state(state_id)?.final_weight.take())
+    }
+
+    unsafe fn take_final_weight_unchecked(&mut self, state_id: StateId) -> Option<Self::W> {
+        self.edited_state_unchecked(state_id).final_weight.take()
+    }
+
+    fn sort_arcs_unchecked<C: Fn(&Arc<Self::W>, &Arc<Self::W>) -> Ordering>(
+        &mut self,
+        state: StateId,
+        f: C,
+    ) {
+        unsafe { self.edited_state_unchecked(state).arcs.sort_by(f) }
+    }
+
+    unsafe fn unique_arcs_unchecked(&mut self, state: StateId) {
+        unique_arcs(&mut self.edited_state_unchecked(state).arcs)
+    }
+
+    unsafe fn sum_arcs_unchecked(&mut self, state: StateId) {
+        sum_arcs(&mut self.edited_state_unchecked(state).arcs)
+    }
+}
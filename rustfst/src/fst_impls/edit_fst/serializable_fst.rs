@@ -0,0 +1,35 @@
+use std::io::Write;
+
+use anyhow::Result;
+use nom::IResult;
+
+use crate::fst_impls::{EditFst, VectorFst};
+use crate::fst_traits::{ExpandedFst, SerializableFst};
+use crate::parsers::text_fst::ParsedTextFst;
+use crate::semirings::SerializableSemiring;
+
+/// An `EditFst` is serialized as a regular `VectorFst` containing both the wrapped and the
+/// edited states. Once read back, all the states are stored in the overlay.
+impl<F: ExpandedFst + 'static> SerializableFst for EditFst<F>
+where
+    F::W: 'static + SerializableSemiring,
+{
+    fn fst_type() -> String {
+        VectorFst::<F::W>::fst_type()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, fst) = VectorFst::parse_binary(i)?;
+        Ok((i, Self::from_vector_fst(fst)))
+    }
+
+    fn write_binary<O: Write>(&self, file: &mut O) -> Result<()> {
+        self.to_vector_fst().write_binary(file)
+    }
+
+    fn from_parsed_fst_text(parsed_fst_text: ParsedTextFst<F::W>) -> Result<Self> {
+        Ok(Self::from_vector_fst(VectorFst::from_parsed_fst_text(
+            parsed_fst_text,
+        )?))
+    }
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use anyhow::Result;
+    use tempfile::tempdir;
+
+    use crate::algorithms::{arc_sum, fst_convert_from_ref};
+    use crate::fst_impls::{ConstFst, EditFst, VectorFst};
+    use crate::fst_traits::{
+        CoreFst, ExpandedFst, Fst, FstIteratorMut, MutableArcIterator, MutableFst, SerializableFst,
+    };
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::utils::acceptor;
+    use crate::Arc;
+
+    fn wrapped_fst() -> Rc<ConstFst<TropicalWeight>> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(1.0));
+        Rc::new(fst.into())
+    }
+
+    #[test]
+    fn test_edit_fst_copy_on_write() -> Result<()> {
+        let wrapped = wrapped_fst();
+        let mut fst = EditFst::from_wrapped(Rc::clone(&wrapped));
+        assert_eq!(fst.num_states(), 4);
+        assert_eq!(fst.num_edited_states(), 0);
+
+        fst.add_arc(1, Arc::new(5, 5, 3.0, 3))?;
+        fst.set_final(2, 4.0)?;
+        let s = fst.add_state();
+        fst.add_arc(3, Arc::new(6, 6, TropicalWeight::one(), s))?;
+        fst.set_start(s)?;
+        assert_eq!(fst.num_edited_states(), 4);
+
+        let mut expected: VectorFst<TropicalWeight> = fst_convert_from_ref(wrapped.as_ref());
+        expected.add_arc(1, Arc::new(5, 5, 3.0, 3))?;
+        expected.set_final(2, 4.0)?;
+        let s = expected.add_state();
+        expected.add_arc(3, Arc::new(6, 6, TropicalWeight::one(), s))?;
+        expected.set_start(s)?;
+        assert_eq!(fst.to_vector_fst(), expected);
+        let converted: VectorFst<TropicalWeight> = fst_convert_from_ref(&fst);
+        assert_eq!(converted, expected);
+
+        // The wrapped fst must not be modified.
+        let original: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], TropicalWeight::new(1.0));
+        assert_eq!(wrapped.as_ref(), &ConstFst::from(original));
+        assert_eq!(wrapped.num_arcs(1)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_shared_wrapped() -> Result<()> {
+        let wrapped = wrapped_fst();
+        let mut fst_1 = EditFst::from_wrapped(Rc::clone(&wrapped));
+        let mut fst_2 = EditFst::from_wrapped(Rc::clone(&wrapped));
+        assert_eq!(fst_1, fst_2);
+
+        fst_1.delete_arcs(0)?;
+        fst_2.set_final(0, 2.0)?;
+        assert_eq!(fst_1.num_arcs(0)?, 0);
+        assert_eq!(fst_1.final_weight(0)?, None);
+        assert_eq!(fst_2.num_arcs(0)?, 1);
+        assert_eq!(fst_2.final_weight(0)?, Some(&TropicalWeight::new(2.0)));
+        assert_ne!(fst_1, fst_2);
+        assert_eq!(Rc::strong_count(&wrapped), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_mutable_iterators() -> Result<()> {
+        let mut fst = EditFst::from_wrapped(wrapped_fst());
+        for arc in fst.arcs_iter_mut(0)? {
+            arc.ilabel = 7;
+        }
+        assert_eq!(fst.num_edited_states(), 1);
+
+        for data in fst.fst_iter_mut() {
+            for arc in data.arcs {
+                arc.olabel = 8;
+            }
+        }
+        assert!(fst.wrapped().is_none());
+
+        let mut expected: VectorFst<TropicalWeight> = acceptor(&[7, 2, 3], 1.0.into());
+        for s in 0..expected.num_states() {
+            for arc in expected.arcs_iter_mut(s)? {
+                arc.olabel = 8;
+            }
+        }
+        assert_eq!(fst.to_vector_fst(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_del_states() -> Result<()> {
+        let mut fst = EditFst::from_wrapped(wrapped_fst());
+        fst.del_state(1)?;
+        assert_eq!(fst.num_states(), 3);
+
+        let mut expected: VectorFst<TropicalWeight> = acceptor(&[1, 2, 3], 1.0.into());
+        expected.del_state(1)?;
+        assert_eq!(fst.to_vector_fst(), expected);
+
+        fst.del_all_states();
+        assert_eq!(fst.num_states(), 0);
+        assert_eq!(fst.start(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_algorithm() -> Result<()> {
+        let mut fst = EditFst::from_wrapped(wrapped_fst());
+        fst.add_arc(0, Arc::new(1, 1, 0.5, 1))?;
+        arc_sum(&mut fst);
+        assert_eq!(fst.num_arcs(0)?, 1);
+        assert_eq!(fst.num_edited_states(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edit_fst_serialization() -> Result<()> {
+        let mut fst = EditFst::from_wrapped(wrapped_fst());
+        fst.add_arc(2, Arc::new(4, 4, 1.5, 0))?;
+
+        let dir = tempdir()?;
+        let path = dir.path().join("edit.fst");
+        fst.write(&path)?;
+
+        let vector_fst = VectorFst::<TropicalWeight>::read(&path)?;
+        assert_eq!(vector_fst, fst.to_vector_fst());
+
+        let read_fst = EditFst::<ConstFst<TropicalWeight>>::read(&path)?;
+        assert_eq!(read_fst, fst);
+        assert!(read_fst.wrapped().is_none());
+
+        let text_fst = EditFst::<ConstFst<TropicalWeight>>::from_text_string(&fst.text()?)?;
+        assert_eq!(text_fst, fst);
+        assert!(text_fst.input_symbols().is_none());
+        Ok(())
+    }
+}
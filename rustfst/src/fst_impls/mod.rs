@@ -1,6 +1,14 @@
+pub(crate) mod compact_fst;
 pub(crate) mod const_fst;
+pub(crate) mod edit_fst;
 mod rc;
 pub(crate) mod vector_fst;
 
+pub use self::compact_fst::{
+    AcceptorCompactor, CompactAcceptorFst, CompactFst, CompactStringFst,
+    CompactUnweightedAcceptorFst, CompactUnweightedFst, CompactWeightedStringFst, Compactor,
+    StringCompactor, UnweightedAcceptorCompactor, UnweightedCompactor, WeightedStringCompactor,
+};
 pub use self::const_fst::ConstFst;
+pub use self::edit_fst::EditFst;
 pub use self::vector_fst::VectorFst;
@@ -1,5 +1,6 @@
 pub use self::data_structure::VectorFst;
 pub(crate) use self::data_structure::VectorFstState;
+pub(crate) use self::mutable_fst::{sum_arcs, unique_arcs};
 
 mod allocable_fst;
 mod data_structure;
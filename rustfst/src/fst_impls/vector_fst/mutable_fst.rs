@@ -233,30 +233,38 @@ impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
     }
 
     unsafe fn unique_arcs_unchecked(&mut self, state: usize) {
-        let arcs = &mut self.states.get_unchecked_mut(state).arcs;
-        arcs.sort_by(arc_compare);
-        arcs.dedup();
+        unique_arcs(&mut self.states.get_unchecked_mut(state).arcs)
     }
 
     unsafe fn sum_arcs_unchecked(&mut self, state: usize) {
-        let arcs = &mut self.states.get_unchecked_mut(state).arcs;
-        arcs.sort_by(arc_compare);
-        let mut n_arcs: usize = 0;
-        for i in 0..arcs.len() {
-            if n_arcs > 0 && equal_arc(&arcs[i], &arcs[n_arcs - 1]) {
-                let (left, right) = arcs.split_at_mut(i);
-                left[n_arcs - 1]
-                    .weight
-                    .plus_assign(&right[0].weight)
-                    .unwrap();
-            } else {
-                arcs.swap(n_arcs, i);
-                n_arcs += 1;
-            }
+        sum_arcs(&mut self.states.get_unchecked_mut(state).arcs)
+    }
+}
+
+/// Sorts the arcs and removes the duplicates.
+pub(crate) fn unique_arcs<W: Semiring>(arcs: &mut Vec<Arc<W>>) {
+    arcs.sort_by(arc_compare);
+    arcs.dedup();
+}
+
+/// Plus-Sums the weights of the arcs with the same labels and nextstate.
+pub(crate) fn sum_arcs<W: Semiring>(arcs: &mut Vec<Arc<W>>) {
+    arcs.sort_by(arc_compare);
+    let mut n_arcs: usize = 0;
+    for i in 0..arcs.len() {
+        if n_arcs > 0 && equal_arc(&arcs[i], &arcs[n_arcs - 1]) {
+            let (left, right) = arcs.split_at_mut(i);
+            left[n_arcs - 1]
+                .weight
+                .plus_assign(&right[0].weight)
+                .unwrap();
+        } else {
+            arcs.swap(n_arcs, i);
+            n_arcs += 1;
         }
-        arcs.truncate(n_arcs);
-        // Truncate doesn't modify the capacity of the vector. Maybe a shrink_to_fit ?
     }
+    arcs.truncate(n_arcs);
+    // Truncate doesn't modify the capacity of the vector. Maybe a shrink_to_fit ?
 }
 
 //#[test]
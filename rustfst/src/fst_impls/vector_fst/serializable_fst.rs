@@ -1,9 +1,6 @@
-use std::fs::{read, File};
-use std::io::BufWriter;
-use std::path::Path;
+use std::io::Write;
 
 use anyhow::Result;
-use anyhow::Context;
 use nom::multi::count;
 use nom::number::complete::le_i64;
 use nom::IResult;
@@ -25,23 +22,11 @@ impl<W: 'static + SerializableSemiring> SerializableFst for VectorFst<W> {
         "vector".to_string()
     }
 
-    fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
-        let data = read(path_bin_fst.as_ref()).with_context(|| {
-            format!(
-                "Can't open VectorFst binary file : {:?}",
-                path_bin_fst.as_ref()
-            )
-        })?;
-
-        let (_, parsed_fst) = parse_vector_fst(&data)
-            .map_err(|e| format_err!("Error while parsing binary VectorFst : {:?}", e))?;
-
-        Ok(parsed_fst)
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        parse_vector_fst(i)
     }
 
-    fn write<P: AsRef<Path>>(&self, path_bin_fst: P) -> Result<()> {
-        let mut file = BufWriter::new(File::create(path_bin_fst)?);
-
+    fn write_binary<O: Write>(&self, file: &mut O) -> Result<()> {
         let num_arcs: usize = (0..self.num_states())
             .map(|s: usize| unsafe { self.num_arcs_unchecked(s) })
             .sum();
@@ -69,20 +54,20 @@ impl<W: 'static + SerializableSemiring> SerializableFst for VectorFst<W> {
             isymt: self.input_symbols(),
             osymt: self.output_symbols(),
         };
-        hdr.write(&mut file)?;
+        hdr.write(file)?;
 
         let zero = W::zero();
         // FstBody
         for state in 0..self.num_states() {
             let f_weight = unsafe { self.final_weight_unchecked(state).unwrap_or_else(|| &zero) };
-            f_weight.write_binary(&mut file)?;
-            write_bin_i64(&mut file, unsafe { self.num_arcs_unchecked(state) } as i64)?;
+            f_weight.write_binary(file)?;
+            write_bin_i64(file, unsafe { self.num_arcs_unchecked(state) } as i64)?;
 
             for arc in unsafe { self.arcs_iter_unchecked(state) } {
-                write_bin_i32(&mut file, arc.ilabel as i32)?;
-                write_bin_i32(&mut file, arc.olabel as i32)?;
-                arc.weight.write_binary(&mut file)?;
-                write_bin_i32(&mut file, arc.nextstate as i32)?;
+                write_bin_i32(file, arc.ilabel as i32)?;
+                write_bin_i32(file, arc.olabel as i32)?;
+                arc.weight.write_binary(file)?;
+                write_bin_i32(file, arc.nextstate as i32)?;
             }
         }
 
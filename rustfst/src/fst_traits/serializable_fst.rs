@@ -1,6 +1,8 @@
+use std::fs::read;
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use nom::IResult;
 
 use crate::fst_traits::{ExpandedFst, FinalStatesIterator};
 use crate::parsers::text_fst::ParsedTextFst;
@@ -20,10 +22,32 @@ where
 
     // BINARY
 
+    /// Parses an FST in binary format from a slice of bytes and returns the remaining bytes.
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self>;
+    /// Writes the FST in binary format.
+    fn write_binary<O: Write>(&self, file: &mut O) -> Result<()>;
+
     /// Loads an FST from a file in binary format.
-    fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self>;
+    fn read<P: AsRef<Path>>(path_bin_fst: P) -> Result<Self> {
+        let data = read(path_bin_fst.as_ref()).with_context(|| {
+            format!(
+                "Can't open {} binary file : {:?}",
+                Self::fst_type(),
+                path_bin_fst.as_ref()
+            )
+        })?;
+
+        let (_, parsed_fst) = Self::parse_binary(&data)
+            .map_err(|_| format_err!("Error while parsing binary {} fst", Self::fst_type()))?;
+
+        Ok(parsed_fst)
+    }
+
     /// Writes the FST to a file in binary format.
-    fn write<P: AsRef<Path>>(&self, path_bin_fst: P) -> Result<()>;
+    fn write<P: AsRef<Path>>(&self, path_bin_fst: P) -> Result<()> {
+        let mut file = BufWriter::new(File::create(path_bin_fst)?);
+        self.write_binary(&mut file)
+    }
 
     // TEXT
 
@@ -62,7 +86,13 @@ where
     fn draw<P: AsRef<Path>>(&self, path_output: P, config: &DrawingConfig) -> Result<()> {
         let buffer = File::create(path_output.as_ref())?;
         let mut f = BufWriter::new(LineWriter::new(buffer));
+        self.draw_to_writer(&mut f, config)?;
+        f.flush()?;
+        Ok(())
+    }
 
+    /// Writes the FST in DOT format into a writer.
+    fn draw_to_writer<O: Write>(&self, f: &mut O, config: &DrawingConfig) -> Result<()> {
         if let Some(start_state) = self.start() {
             writeln!(f, "digraph FST {{")?;
 
@@ -94,11 +124,11 @@ where
             }
 
             // Start state first
-            draw_single_fst_state(self, &mut f, start_state, config)?;
+            draw_single_fst_state(self, f, start_state, config)?;
 
             for state in self.states_iter() {
                 if state != start_state {
-                    draw_single_fst_state(self, &mut f, state, config)?;
+                    draw_single_fst_state(self, f, state, config)?;
                 }
             }
 
@@ -145,6 +145,8 @@ pub mod fst_properties;
 #[macro_use]
 /// Provides traits that must be implemented to be able to use generic algorithms.
 pub mod fst_traits;
+/// Reading and writing of FST archives (FAR) compatible with OpenFST.
+pub mod far;
 /// Implementation of the wFST traits with different data structures.
 pub mod fst_impls;
 /// Provides a trait that shall be implemented for all weights stored inside a wFST.
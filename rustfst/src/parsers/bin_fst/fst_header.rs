@@ -108,6 +108,14 @@ impl FstHeader {
         ))
     }
 
+    /// Parses the beginning of a header up to the `arc_type`, without checking the types.
+    pub(crate) fn peek_types(i: &[u8]) -> IResult<&[u8], (String, String)> {
+        let (i, _) = verify(le_i32, |v: &i32| *v == FST_MAGIC_NUMBER)(i)?;
+        let (i, fst_type) = OpenFstString::parse(i)?;
+        let (i, arc_type) = OpenFstString::parse(i)?;
+        Ok((i, (fst_type.s, arc_type.s)))
+    }
+
     pub(crate) fn write<W: Write>(&self, file: &mut W) -> Result<()> {
         //magic_number: i32,
         write_bin_i32(file, self.magic_number)?;
@@ -142,15 +150,9 @@ impl OpenFstString {
         }
     }
     pub(crate) fn parse(i: &[u8]) -> IResult<&[u8], OpenFstString> {
-        let (i, n) = le_i32(i)?;
-        let (i, s) = take(n as usize)(i)?;
-        Ok((
-            i,
-            OpenFstString {
-                n,
-                s: String::from_utf8(s.to_vec()).unwrap(),
-            },
-        ))
+        let (i, n) = verify(le_i32, |v: &i32| *v >= 0)(i)?;
+        let (i, s) = map_res(take(n as usize), |s: &[u8]| String::from_utf8(s.to_vec()))(i)?;
+        Ok((i, OpenFstString { n, s }))
     }
 
     pub(crate) fn write<W: Write>(&self, file: &mut W) -> Result<()> {
@@ -159,8 +161,70 @@ impl OpenFstString {
     }
 }
 
+/// Returns the `fst_type` (e.g `vector`) and the `arc_type` (e.g `standard`) stored in the
+/// header of an FST in binary format, without parsing the rest of the FST.
+pub fn peek_fst_types(data: &[u8]) -> Result<(String, String)> {
+    if let Ok((_, magic_number)) = le_i32::<()>(data) {
+        ensure!(
+            magic_number == FST_MAGIC_NUMBER,
+            "Not an FST in binary format : wrong magic number"
+        );
+    }
+    let (_, types) =
+        FstHeader::peek_types(data).map_err(|_| format_err!("Truncated or invalid FST header"))?;
+    Ok(types)
+}
+
 impl Into<String> for OpenFstString {
     fn into(self) -> String {
         self.s
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_traits::SerializableFst;
+    use crate::semirings::{LogWeight, Semiring, TropicalWeight};
+    use crate::utils::acceptor;
+
+    #[test]
+    fn test_peek_fst_types() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+        assert_eq!(
+            peek_fst_types(&data)?,
+            ("vector".to_string(), "standard".to_string())
+        );
+
+        let fst: VectorFst<LogWeight> = acceptor(&[1, 2], LogWeight::one());
+        let fst: ConstFst<LogWeight> = fst.into();
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+        assert_eq!(
+            peek_fst_types(&data)?,
+            ("const".to_string(), "log".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_peek_fst_types_invalid() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let mut data = vec![];
+        fst.write_binary(&mut data)?;
+
+        let err = peek_fst_types(&data[..10]).unwrap_err();
+        assert!(err.to_string().contains("Truncated"));
+        assert!(peek_fst_types(&data[..2]).is_err());
+        assert!(peek_fst_types(&[]).is_err());
+
+        data[0] ^= 1;
+        let err = peek_fst_types(&data).unwrap_err();
+        assert!(err.to_string().contains("wrong magic number"));
+        Ok(())
+    }
+}
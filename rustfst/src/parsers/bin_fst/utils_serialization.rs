@@ -1,6 +1,11 @@
 use anyhow::Result;
 use std::io::Write;
 
+#[inline]
+pub(crate) fn write_bin_u8<F: Write>(file: &mut F, i: u8) -> Result<()> {
+    file.write_all(&[i]).map_err(|e| e.into())
+}
+
 #[inline]
 pub(crate) fn write_bin_i32<F: Write>(file: &mut F, i: i32) -> Result<()> {
     file.write_all(&i.to_le_bytes()).map_err(|e| e.into())
@@ -26,3 +31,9 @@ pub(crate) fn write_bin_f32<F: Write>(file: &mut F, i: f32) -> Result<()> {
     file.write_all(&i.to_bits().to_le_bytes())
         .map_err(|e| e.into())
 }
+
+#[inline]
+pub(crate) fn write_bin_f64<F: Write>(file: &mut F, i: f64) -> Result<()> {
+    file.write_all(&i.to_bits().to_le_bytes())
+        .map_err(|e| e.into())
+}
@@ -9,7 +9,7 @@ use crate::SymbolTable;
 use anyhow::Result;
 use std::io::Write;
 
-static SYMBOL_TABLE_MAGIC_NUMBER: i32 = 2_125_658_996;
+pub(crate) static SYMBOL_TABLE_MAGIC_NUMBER: i32 = 2_125_658_996;
 
 fn parse_row_symt(i: &[u8]) -> IResult<&[u8], (i64, OpenFstString)> {
     let (i, symbol) = OpenFstString::parse(i)?;
@@ -1,7 +1,20 @@
+use std::borrow::Borrow;
+use std::io::Write;
+
 use anyhow::Result;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::value;
+use nom::number::complete::le_u8;
+use nom::IResult;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_u8;
+use crate::semirings::{
+    CompleteSemiring, IntegerWeight, LogWeight, LogWeight64, MaxTropicalWeight, MinMaxWeight,
+    ProbabilityWeight, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    SignedLogWeight, StarSemiring, TropicalWeight, TropicalWeight64,
+};
 
-use crate::semirings::{CompleteSemiring, ReverseBack, Semiring, SemiringProperties, StarSemiring};
-use std::borrow::Borrow;
 /// Boolean semiring: (&, |, false, true).
 #[derive(Clone, Debug, PartialEq, PartialOrd, Default, Eq, Copy, Hash)]
 pub struct BooleanWeight {
@@ -73,16 +86,75 @@ impl StarSemiring for BooleanWeight {
     }
 }
 
+impl SerializableSemiring for BooleanWeight {
+    fn weight_type() -> String {
+        "boolean".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, weight) = le_u8(i)?;
+        Ok((i, Self::new(weight != 0)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_u8(file, self.value as u8)
+    }
+
+    /// Accepts `true` and `false` as written by `Display` as well as `1` and `0`.
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight) = alt((
+            value(true, alt((tag("true"), tag("1")))),
+            value(false, alt((tag("false"), tag("0")))),
+        ))(i)?;
+        Ok((i, Self::new(weight)))
+    }
+}
+
+test_semiring_serializable!(
+    tests_boolean_weight_serializable,
+    BooleanWeight,
+    BooleanWeight::one() BooleanWeight::zero()
+);
+
 impl Into<BooleanWeight> for bool {
     fn into(self) -> BooleanWeight {
         BooleanWeight::new(self)
     }
 }
 
+/// Any weight different from zero is converted to `true`.
+macro_rules! impl_boolean_weight_from {
+    ($($semiring:ty),*) => {
+        $(
+            impl From<$semiring> for BooleanWeight {
+                fn from(weight: $semiring) -> Self {
+                    BooleanWeight::new(!weight.is_zero())
+                }
+            }
+        )*
+    };
+}
+
+impl_boolean_weight_from!(
+    IntegerWeight,
+    LogWeight,
+    LogWeight64,
+    MaxTropicalWeight,
+    MinMaxWeight,
+    ProbabilityWeight,
+    SignedLogWeight,
+    TropicalWeight,
+    TropicalWeight64
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{MutableFst, SerializableFst};
+    use crate::Arc;
+
     #[test]
     fn test_boolean_weight() -> Result<()> {
         let b_true = BooleanWeight::new(true);
@@ -101,4 +173,43 @@ mod tests {
         assert_eq!(b_false.times(&b_false)?, b_false);
         Ok(())
     }
+
+    #[test]
+    fn test_boolean_weight_serialization() -> Result<()> {
+        assert_eq!(BooleanWeight::weight_type(), "boolean");
+        assert_eq!(
+            BooleanWeight::parse_text("1").unwrap().1,
+            BooleanWeight::one()
+        );
+        assert_eq!(
+            BooleanWeight::parse_text("0").unwrap().1,
+            BooleanWeight::zero()
+        );
+        assert!(BooleanWeight::parse_text("2").is_err());
+
+        let mut serialization = vec![];
+        BooleanWeight::one().write_binary(&mut serialization)?;
+        assert_eq!(serialization, vec![1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_boolean_weight_fst_serialization() -> Result<()> {
+        let mut fst: VectorFst<BooleanWeight> = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, BooleanWeight::one())?;
+        fst.add_arc(s0, Arc::new(1, 1, BooleanWeight::one(), s1))?;
+
+        let fst_text = VectorFst::<BooleanWeight>::from_text_string(&fst.text()?)?;
+        assert_eq!(fst_text, fst);
+
+        let mut serialization = vec![];
+        fst.write_binary(&mut serialization)?;
+        let (_, fst_bin) = VectorFst::<BooleanWeight>::parse_binary(&serialization)
+            .map_err(|e| format_err!("Can't parse fst : {:?}", e))?;
+        assert_eq!(fst_bin, fst);
+        Ok(())
+    }
 }
@@ -0,0 +1,291 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::bytes::complete::tag;
+use nom::multi::{count, separated_list};
+use nom::number::complete::le_i32;
+use nom::IResult;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_i32;
+use crate::parsers::nom_utils::num;
+use crate::semirings::{
+    DivideType, LatticeWeight, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::Label;
+
+/// Weight used by Kaldi to represent compact lattices. Equivalent to Kaldi's
+/// `CompactLatticeWeight`.
+///
+/// The weight is a `LatticeWeight` together with a string of labels (typically the transition
+/// ids). The product multiplies the lattice weights and concatenates the strings. The sum keeps
+/// the weight with the lowest cost, ties being broken by keeping the shortest string and then the
+/// greatest string in lexicographic order.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub struct CompactLatticeWeight {
+    value: (LatticeWeight, Vec<Label>),
+}
+
+impl CompactLatticeWeight {
+    /// Lattice weight part of the weight.
+    pub fn weight(&self) -> &LatticeWeight {
+        &self.value.0
+    }
+
+    /// String part of the weight.
+    pub fn string(&self) -> &[Label] {
+        &self.value.1
+    }
+
+    /// Compares two weights : returns `Ordering::Greater` if `self` is kept by the sum.
+    /// Equivalent to Kaldi's `Compare`.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match self.value.0.compare(&other.value.0) {
+            Ordering::Equal => {}
+            c => return c,
+        };
+        // The shortest string wins.
+        match other.value.1.len().cmp(&self.value.1.len()) {
+            Ordering::Equal => {}
+            c => return c,
+        };
+        self.value.1.cmp(&other.value.1)
+    }
+}
+
+impl Semiring for CompactLatticeWeight {
+    type Type = (LatticeWeight, Vec<Label>);
+    type ReverseWeight = CompactLatticeWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: (LatticeWeight::zero(), vec![]),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            value: (LatticeWeight::one(), vec![]),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if self.compare(rhs.borrow()) == Ordering::Less {
+            self.value = rhs.borrow().value.clone();
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        if self.value.0.is_zero() || rhs.value.0.is_zero() {
+            *self = Self::zero();
+        } else {
+            self.value.0.times_assign(rhs.value.0)?;
+            self.value.1.extend_from_slice(&rhs.value.1);
+        }
+        Ok(())
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        let mut labels = self.value.1.clone();
+        labels.reverse();
+        Ok(Self::new((self.value.0.reverse()?, labels)))
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<CompactLatticeWeight> for CompactLatticeWeight {
+    fn reverse_back(&self) -> Result<CompactLatticeWeight> {
+        self.reverse()
+    }
+}
+
+impl AsRef<CompactLatticeWeight> for CompactLatticeWeight {
+    fn as_ref(&self) -> &CompactLatticeWeight {
+        self
+    }
+}
+
+impl WeaklyDivisibleSemiring for CompactLatticeWeight {
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        if self.value.0.is_zero() {
+            ensure!(!rhs.value.0.is_zero(), "Division by zero : zero / zero");
+            *self = Self::zero();
+            return Ok(());
+        }
+        ensure!(!rhs.value.0.is_zero(), "Division by zero");
+
+        let (l1, l2) = (&self.value.1, &rhs.value.1);
+        ensure!(
+            l2.len() <= l1.len(),
+            "Can't divide {:?} by {:?} : the divisor is longer",
+            l1,
+            l2
+        );
+        let labels = match divide_type {
+            DivideType::DivideLeft => {
+                ensure!(
+                    l1.starts_with(l2),
+                    "Can't divide {:?} by {:?} : not a prefix",
+                    l1,
+                    l2
+                );
+                l1[l2.len()..].to_vec()
+            }
+            DivideType::DivideRight => {
+                ensure!(
+                    l1.ends_with(l2),
+                    "Can't divide {:?} by {:?} : not a suffix",
+                    l1,
+                    l2
+                );
+                l1[..l1.len() - l2.len()].to_vec()
+            }
+            DivideType::DivideAny => {
+                bail!("Only explicit left or right division is defined for CompactLatticeWeight")
+            }
+        };
+        self.value.0.divide_assign(&rhs.value.0, divide_type)?;
+        self.value.1 = labels;
+        Ok(())
+    }
+}
+
+impl WeightQuantize for CompactLatticeWeight {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.value.0.quantize_assign(delta)
+    }
+}
+
+impl fmt::Display for CompactLatticeWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let labels: Vec<_> = self.value.1.iter().map(|l| l.to_string()).collect();
+        write!(f, "{},{}", self.value.0, labels.join("_"))
+    }
+}
+
+impl SerializableSemiring for CompactLatticeWeight {
+    fn weight_type() -> String {
+        "compactlattice44".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, weight) = LatticeWeight::parse_binary(i)?;
+        let (i, n) = le_i32(i)?;
+        let (i, labels) = count(le_i32, n as usize)(i)?;
+        let labels = labels.into_iter().map(|l| l as Label).collect();
+        Ok((i, Self::new((weight, labels))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value.0.write_binary(file)?;
+        write_bin_i32(file, self.value.1.len() as i32)?;
+        for label in &self.value.1 {
+            write_bin_i32(file, *label as i32)?;
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight) = LatticeWeight::parse_text(i)?;
+        let (i, _) = tag(",")(i)?;
+        let (i, labels) = separated_list(tag("_"), num)(i)?;
+        Ok((i, Self::new((weight, labels))))
+    }
+}
+
+impl From<(LatticeWeight, Vec<Label>)> for CompactLatticeWeight {
+    fn from(value: (LatticeWeight, Vec<Label>)) -> Self {
+        Self::new(value)
+    }
+}
+
+test_semiring_serializable!(
+    tests_compact_lattice_weight_serializable,
+    CompactLatticeWeight,
+    CompactLatticeWeight::one() CompactLatticeWeight::zero()
+    CompactLatticeWeight::new((LatticeWeight::new((0.3, 1.2)), vec![1, 2, 3]))
+    CompactLatticeWeight::new((LatticeWeight::new((-1.5, 2.0)), vec![5]))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clat(a: f32, b: f32, labels: Vec<Label>) -> CompactLatticeWeight {
+        CompactLatticeWeight::new((LatticeWeight::new((a, b)), labels))
+    }
+
+    #[test]
+    fn test_compact_lattice_weight() -> Result<()> {
+        let w1 = clat(1.0, 2.0, vec![1, 2]);
+        let w2 = clat(2.5, 0.0, vec![3]);
+        let w3 = clat(1.0, 2.0, vec![4]);
+        let w4 = clat(1.0, 2.0, vec![1, 1]);
+
+        // Lowest cost wins, then shortest string, then greatest string.
+        assert_eq!(w1.plus(&w2)?, w2);
+        assert_eq!(w1.plus(&w3)?, w3);
+        assert_eq!(w1.plus(&w4)?, w1);
+        assert_eq!(w4.plus(&w1)?, w1);
+        assert_eq!(w1.plus(CompactLatticeWeight::zero())?, w1);
+
+        assert_eq!(w1.times(&w2)?, clat(3.5, 2.0, vec![1, 2, 3]));
+        assert_eq!(
+            w1.times(CompactLatticeWeight::zero())?,
+            CompactLatticeWeight::zero()
+        );
+        assert_eq!(w1.times(CompactLatticeWeight::one())?, w1);
+        assert_eq!(w1.reverse()?, clat(1.0, 2.0, vec![2, 1]));
+
+        let w12 = w1.times(&w2)?;
+        assert_eq!(w12.divide(&w1, DivideType::DivideLeft)?, w2);
+        assert_eq!(w12.divide(&w2, DivideType::DivideRight)?, w1);
+        assert!(w12.divide(&w2, DivideType::DivideLeft).is_err());
+        assert!(w12.divide(&w2, DivideType::DivideAny).is_err());
+        assert!(w1
+            .divide(&CompactLatticeWeight::zero(), DivideType::DivideLeft)
+            .is_err());
+        assert_eq!(
+            CompactLatticeWeight::zero().divide(&w1, DivideType::DivideLeft)?,
+            CompactLatticeWeight::zero()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_lattice_weight_text() -> Result<()> {
+        assert_eq!(format!("{}", clat(1.5, 2.0, vec![1, 2, 3])), "1.5,2,1_2_3");
+        assert_eq!(format!("{}", clat(1.5, 2.0, vec![])), "1.5,2,");
+        let (_, w) = CompactLatticeWeight::parse_text("0.5,3,4_5")
+            .map_err(|e| format_err!("Can't parse weight : {:?}", e))?;
+        assert_eq!(w, clat(0.5, 3.0, vec![4, 5]));
+        Ok(())
+    }
+}
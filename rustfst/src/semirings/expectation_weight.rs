@@ -0,0 +1,306 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fmt::Debug;
+use std::io::Write;
+
+use anyhow::Result;
+use nom::IResult;
+
+#[cfg(test)]
+use crate::semirings::{LogWeight, ProbabilityWeight};
+use crate::semirings::{
+    ReverseBack, Semiring, SemiringProperties, SerializableSemiring, WeightQuantize,
+};
+
+/// Expectation semiring: W1 * W2, where W1 is a probability-like weight and W2 accumulates
+/// expectations. Equivalent to OpenFST's `ExpectationWeight`.
+///
+/// The operations are defined as follows :
+/// - `(p1, v1) + (p2, v2) = (p1 + p2, v1 + v2)`
+/// - `(p1, v1) * (p2, v2) = (p1 * p2, p1 * v2 + v1 * p2)`
+///
+/// The products between W1 and W2 are computed by converting the W1 weight into a W2 one.
+/// Using this semiring, the shortest distance of an FST whose arcs are weighted with `(p, p * v)`
+/// is `(P, E)` where `P` is the total probability and `E / P` the expectation of `v` over
+/// the paths.
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Default, Hash)]
+pub struct ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    pub(crate) weight: (W1, W2),
+}
+
+impl<W1, W2> AsRef<Self> for ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn as_ref(&self) -> &ExpectationWeight<W1, W2> {
+        self
+    }
+}
+
+impl<W1, W2> Semiring for ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring + From<W1>,
+    W1::ReverseWeight: Semiring<ReverseWeight = W1>,
+    W2::ReverseWeight: Semiring<ReverseWeight = W2> + From<W1::ReverseWeight>,
+{
+    type Type = (W1, W2);
+    type ReverseWeight = ExpectationWeight<W1::ReverseWeight, W2::ReverseWeight>;
+
+    fn zero() -> Self {
+        Self {
+            weight: (W1::zero(), W2::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            weight: (W1::one(), W2::zero()),
+        }
+    }
+
+    fn new(weight: <Self as Semiring>::Type) -> Self {
+        Self { weight }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.weight.0.plus_assign(&rhs.borrow().weight.0)?;
+        self.weight.1.plus_assign(&rhs.borrow().weight.1)?;
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let (p2, v2) = &rhs.borrow().weight;
+        let p1_v2 = W2::from(self.weight.0.clone()).times(v2)?;
+        let v1_p2 = self.weight.1.times(W2::from(p2.clone()))?;
+        self.weight.0.times_assign(p2)?;
+        self.weight.1 = p1_v2.plus(v1_p2)?;
+        Ok(())
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weight
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weight
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.weight = value;
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(ExpectationWeight::new((
+            self.value1().reverse()?,
+            self.value2().reverse()?,
+        )))
+    }
+
+    fn properties() -> SemiringProperties {
+        W1::properties()
+            & W2::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE
+                | SemiringProperties::IDEMPOTENT)
+    }
+}
+
+impl<W1, W2> ReverseBack<ExpectationWeight<W1, W2>>
+    for ExpectationWeight<W1::ReverseWeight, W2::ReverseWeight>
+where
+    W1: Semiring,
+    W2: Semiring + From<W1>,
+    W1::ReverseWeight: Semiring<ReverseWeight = W1>,
+    W2::ReverseWeight: Semiring<ReverseWeight = W2> + From<W1::ReverseWeight>,
+{
+    fn reverse_back(&self) -> Result<ExpectationWeight<W1, W2>> {
+        Ok(ExpectationWeight::new((
+            self.value1().reverse_back()?,
+            self.value2().reverse_back()?,
+        )))
+    }
+}
+
+impl<W1, W2> ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    pub fn value1(&self) -> &W1 {
+        &self.weight.0
+    }
+
+    pub fn value2(&self) -> &W2 {
+        &self.weight.1
+    }
+
+    pub fn set_value1(&mut self, new_weight: W1) {
+        self.weight.0 = new_weight;
+    }
+
+    pub fn set_value2(&mut self, new_weight: W2) {
+        self.weight.1 = new_weight;
+    }
+}
+
+impl<W1, W2> From<(W1, W2)> for ExpectationWeight<W1, W2>
+where
+    W1: Semiring,
+    W2: Semiring,
+{
+    fn from(t: (W1, W2)) -> Self {
+        Self { weight: t }
+    }
+}
+
+impl<W1, W2> WeightQuantize for ExpectationWeight<W1, W2>
+where
+    W1: WeightQuantize,
+    W2: WeightQuantize + From<W1>,
+    W1::ReverseWeight: Semiring<ReverseWeight = W1>,
+    W2::ReverseWeight: Semiring<ReverseWeight = W2> + From<W1::ReverseWeight>,
+{
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.weight.0.quantize_assign(delta)?;
+        self.weight.1.quantize_assign(delta)?;
+        Ok(())
+    }
+}
+
+impl<W1, W2> fmt::Display for ExpectationWeight<W1, W2>
+where
+    W1: SerializableSemiring,
+    W2: SerializableSemiring,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value1(), self.value2())?;
+        Ok(())
+    }
+}
+
+impl<W1, W2> SerializableSemiring for ExpectationWeight<W1, W2>
+where
+    W1: SerializableSemiring,
+    W2: SerializableSemiring + From<W1>,
+    W1::ReverseWeight: Semiring<ReverseWeight = W1>,
+    W2::ReverseWeight: Semiring<ReverseWeight = W2> + From<W1::ReverseWeight>,
+{
+    fn weight_type() -> String {
+        format!("expectation_{}_{}", W1::weight_type(), W2::weight_type())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, weight_1) = W1::parse_binary(i)?;
+        let (i, weight_2) = W2::parse_binary(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value1().write_binary(file)?;
+        self.value2().write_binary(file)?;
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight_1) = W1::parse_text(i)?;
+        let (i, _) = nom::bytes::complete::tag(",")(i)?;
+        let (i, weight_2) = W2::parse_text(i)?;
+        Ok((i, Self::new((weight_1, weight_2))))
+    }
+}
+
+test_semiring_serializable!(
+    tests_expectation_weight_serializable,
+    ExpectationWeight::<LogWeight, LogWeight>,
+    ExpectationWeight::new((LogWeight::new(0.2), LogWeight::new(1.7)))
+    ExpectationWeight::<LogWeight, LogWeight>::one()
+    ExpectationWeight::<LogWeight, LogWeight>::zero()
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::shortest_distance;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{SignedLogWeight, TropicalWeight};
+    use crate::Arc;
+
+    type ProbExpectation = ExpectationWeight<ProbabilityWeight, ProbabilityWeight>;
+
+    fn expectation(p: f32, v: f32) -> ProbExpectation {
+        ExpectationWeight::new((ProbabilityWeight::new(p), ProbabilityWeight::new(v)))
+    }
+
+    #[test]
+    fn test_expectation_weight() -> Result<()> {
+        let w1 = expectation(0.5, 1.0);
+        let w2 = expectation(0.25, 2.0);
+
+        assert_eq!(w1.plus(&w2)?, expectation(0.75, 3.0));
+        assert_eq!(w1.times(&w2)?, expectation(0.125, 0.5 * 2.0 + 1.0 * 0.25));
+        assert_eq!(w1.times(ProbExpectation::one())?, w1);
+        assert_eq!(w1.times(ProbExpectation::zero())?, ProbExpectation::zero());
+        assert_eq!(w1.plus(ProbExpectation::zero())?, w1);
+        assert_eq!(
+            ProbExpectation::weight_type(),
+            "expectation_probability_probability"
+        );
+
+        // Log probabilities with signed expectations.
+        let w: ExpectationWeight<LogWeight, SignedLogWeight> = ExpectationWeight::new((
+            LogWeight::new(0.0),
+            SignedLogWeight::from_sign_and_log(false, 0.0),
+        ));
+        let w2 = w.times(&w)?;
+        assert_eq!(w2.value1(), &LogWeight::new(0.0));
+        assert_eq!(
+            w2.value2(),
+            &SignedLogWeight::from_sign_and_log(false, -(2f32.ln()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expectation_weight_properties() {
+        assert!(!ProbExpectation::properties().contains(SemiringProperties::IDEMPOTENT));
+        assert!(
+            ExpectationWeight::<TropicalWeight, TropicalWeight>::properties()
+                .contains(SemiringProperties::IDEMPOTENT | SemiringProperties::COMMUTATIVE)
+        );
+        assert!(
+            !ExpectationWeight::<TropicalWeight, TropicalWeight>::properties()
+                .contains(SemiringProperties::PATH)
+        );
+    }
+
+    #[test]
+    fn test_expectation_weight_shortest_distance() -> Result<()> {
+        // Two paths : one of length 1 with probability 0.25, one of length 2 with probability
+        // 0.75. Each arc is weighted with (p, p * 1).
+        let mut fst: VectorFst<ProbExpectation> = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s2, ProbExpectation::one())?;
+        fst.add_arc(s0, Arc::new(1, 1, expectation(0.25, 0.25), s2))?;
+        fst.add_arc(s0, Arc::new(2, 2, expectation(0.75, 0.75), s1))?;
+        fst.add_arc(s1, Arc::new(3, 3, expectation(1.0, 1.0), s2))?;
+
+        let distances = shortest_distance(&fst, false)?;
+        let total = &distances[s2];
+        assert_eq!(total.value1(), &ProbabilityWeight::new(1.0));
+        // Expected length : 0.25 * 1 + 0.75 * 2.
+        assert_eq!(total.value2(), &ProbabilityWeight::new(1.75));
+        Ok(())
+    }
+}
@@ -1,9 +1,19 @@
 use std::borrow::Borrow;
 use std::i32;
+use std::io::Write;
 
 use anyhow::Result;
-
-use crate::semirings::{CompleteSemiring, ReverseBack, Semiring, SemiringProperties, StarSemiring};
+use nom::bytes::complete::tag;
+use nom::character::complete::digit1;
+use nom::combinator::{map_res, opt, recognize};
+use nom::number::complete::le_i32;
+use nom::sequence::pair;
+use nom::IResult;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_i32;
+use crate::semirings::{
+    CompleteSemiring, ReverseBack, Semiring, SemiringProperties, SerializableSemiring, StarSemiring,
+};
 
 /// Probability semiring: (x, +, 0.0, 1.0).
 #[derive(Clone, Debug, PartialEq, PartialOrd, Default, Hash, Eq, Copy)]
@@ -84,6 +94,34 @@ impl StarSemiring for IntegerWeight {
     }
 }
 
+impl SerializableSemiring for IntegerWeight {
+    fn weight_type() -> String {
+        "integer".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, weight) = le_i32(i)?;
+        Ok((i, Self::new(weight)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_i32(file, self.value)
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, weight) = map_res(recognize(pair(opt(tag("-")), digit1)), |s: &str| {
+            s.parse::<i32>()
+        })(i)?;
+        Ok((i, Self::new(weight)))
+    }
+}
+
+test_semiring_serializable!(
+    tests_integer_weight_serializable,
+    IntegerWeight,
+    IntegerWeight::one() IntegerWeight::zero() IntegerWeight::new(42) IntegerWeight::new(-7)
+);
+
 impl Into<IntegerWeight> for i32 {
     fn into(self) -> IntegerWeight {
         IntegerWeight::new(self)
@@ -0,0 +1,238 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::f32;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::bytes::complete::tag;
+use nom::number::complete::{float, le_f32};
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_f32;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+/// Weight used by Kaldi to represent lattices. Equivalent to Kaldi's `LatticeWeight`.
+///
+/// The weight is a pair of costs (typically the graph cost and the acoustic cost).
+/// The product adds the costs component-wise and the sum keeps the weight with the lowest total
+/// cost, ties being broken by the lowest first cost. The zero is `(inf, inf)` and the one is
+/// `(0, 0)`.
+#[derive(Clone, Debug, PartialOrd, Copy)]
+pub struct LatticeWeight {
+    value: (f32, f32),
+}
+
+impl LatticeWeight {
+    /// Compares two weights : returns `Ordering::Greater` if `self` has a lower cost than
+    /// `other`, i.e `self` is kept by the sum. Equivalent to Kaldi's `Compare`.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        let f1 = self.value.0 + self.value.1;
+        let f2 = other.value.0 + other.value.1;
+        if f1 < f2 {
+            Ordering::Greater
+        } else if f1 > f2 {
+            Ordering::Less
+        } else if self.value.0 < other.value.0 {
+            Ordering::Greater
+        } else if self.value.0 > other.value.0 {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+impl Semiring for LatticeWeight {
+    type Type = (f32, f32);
+    type ReverseWeight = LatticeWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: (f32::INFINITY, f32::INFINITY),
+        }
+    }
+
+    fn one() -> Self {
+        Self { value: (0.0, 0.0) }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if self.compare(rhs.borrow()) == Ordering::Less {
+            self.value = rhs.borrow().value;
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.value.0 += rhs.borrow().value.0;
+        self.value.1 += rhs.borrow().value.1;
+        Ok(())
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<LatticeWeight> for LatticeWeight {
+    fn reverse_back(&self) -> Result<LatticeWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<LatticeWeight> for LatticeWeight {
+    fn as_ref(&self) -> &LatticeWeight {
+        self
+    }
+}
+
+impl WeaklyDivisibleSemiring for LatticeWeight {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        let a = self.value.0 - rhs.value.0;
+        let b = self.value.1 - rhs.value.1;
+        if a.is_nan() || b.is_nan() || a.is_infinite() || b.is_infinite() {
+            // Division by zero or of zero.
+            *self = Self::zero();
+        } else {
+            self.value = (a, b);
+        }
+        Ok(())
+    }
+}
+
+impl WeightQuantize for LatticeWeight {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        let sum = self.value.0 + self.value.1;
+        if sum.is_infinite() || sum.is_nan() {
+            self.value = (sum, sum);
+        } else {
+            self.value = (
+                ((self.value.0 / delta) + 0.5).floor() * delta,
+                ((self.value.1 / delta) + 0.5).floor() * delta,
+            );
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for LatticeWeight {
+    fn eq(&self, other: &Self) -> bool {
+        self.quantize(KDELTA).unwrap().value == other.quantize(KDELTA).unwrap().value
+    }
+}
+
+impl Eq for LatticeWeight {}
+
+impl Hash for LatticeWeight {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (a, b) = self.quantize(KDELTA).unwrap().value;
+        OrderedFloat(a).hash(state);
+        OrderedFloat(b).hash(state);
+    }
+}
+
+impl fmt::Display for LatticeWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value.0, self.value.1)
+    }
+}
+
+impl SerializableSemiring for LatticeWeight {
+    fn weight_type() -> String {
+        "lattice4".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, a) = le_f32(i)?;
+        let (i, b) = le_f32(i)?;
+        Ok((i, Self::new((a, b))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_f32(file, self.value.0)?;
+        write_bin_f32(file, self.value.1)
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, a) = float(i)?;
+        let (i, _) = tag(",")(i)?;
+        let (i, b) = float(i)?;
+        Ok((i, Self::new((a, b))))
+    }
+}
+
+impl From<(f32, f32)> for LatticeWeight {
+    fn from(value: (f32, f32)) -> Self {
+        Self::new(value)
+    }
+}
+
+test_semiring_serializable!(
+    tests_lattice_weight_serializable,
+    LatticeWeight,
+    LatticeWeight::one() LatticeWeight::zero() LatticeWeight::new((0.3, 1.2)) LatticeWeight::new((-1.5, 2.0))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lattice_weight() -> Result<()> {
+        let w1 = LatticeWeight::new((1.0, 2.0));
+        let w2 = LatticeWeight::new((2.5, 0.0));
+        let w3 = LatticeWeight::new((2.0, 1.0));
+
+        // Lowest total cost wins, then lowest first cost.
+        assert_eq!(w1.plus(w2)?, w2);
+        assert_eq!(w1.plus(w3)?, w1);
+        assert_eq!(w3.plus(w1)?, w1);
+        assert_eq!(w1.plus(LatticeWeight::zero())?, w1);
+
+        assert_eq!(w1.times(w2)?, LatticeWeight::new((3.5, 2.0)));
+        assert_eq!(w1.times(LatticeWeight::zero())?, LatticeWeight::zero());
+        assert_eq!(w1.times(LatticeWeight::one())?, w1);
+
+        assert_eq!(w1.times(w2)?.divide(&w2, DivideType::DivideAny)?, w1);
+        assert_eq!(
+            w1.divide(&LatticeWeight::zero(), DivideType::DivideAny)?,
+            LatticeWeight::zero()
+        );
+        assert_eq!(
+            LatticeWeight::new((0.1001, 2.0)).quantize(0.01)?,
+            LatticeWeight::new((0.1, 2.0))
+        );
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use ordered_float::OrderedFloat;
 use crate::parsers::bin_fst::utils_serialization::write_bin_f32;
 use crate::semirings::{
     CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
-    StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
+    StarSemiring, TropicalWeight, WeaklyDivisibleSemiring, WeightQuantize,
 };
 use crate::KDELTA;
 use nom::number::complete::{float, le_f32};
@@ -165,3 +165,15 @@ impl Into<LogWeight> for f32 {
         LogWeight::new(self)
     }
 }
+
+impl From<TropicalWeight> for LogWeight {
+    fn from(weight: TropicalWeight) -> Self {
+        LogWeight::new(*weight.value())
+    }
+}
+
+impl From<LogWeight> for TropicalWeight {
+    fn from(weight: LogWeight) -> Self {
+        TropicalWeight::new(*weight.value())
+    }
+}
@@ -0,0 +1,201 @@
+use std::borrow::Borrow;
+use std::f32;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::number::complete::{float, le_f32};
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_f32;
+use crate::semirings::semiring::SerializableSemiring;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+/// Max tropical semiring (also known as arctic semiring): (max, +, -inf, 0).
+///
+/// The shortest path according to this semiring is the path with the largest total weight.
+#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+pub struct MaxTropicalWeight {
+    value: OrderedFloat<f32>,
+}
+
+impl Semiring for MaxTropicalWeight {
+    type Type = f32;
+    type ReverseWeight = MaxTropicalWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: OrderedFloat(f32::NEG_INFINITY),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            value: OrderedFloat(0.0),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        MaxTropicalWeight {
+            value: OrderedFloat(value),
+        }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if rhs.borrow().value > self.value {
+            self.value = rhs.borrow().value;
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let f1 = self.value();
+        let f2 = rhs.borrow().value();
+        if f1.eq(&f32::NEG_INFINITY) {
+        } else if f2.eq(&f32::NEG_INFINITY) {
+            self.value.0 = *f2;
+        } else {
+            self.value.0 += f2;
+        }
+        Ok(())
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value.0
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value.0
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value.0 = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<MaxTropicalWeight> for MaxTropicalWeight {
+    fn reverse_back(&self) -> Result<MaxTropicalWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<MaxTropicalWeight> for MaxTropicalWeight {
+    fn as_ref(&self) -> &MaxTropicalWeight {
+        self
+    }
+}
+
+display_semiring!(MaxTropicalWeight);
+
+impl WeaklyDivisibleSemiring for MaxTropicalWeight {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        ensure!(
+            rhs.value.0 != f32::NEG_INFINITY,
+            "Division by zero in MaxTropicalWeight"
+        );
+        self.value.0 -= rhs.value.0;
+        Ok(())
+    }
+}
+
+impl_quantize_f32!(MaxTropicalWeight);
+
+partial_eq_and_hash_f32!(MaxTropicalWeight);
+
+impl SerializableSemiring for MaxTropicalWeight {
+    fn weight_type() -> String {
+        "max_tropical".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, weight) = le_f32(i)?;
+        Ok((i, Self::new(weight)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_f32(file, *self.value())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, f) = float(i)?;
+        Ok((i, Self::new(f)))
+    }
+}
+
+impl From<f32> for MaxTropicalWeight {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+test_semiring_serializable!(
+    tests_max_tropical_weight_serializable,
+    MaxTropicalWeight,
+    MaxTropicalWeight::one() MaxTropicalWeight::zero() MaxTropicalWeight::new(0.3) MaxTropicalWeight::new(-1.2)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::shortest_path;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::utils::decode_linear_fst;
+    use crate::Arc;
+
+    #[test]
+    fn test_max_tropical_weight() -> Result<()> {
+        let w1 = MaxTropicalWeight::new(1.0);
+        let w2 = MaxTropicalWeight::new(3.0);
+
+        assert_eq!(w1.plus(w2)?, w2);
+        assert_eq!(w1.times(w2)?, MaxTropicalWeight::new(4.0));
+        assert_eq!(w1.plus(MaxTropicalWeight::zero())?, w1);
+        assert_eq!(w1.times(MaxTropicalWeight::one())?, w1);
+        assert_eq!(
+            w1.times(MaxTropicalWeight::zero())?,
+            MaxTropicalWeight::zero()
+        );
+        assert_eq!(w1.times(w2)?.divide(&w2, DivideType::DivideAny)?, w1);
+        assert!(w1
+            .divide(&MaxTropicalWeight::zero(), DivideType::DivideAny)
+            .is_err());
+        assert_eq!(MaxTropicalWeight::weight_type(), "max_tropical");
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_tropical_weight_longest_path() -> Result<()> {
+        let mut fst: VectorFst<MaxTropicalWeight> = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, MaxTropicalWeight::one())?;
+        fst.add_arc(s0, Arc::new(1, 1, MaxTropicalWeight::new(1.0), s1))?;
+        fst.add_arc(s0, Arc::new(2, 2, MaxTropicalWeight::new(4.0), s1))?;
+        fst.add_arc(s0, Arc::new(3, 3, MaxTropicalWeight::new(2.0), s1))?;
+
+        let best: VectorFst<MaxTropicalWeight> = shortest_path(&fst, 1, false)?;
+        let path = decode_linear_fst(&best)?;
+        assert_eq!(path.ilabels, vec![2]);
+        assert_eq!(path.weight, MaxTropicalWeight::new(4.0));
+        Ok(())
+    }
+}
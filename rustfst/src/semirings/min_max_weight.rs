@@ -0,0 +1,195 @@
+use std::borrow::Borrow;
+use std::f32;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::number::complete::{float, le_f32};
+use nom::IResult;
+use ordered_float::OrderedFloat;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_f32;
+use crate::semirings::semiring::SerializableSemiring;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::KDELTA;
+
+/// MinMax semiring: (min, max, inf, -inf). Also known as the bottleneck or fuzzy semiring, the
+/// shortest path being the one whose largest weight is the smallest.
+#[derive(Clone, Debug, PartialOrd, Default, Copy, Eq)]
+pub struct MinMaxWeight {
+    value: OrderedFloat<f32>,
+}
+
+impl Semiring for MinMaxWeight {
+    type Type = f32;
+    type ReverseWeight = MinMaxWeight;
+
+    fn zero() -> Self {
+        Self {
+            value: OrderedFloat(f32::INFINITY),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            value: OrderedFloat(f32::NEG_INFINITY),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        MinMaxWeight {
+            value: OrderedFloat(value),
+        }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if rhs.borrow().value < self.value {
+            self.value = rhs.borrow().value;
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        if rhs.borrow().value > self.value {
+            self.value = rhs.borrow().value;
+        }
+        Ok(())
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value.0
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value.0
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value.0 = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+            | SemiringProperties::PATH
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl ReverseBack<MinMaxWeight> for MinMaxWeight {
+    fn reverse_back(&self) -> Result<MinMaxWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<MinMaxWeight> for MinMaxWeight {
+    fn as_ref(&self) -> &MinMaxWeight {
+        self
+    }
+}
+
+display_semiring!(MinMaxWeight);
+
+/// Returns `self` if it is greater than or equal to `rhs` (as `self = max(self, rhs)` holds)
+/// and `zero` otherwise.
+impl WeaklyDivisibleSemiring for MinMaxWeight {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        if self.value < rhs.value {
+            *self = Self::zero();
+        }
+        Ok(())
+    }
+}
+
+impl_quantize_f32!(MinMaxWeight);
+
+partial_eq_and_hash_f32!(MinMaxWeight);
+
+impl SerializableSemiring for MinMaxWeight {
+    fn weight_type() -> String {
+        "minmax".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, weight) = le_f32(i)?;
+        Ok((i, Self::new(weight)))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        write_bin_f32(file, *self.value())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, f) = float(i)?;
+        Ok((i, Self::new(f)))
+    }
+}
+
+impl From<f32> for MinMaxWeight {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+test_semiring_serializable!(
+    tests_min_max_weight_serializable,
+    MinMaxWeight,
+    MinMaxWeight::one() MinMaxWeight::zero() MinMaxWeight::new(0.3) MinMaxWeight::new(-1.2)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::shortest_path;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::utils::decode_linear_fst;
+    use crate::Arc;
+
+    #[test]
+    fn test_min_max_weight() -> Result<()> {
+        let w1 = MinMaxWeight::new(1.0);
+        let w2 = MinMaxWeight::new(3.0);
+
+        assert_eq!(w1.plus(w2)?, w1);
+        assert_eq!(w1.times(w2)?, w2);
+        assert_eq!(w1.plus(MinMaxWeight::zero())?, w1);
+        assert_eq!(w1.times(MinMaxWeight::one())?, w1);
+        assert_eq!(w1.times(MinMaxWeight::zero())?, MinMaxWeight::zero());
+        assert_eq!(w2.divide(&w1, DivideType::DivideAny)?, w2);
+        assert_eq!(w1.divide(&w2, DivideType::DivideAny)?, MinMaxWeight::zero());
+        assert_eq!(MinMaxWeight::weight_type(), "minmax");
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_max_weight_bottleneck_path() -> Result<()> {
+        // The path 0 -> 1 -> 3 has the smallest total cost but the path 0 -> 2 -> 3 has the
+        // smallest maximum cost.
+        let mut fst: VectorFst<MinMaxWeight> = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s3, MinMaxWeight::one())?;
+        fst.add_arc(s0, Arc::new(1, 1, MinMaxWeight::new(0.5), s1))?;
+        fst.add_arc(s1, Arc::new(2, 2, MinMaxWeight::new(5.0), s3))?;
+        fst.add_arc(s0, Arc::new(3, 3, MinMaxWeight::new(3.0), s2))?;
+        fst.add_arc(s2, Arc::new(4, 4, MinMaxWeight::new(3.0), s3))?;
+
+        let best: VectorFst<MinMaxWeight> = shortest_path(&fst, 1, false)?;
+        let path = decode_linear_fst(&best)?;
+        assert_eq!(path.ilabels, vec![3, 4]);
+        assert_eq!(path.weight, MinMaxWeight::new(3.0));
+        Ok(())
+    }
+}
@@ -1,21 +1,51 @@
-#![allow(unused)]
-
+use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::fmt;
-use std::fmt::Debug;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::io::Write;
 
 use anyhow::Result;
-
+use generic_array::sequence::GenericSequence;
 use generic_array::ArrayLength;
 use generic_array::GenericArray;
+use nom::bytes::complete::tag;
+use nom::IResult;
 
 use crate::semirings::{
-    DivideType, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
 };
 
 /// Cartesian power semiring: W ^ n.
+///
+/// The size `N` is a type-level integer from `generic_array::typenum`. All the operations are
+/// done element-wise.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use generic_array::typenum::U2;
+/// # use generic_array::GenericArray;
+/// # use rustfst::semirings::{PowerWeight, Semiring, TropicalWeight};
+/// # fn main() -> Result<()> {
+/// let w1: PowerWeight<TropicalWeight, U2> = PowerWeight::new(GenericArray::clone_from_slice(&[
+///     TropicalWeight::new(1.0),
+///     TropicalWeight::new(3.0),
+/// ]));
+/// let w2 = PowerWeight::new(GenericArray::clone_from_slice(&[
+///     TropicalWeight::new(2.0),
+///     TropicalWeight::new(2.0),
+/// ]));
+///
+/// assert_eq!(
+///     w1.plus(&w2)?.value().as_slice(),
+///     &[TropicalWeight::new(1.0), TropicalWeight::new(2.0)]
+/// );
+/// # Ok(())
+/// # }
+/// ```
 pub struct PowerWeight<W, N>
 where
     W: Semiring,
@@ -26,11 +56,17 @@ where
 
 impl<W, N> fmt::Display for PowerWeight<W, N>
 where
-    W: Semiring,
+    W: SerializableSemiring,
     N: ArrayLength<W>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.weights.as_slice().fmt(f)
+        for (idx, weight) in self.weights.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", weight)?;
+        }
+        Ok(())
     }
 }
 
@@ -92,7 +128,7 @@ where
     N: ArrayLength<W>,
 {
     fn as_ref(&self) -> &PowerWeight<W, N> {
-        &self
+        self
     }
 }
 
@@ -103,92 +139,227 @@ where
 {
 }
 
-//impl<W, N> Semiring for PowerWeight<W, N>
-//where
-//    W: Semiring,
-//    N: ArrayLength<W>,
-//{
-//    type Type = GenericArray<W, N>;
-//    type ReverseSemiring<P> = PowerWeight<W::ReverseSemiring, P>;
-//
-//    fn zero() -> Self {
-//        Self {
-//            weights: GenericArray::clone_from_slice(&[W::zero()]),
-//        }
-//    }
-//
-//    fn one() -> Self {
-//        Self {
-//            weights: GenericArray::clone_from_slice(&[W::one()]),
-//        }
-//    }
-//
-//    fn new(value: <Self as Semiring>::Type) -> Self {
-//        Self { weights: value }
-//    }
-//
-//    fn plus_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Result<()> {
-//        for i in 0..self.weights.len() {
-//            self.weights[i].plus_assign(&rhs.as_ref().weights[i])?;
-//        }
-//        Ok(())
-//    }
-//
-//    fn times_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Result<()> {
-//        for i in 0..self.weights.len() {
-//            self.weights[i].times_assign(&rhs.as_ref().weights[i])?;
-//        }
-//        Ok(())
-//    }
-//
-//    fn value(&self) -> <Self as Semiring>::Type {
-//        self.weights.clone()
-//    }
-//
-//    fn set_value(&mut self, value: <Self as Semiring>::Type) {
-//        self.weights = value;
-//    }
-//
-//    fn reverse(&self) -> Self::ReverseSemiring {
-//        let mut rw = Vec::with_capacity(self.weights.len());
-//        for i in 0..self.weights.len() {
-//            rw.push(self.weights[i].reverse());
-//        }
-//        PowerWeight::new(GenericArray::clone_from(rw))
-//    }
-//
-//    fn properties() -> SemiringProperties {
-//        W::properties()
-//            & (SemiringProperties::LEFT_SEMIRING
-//                | SemiringProperties::RIGHT_SEMIRING
-//                | SemiringProperties::COMMUTATIVE
-//                | SemiringProperties::IDEMPOTENT)
-//    }
-//}
-//
-//impl<W, N> WeaklyDivisibleSemiring for PowerWeight<W, N>
-//where
-//    W: WeaklyDivisibleSemiring,
-//    N: ArrayLength<W>,
-//{
-//    fn divide(&self, rhs: &Self, divide_type: DivideType) -> Result<Self> {
-//        let mut mul = self.clone();
-//        for i in 0..self.weights.len() {
-//            mul.weights[i] = self.weights[i].divide(&rhs.weights[i], divide_type)?;
-//        }
-//        Ok(mul)
-//    }
-//}
-//
-//impl<W, N> WeightQuantize for PowerWeight<W, N>
-//where
-//    W: WeightQuantize,
-//    N: ArrayLength<W>,
-//{
-//    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
-//        for i in 0..self.weights.len() {
-//            unsafe { self.weights.get_unchecked_mut(i).quantize_assign(delta)? };
-//        }
-//        Ok(())
-//    }
-//}
+impl<W, N> Semiring for PowerWeight<W, N>
+where
+    W: Semiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight>,
+{
+    type Type = GenericArray<W, N>;
+    type ReverseWeight = PowerWeight<W::ReverseWeight, N>;
+
+    fn zero() -> Self {
+        Self {
+            weights: GenericArray::generate(|_| W::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            weights: GenericArray::generate(|_| W::one()),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { weights: value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        for (w, w_rhs) in self.weights.iter_mut().zip(rhs.borrow().weights.iter()) {
+            w.plus_assign(w_rhs)?;
+        }
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        for (w, w_rhs) in self.weights.iter_mut().zip(rhs.borrow().weights.iter()) {
+            w.times_assign(w_rhs)?;
+        }
+        Ok(())
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weights
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weights
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.weights = value;
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        let weights = self
+            .weights
+            .iter()
+            .map(|w| w.reverse())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(PowerWeight::new(GenericArray::clone_from_slice(&weights)))
+    }
+
+    fn properties() -> SemiringProperties {
+        W::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE
+                | SemiringProperties::IDEMPOTENT)
+    }
+}
+
+impl<W, N> ReverseBack<PowerWeight<W, N>> for PowerWeight<W::ReverseWeight, N>
+where
+    W: Semiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight>,
+{
+    fn reverse_back(&self) -> Result<PowerWeight<W, N>> {
+        let weights = self
+            .weights
+            .iter()
+            .map(|w| w.reverse_back())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(PowerWeight::new(GenericArray::clone_from_slice(&weights)))
+    }
+}
+
+impl<W, N> WeaklyDivisibleSemiring for PowerWeight<W, N>
+where
+    W: WeaklyDivisibleSemiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight>,
+{
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        for (w, w_rhs) in self.weights.iter_mut().zip(rhs.weights.iter()) {
+            w.divide_assign(w_rhs, divide_type)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W, N> WeightQuantize for PowerWeight<W, N>
+where
+    W: WeightQuantize,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight>,
+{
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        for w in self.weights.iter_mut() {
+            w.quantize_assign(delta)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W, N> SerializableSemiring for PowerWeight<W, N>
+where
+    W: SerializableSemiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+    N: ArrayLength<W> + ArrayLength<W::ReverseWeight>,
+{
+    fn weight_type() -> String {
+        format!("{}_^{}", W::weight_type(), N::to_usize())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let mut weights = Vec::with_capacity(N::to_usize());
+        let mut i = i;
+        for _ in 0..N::to_usize() {
+            let (rest, weight) = W::parse_binary(i)?;
+            weights.push(weight);
+            i = rest;
+        }
+        Ok((i, Self::new(GenericArray::clone_from_slice(&weights))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        for weight in self.weights.iter() {
+            weight.write_binary(file)?;
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let mut weights = Vec::with_capacity(N::to_usize());
+        let mut i = i;
+        for idx in 0..N::to_usize() {
+            if idx > 0 {
+                let (rest, _) = tag(",")(i)?;
+                i = rest;
+            }
+            let (rest, weight) = W::parse_text(i)?;
+            weights.push(weight);
+            i = rest;
+        }
+        Ok((i, Self::new(GenericArray::clone_from_slice(&weights))))
+    }
+}
+
+#[cfg(test)]
+use crate::semirings::{LogWeight, TropicalWeight};
+#[cfg(test)]
+use generic_array::typenum::{U1, U3};
+
+test_semiring_serializable!(
+    tests_power_weight_serializable,
+    PowerWeight::<TropicalWeight, U3>,
+    PowerWeight::<TropicalWeight, U3>::one()
+    PowerWeight::<TropicalWeight, U3>::zero()
+    PowerWeight::<TropicalWeight, U3>::new(GenericArray::clone_from_slice(&[TropicalWeight::new(0.5), TropicalWeight::new(2.0), TropicalWeight::zero()]))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn power_weight(weights: &[f32]) -> PowerWeight<LogWeight, U3> {
+        let weights: Vec<_> = weights.iter().map(|w| LogWeight::new(*w)).collect();
+        PowerWeight::new(GenericArray::clone_from_slice(&weights))
+    }
+
+    #[test]
+    fn test_power_weight() -> Result<()> {
+        let w1 = power_weight(&[1.0, 2.0, 3.0]);
+        let w2 = power_weight(&[0.5, 0.5, 0.5]);
+
+        assert_eq!(
+            w1.plus(&w2)?,
+            power_weight(&[
+                *LogWeight::new(1.0).plus(LogWeight::new(0.5))?.value(),
+                *LogWeight::new(2.0).plus(LogWeight::new(0.5))?.value(),
+                *LogWeight::new(3.0).plus(LogWeight::new(0.5))?.value(),
+            ])
+        );
+        assert_eq!(w1.times(&w2)?, power_weight(&[1.5, 2.5, 3.5]));
+        assert_eq!(w1.times(&w2)?.divide(&w2, DivideType::DivideAny)?, w1);
+        assert_eq!(w1.plus(PowerWeight::zero())?, w1);
+        assert_eq!(w1.times(PowerWeight::one())?, w1);
+        assert_eq!(w1.reverse()?.reverse_back()?, w1);
+        assert_eq!(
+            power_weight(&[0.1001, 2.0, 3.0]).quantize(0.01)?,
+            power_weight(&[0.1, 2.0, 3.0])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_power_weight_serialization() -> Result<()> {
+        assert_eq!(
+            PowerWeight::<LogWeight, U3>::weight_type(),
+            "log_^3".to_string()
+        );
+        assert_eq!(format!("{}", power_weight(&[1.0, 2.5, 3.0])), "1,2.5,3");
+
+        let (rest, w) = PowerWeight::<LogWeight, U3>::parse_text("1,2.5,3")
+            .map_err(|e| format_err!("Can't parse weight : {:?}", e))?;
+        assert_eq!(rest, "");
+        assert_eq!(w, power_weight(&[1.0, 2.5, 3.0]));
+        assert!(PowerWeight::<LogWeight, U3>::parse_text("1,2.5").is_err());
+
+        let mut serialization = vec![];
+        PowerWeight::<TropicalWeight, U1>::one().write_binary(&mut serialization)?;
+        assert_eq!(serialization, 0.0f32.to_le_bytes());
+        Ok(())
+    }
+}
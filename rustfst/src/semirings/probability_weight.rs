@@ -4,8 +4,8 @@ use std::hash::{Hash, Hasher};
 
 use crate::parsers::bin_fst::utils_serialization::write_bin_f32;
 use crate::semirings::{
-    CompleteSemiring, DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
-    StarSemiring, WeaklyDivisibleSemiring, WeightQuantize,
+    CompleteSemiring, DivideType, LogWeight, ReverseBack, Semiring, SemiringProperties,
+    SerializableSemiring, StarSemiring, TropicalWeight, WeaklyDivisibleSemiring, WeightQuantize,
 };
 use crate::KDELTA;
 use anyhow::Result;
@@ -142,3 +142,27 @@ impl Into<ProbabilityWeight> for f32 {
         ProbabilityWeight::new(self)
     }
 }
+
+impl From<LogWeight> for ProbabilityWeight {
+    fn from(weight: LogWeight) -> Self {
+        ProbabilityWeight::new((-weight.value()).exp())
+    }
+}
+
+impl From<ProbabilityWeight> for LogWeight {
+    fn from(weight: ProbabilityWeight) -> Self {
+        LogWeight::new(-weight.value().ln())
+    }
+}
+
+impl From<TropicalWeight> for ProbabilityWeight {
+    fn from(weight: TropicalWeight) -> Self {
+        ProbabilityWeight::new((-weight.value()).exp())
+    }
+}
+
+impl From<ProbabilityWeight> for TropicalWeight {
+    fn from(weight: ProbabilityWeight) -> Self {
+        TropicalWeight::new(-weight.value().ln())
+    }
+}
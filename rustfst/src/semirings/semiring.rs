@@ -140,6 +140,40 @@ macro_rules! impl_quantize_f32 {
     };
 }
 
+macro_rules! impl_quantize_f64 {
+    ($semiring: ident) => {
+        impl WeightQuantize for $semiring {
+            fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+                let v = *self.value();
+                if v.is_infinite() {
+                    return Ok(());
+                }
+                let delta = f64::from(delta);
+                self.set_value(((v / delta) + 0.5).floor() * delta);
+                Ok(())
+            }
+        }
+    };
+}
+
+// The 64-bit weights are compared with the same `KDELTA` as the 32-bit ones, as OpenFST does
+// for its `double` weights.
+macro_rules! partial_eq_and_hash_f64 {
+    ($semiring:tt) => {
+        impl PartialEq for $semiring {
+            fn eq(&self, other: &Self) -> bool {
+                self.quantize(KDELTA).unwrap().value() == other.quantize(KDELTA).unwrap().value()
+            }
+        }
+
+        impl Hash for $semiring {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.quantize(KDELTA).unwrap().value.hash(state);
+            }
+        }
+    };
+}
+
 macro_rules! display_semiring {
     ($semiring:tt) => {
         use std::fmt;
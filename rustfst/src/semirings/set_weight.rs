@@ -0,0 +1,413 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::io::Write;
+use std::marker::PhantomData;
+
+use anyhow::Result;
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::multi::{count, separated_nonempty_list};
+use nom::number::complete::le_i32;
+use nom::IResult;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_i32;
+use crate::parsers::nom_utils::num;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+use crate::Label;
+
+/// Content of a `SetWeight` : either the universal set or a finite set of labels.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub enum SetWeightVariant {
+    Universal,
+    /// Sorted labels, without duplicates.
+    Labels(Vec<Label>),
+}
+
+/// Determines the operations used by a `SetWeight`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetType {
+    /// Plus is the intersection and times the union. Zero is the universal set and One the
+    /// empty set.
+    SetIntersectUnion,
+    /// Plus is the union and times the intersection. Zero is the empty set and One the
+    /// universal set.
+    SetUnionIntersect,
+}
+
+pub trait SetWeightOption: Debug + Hash + Clone + PartialOrd + Eq {
+    fn set_type() -> SetType;
+}
+
+/// Options of a `SetWeight` using the intersection for Plus and the union for Times.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash, Default)]
+pub struct SetIntersectUnion {}
+
+impl SetWeightOption for SetIntersectUnion {
+    fn set_type() -> SetType {
+        SetType::SetIntersectUnion
+    }
+}
+
+/// Options of a `SetWeight` using the union for Plus and the intersection for Times.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash, Default)]
+pub struct SetUnionIntersect {}
+
+impl SetWeightOption for SetUnionIntersect {
+    fn set_type() -> SetType {
+        SetType::SetUnionIntersect
+    }
+}
+
+/// Set semiring: sets of labels using union and intersection for Plus and Times, as specified
+/// by the options `O`. Equivalent to OpenFST's `SetWeight`.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, SetUnionIntersect, SetWeight};
+/// # fn main() -> Result<()> {
+/// let w1 = SetWeight::<SetUnionIntersect>::from_labels(vec![3, 1]);
+/// let w2 = SetWeight::<SetUnionIntersect>::from_labels(vec![2, 3]);
+///
+/// assert_eq!(w1.plus(&w2)?, SetWeight::from_labels(vec![1, 2, 3]));
+/// assert_eq!(w1.times(&w2)?, SetWeight::from_labels(vec![3]));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub struct SetWeight<O: SetWeightOption> {
+    pub(crate) value: SetWeightVariant,
+    ghost: PhantomData<O>,
+}
+
+fn set_union(s1: &SetWeightVariant, s2: &SetWeightVariant) -> SetWeightVariant {
+    match (s1, s2) {
+        (SetWeightVariant::Labels(l1), SetWeightVariant::Labels(l2)) => {
+            let mut labels: Vec<_> = l1.iter().chain(l2.iter()).cloned().collect();
+            labels.sort();
+            labels.dedup();
+            SetWeightVariant::Labels(labels)
+        }
+        _ => SetWeightVariant::Universal,
+    }
+}
+
+fn set_intersection(s1: &SetWeightVariant, s2: &SetWeightVariant) -> SetWeightVariant {
+    match (s1, s2) {
+        (SetWeightVariant::Universal, s) | (s, SetWeightVariant::Universal) => s.clone(),
+        (SetWeightVariant::Labels(l1), SetWeightVariant::Labels(l2)) => SetWeightVariant::Labels(
+            l1.iter()
+                .filter(|l| l2.binary_search(l).is_ok())
+                .cloned()
+                .collect(),
+        ),
+    }
+}
+
+fn set_difference(s1: &SetWeightVariant, s2: &SetWeightVariant) -> Result<SetWeightVariant> {
+    match (s1, s2) {
+        (_, SetWeightVariant::Universal) => Ok(SetWeightVariant::Labels(vec![])),
+        (SetWeightVariant::Universal, _) => {
+            bail!("The complement of a finite set can't be represented by a SetWeight")
+        }
+        (SetWeightVariant::Labels(l1), SetWeightVariant::Labels(l2)) => {
+            Ok(SetWeightVariant::Labels(
+                l1.iter()
+                    .filter(|l| l2.binary_search(l).is_err())
+                    .cloned()
+                    .collect(),
+            ))
+        }
+    }
+}
+
+impl<O: SetWeightOption> SetWeight<O> {
+    /// Builds the set containing the labels, in any order.
+    pub fn from_labels<I: IntoIterator<Item = Label>>(labels: I) -> Self {
+        Self::new(SetWeightVariant::Labels(labels.into_iter().collect()))
+    }
+
+    /// Set containing all the labels.
+    pub fn universal() -> Self {
+        Self::new(SetWeightVariant::Universal)
+    }
+
+    pub fn is_universal(&self) -> bool {
+        self.value == SetWeightVariant::Universal
+    }
+
+    pub fn contains(&self, label: Label) -> bool {
+        match &self.value {
+            SetWeightVariant::Universal => true,
+            SetWeightVariant::Labels(labels) => labels.binary_search(&label).is_ok(),
+        }
+    }
+}
+
+impl<O: SetWeightOption> AsRef<SetWeight<O>> for SetWeight<O> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+impl<O: SetWeightOption> Semiring for SetWeight<O> {
+    type Type = SetWeightVariant;
+    type ReverseWeight = SetWeight<O>;
+
+    fn zero() -> Self {
+        match O::set_type() {
+            SetType::SetIntersectUnion => Self::universal(),
+            SetType::SetUnionIntersect => Self::from_labels(vec![]),
+        }
+    }
+
+    fn one() -> Self {
+        match O::set_type() {
+            SetType::SetIntersectUnion => Self::from_labels(vec![]),
+            SetType::SetUnionIntersect => Self::universal(),
+        }
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        let value = match value {
+            SetWeightVariant::Labels(mut labels) => {
+                labels.sort();
+                labels.dedup();
+                SetWeightVariant::Labels(labels)
+            }
+            SetWeightVariant::Universal => SetWeightVariant::Universal,
+        };
+        Self {
+            value,
+            ghost: PhantomData,
+        }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.value = match O::set_type() {
+            SetType::SetIntersectUnion => set_intersection(&self.value, &rhs.borrow().value),
+            SetType::SetUnionIntersect => set_union(&self.value, &rhs.borrow().value),
+        };
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.value = match O::set_type() {
+            SetType::SetIntersectUnion => set_union(&self.value, &rhs.borrow().value),
+            SetType::SetUnionIntersect => set_intersection(&self.value, &rhs.borrow().value),
+        };
+        Ok(())
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        *self = Self::new(value);
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(self.clone())
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+            | SemiringProperties::IDEMPOTENT
+    }
+}
+
+impl<O: SetWeightOption> ReverseBack<SetWeight<O>> for SetWeight<O> {
+    fn reverse_back(&self) -> Result<SetWeight<O>> {
+        Ok(self.clone())
+    }
+}
+
+impl<O: SetWeightOption> WeaklyDivisibleSemiring for SetWeight<O> {
+    fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Result<()> {
+        match O::set_type() {
+            // (w1 \ w2) U w2 = w1 if w2 is included in w1.
+            SetType::SetIntersectUnion => {
+                self.value = set_difference(&self.value, &rhs.value)?;
+            }
+            // w1 ∩ w2 = w1 if w1 is included in w2.
+            SetType::SetUnionIntersect => {}
+        };
+        Ok(())
+    }
+}
+
+impl<O: SetWeightOption> WeightQuantize for SetWeight<O> {
+    fn quantize_assign(&mut self, _delta: f32) -> Result<()> {
+        // Nothing to do
+        Ok(())
+    }
+}
+
+impl<O: SetWeightOption> fmt::Display for SetWeight<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            SetWeightVariant::Universal => {
+                write!(f, "Universal")?;
+            }
+            SetWeightVariant::Labels(v) => {
+                if v.is_empty() {
+                    write!(f, "Empty")?;
+                } else {
+                    for (idx, label) in v.iter().enumerate() {
+                        if idx > 0 {
+                            write!(f, "_")?;
+                        }
+                        write!(f, "{}", label)?;
+                    }
+                }
+            }
+        };
+        Ok(())
+    }
+}
+
+impl<O: SetWeightOption> SetWeight<O> {
+    fn parse_text_universal(i: &str) -> IResult<&str, Self> {
+        let (i, _) = tag("Universal")(i)?;
+        Ok((i, Self::universal()))
+    }
+
+    fn parse_text_empty(i: &str) -> IResult<&str, Self> {
+        let (i, _) = tag("Empty")(i)?;
+        Ok((i, Self::from_labels(vec![])))
+    }
+
+    fn parse_text_labels(i: &str) -> IResult<&str, Self> {
+        let (i, labels) = separated_nonempty_list(tag("_"), num)(i)?;
+        Ok((i, Self::from_labels(labels)))
+    }
+}
+
+impl<O: SetWeightOption> SerializableSemiring for SetWeight<O> {
+    fn weight_type() -> String {
+        match O::set_type() {
+            SetType::SetIntersectUnion => "intersect_union_set".to_string(),
+            SetType::SetUnionIntersect => "union_intersect_set".to_string(),
+        }
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, n) = le_i32(i)?;
+        let (i, labels) = count(le_i32, n as usize)(i)?;
+        // Check for the universal set
+        let weight = if labels == vec![-1] {
+            Self::universal()
+        } else {
+            Self::from_labels(labels.into_iter().map(|e| e as Label))
+        };
+        Ok((i, weight))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        match &self.value {
+            SetWeightVariant::Universal => {
+                write_bin_i32(file, 1)?;
+                write_bin_i32(file, -1)?;
+            }
+            SetWeightVariant::Labels(labels) => {
+                write_bin_i32(file, labels.len() as i32)?;
+                for label in labels.iter() {
+                    write_bin_i32(file, *label as i32)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        alt((
+            Self::parse_text_universal,
+            Self::parse_text_empty,
+            Self::parse_text_labels,
+        ))(i)
+    }
+}
+
+test_semiring_serializable!(
+    tests_set_weight_serializable,
+    SetWeight::<SetIntersectUnion>,
+    SetWeight::<SetIntersectUnion>::one() SetWeight::<SetIntersectUnion>::zero()
+    SetWeight::<SetIntersectUnion>::from_labels(vec![5, 2, 8])
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type IntersectUnion = SetWeight<SetIntersectUnion>;
+    type UnionIntersect = SetWeight<SetUnionIntersect>;
+
+    #[test]
+    fn test_set_weight_intersect_union() -> Result<()> {
+        let w1 = IntersectUnion::from_labels(vec![1, 2, 3]);
+        let w2 = IntersectUnion::from_labels(vec![3, 4]);
+
+        assert_eq!(w1.plus(&w2)?, IntersectUnion::from_labels(vec![3]));
+        assert_eq!(
+            w1.times(&w2)?,
+            IntersectUnion::from_labels(vec![1, 2, 3, 4])
+        );
+        assert_eq!(w1.plus(IntersectUnion::zero())?, w1);
+        assert_eq!(w1.times(IntersectUnion::one())?, w1);
+        assert_eq!(w1.times(IntersectUnion::zero())?, IntersectUnion::zero());
+        assert_eq!(
+            w1.times(&w2)?.divide(&w2, DivideType::DivideAny)?,
+            IntersectUnion::from_labels(vec![1, 2])
+        );
+        assert!(IntersectUnion::universal()
+            .divide(&w2, DivideType::DivideAny)
+            .is_err());
+        assert_eq!(IntersectUnion::weight_type(), "intersect_union_set");
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_weight_union_intersect() -> Result<()> {
+        let w1 = UnionIntersect::from_labels(vec![1, 2, 3]);
+        let w2 = UnionIntersect::from_labels(vec![3, 4]);
+
+        assert_eq!(w1.plus(&w2)?, UnionIntersect::from_labels(vec![1, 2, 3, 4]));
+        assert_eq!(w1.times(&w2)?, UnionIntersect::from_labels(vec![3]));
+        assert_eq!(w1.plus(UnionIntersect::zero())?, w1);
+        assert_eq!(w1.times(UnionIntersect::one())?, w1);
+        assert_eq!(w1.times(UnionIntersect::zero())?, UnionIntersect::zero());
+        assert!(w1.contains(2));
+        assert!(!w1.contains(4));
+        assert!(UnionIntersect::one().contains(4));
+        assert_eq!(UnionIntersect::weight_type(), "union_intersect_set");
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_weight_text() -> Result<()> {
+        assert_eq!(
+            format!("{}", UnionIntersect::from_labels(vec![3, 1])),
+            "1_3"
+        );
+        assert_eq!(format!("{}", UnionIntersect::zero()), "Empty");
+        assert_eq!(format!("{}", UnionIntersect::one()), "Universal");
+        let (rest, w) = UnionIntersect::parse_text("4_2")
+            .map_err(|e| format_err!("Can't parse weight : {:?}", e))?;
+        assert_eq!(rest, "");
+        assert_eq!(w, UnionIntersect::from_labels(vec![2, 4]));
+        Ok(())
+    }
+}
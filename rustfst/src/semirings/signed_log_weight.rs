@@ -0,0 +1,306 @@
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::f32;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+
+use anyhow::Result;
+use nom::bytes::complete::tag;
+use nom::IResult;
+
+use crate::semirings::{
+    DivideType, LogWeight, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    TropicalWeight, WeaklyDivisibleSemiring, WeightQuantize,
+};
+
+/// Signed log semiring: a log weight with a sign, allowing to represent negative quantities.
+/// Equivalent to OpenFST's `SignedLogWeight`.
+///
+/// The first component is the sign (`1.0` for positive weights and `-1.0` for negative ones) and
+/// the second one is the negated log of the magnitude. The sum of two weights with opposite
+/// signs subtracts their magnitudes.
+#[derive(Clone, Debug, PartialOrd, Copy)]
+pub struct SignedLogWeight {
+    value: (TropicalWeight, LogWeight),
+}
+
+/// log(1 + exp(-x))
+fn ln_pos_exp(x: f32) -> f32 {
+    ((-x).exp()).ln_1p()
+}
+
+/// log(1 - exp(-x))
+fn ln_neg_exp(x: f32) -> f32 {
+    (-((-x).exp())).ln_1p()
+}
+
+impl SignedLogWeight {
+    /// Builds a weight from its sign and the negated log of its magnitude.
+    pub fn from_sign_and_log(positive: bool, log_value: f32) -> Self {
+        let sign = if positive { 1.0 } else { -1.0 };
+        Self::new((TropicalWeight::new(sign), LogWeight::new(log_value)))
+    }
+
+    /// Sign of the weight.
+    pub fn value1(&self) -> &TropicalWeight {
+        &self.value.0
+    }
+
+    /// Negated log of the magnitude of the weight.
+    pub fn value2(&self) -> &LogWeight {
+        &self.value.1
+    }
+
+    /// Returns true if the sign of the weight is positive.
+    pub fn is_positive(&self) -> bool {
+        *self.value.0.value() > 0.0
+    }
+}
+
+impl Semiring for SignedLogWeight {
+    type Type = (TropicalWeight, LogWeight);
+    type ReverseWeight = SignedLogWeight;
+
+    fn zero() -> Self {
+        Self::from_sign_and_log(true, f32::INFINITY)
+    }
+
+    fn one() -> Self {
+        Self::from_sign_and_log(true, 0.0)
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        Self { value }
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        let (s1, s2) = (self.is_positive(), rhs.is_positive());
+        let f1 = *self.value.1.value();
+        let f2 = *rhs.value.1.value();
+        *self = if f1 == f32::INFINITY {
+            *rhs
+        } else if f2 == f32::INFINITY {
+            *self
+        } else if f1 == f2 {
+            if s1 == s2 {
+                Self::from_sign_and_log(s1, f2 - 2f32.ln())
+            } else {
+                Self::zero()
+            }
+        } else if f1 > f2 {
+            if s1 == s2 {
+                Self::from_sign_and_log(s2, f2 - ln_pos_exp(f1 - f2))
+            } else {
+                Self::from_sign_and_log(s2, f2 - ln_neg_exp(f1 - f2))
+            }
+        } else if s1 == s2 {
+            Self::from_sign_and_log(s1, f1 - ln_pos_exp(f2 - f1))
+        } else {
+            Self::from_sign_and_log(s1, f1 - ln_neg_exp(f2 - f1))
+        };
+        Ok(())
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        let rhs = rhs.borrow();
+        let positive = self.is_positive() == rhs.is_positive();
+        self.value.1.times_assign(rhs.value.1)?;
+        self.value.0 = TropicalWeight::new(if positive { 1.0 } else { -1.0 });
+        Ok(())
+    }
+
+    fn value(&self) -> &Self::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> Self::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.value = value
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        Ok(*self)
+    }
+
+    fn properties() -> SemiringProperties {
+        SemiringProperties::LEFT_SEMIRING
+            | SemiringProperties::RIGHT_SEMIRING
+            | SemiringProperties::COMMUTATIVE
+    }
+}
+
+impl ReverseBack<SignedLogWeight> for SignedLogWeight {
+    fn reverse_back(&self) -> Result<SignedLogWeight> {
+        Ok(*self)
+    }
+}
+
+impl AsRef<SignedLogWeight> for SignedLogWeight {
+    fn as_ref(&self) -> &SignedLogWeight {
+        self
+    }
+}
+
+impl WeaklyDivisibleSemiring for SignedLogWeight {
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        ensure!(!rhs.value.1.is_zero(), "Division by zero");
+        if self.value.1.is_zero() {
+            *self = Self::zero();
+            return Ok(());
+        }
+        let positive = self.is_positive() == rhs.is_positive();
+        self.value.1.divide_assign(&rhs.value.1, divide_type)?;
+        self.value.0 = TropicalWeight::new(if positive { 1.0 } else { -1.0 });
+        Ok(())
+    }
+}
+
+impl WeightQuantize for SignedLogWeight {
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.value.1.quantize_assign(delta)
+    }
+}
+
+/// Zero has two representations (positive and negative) which are equal.
+impl PartialEq for SignedLogWeight {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_positive() == other.is_positive() {
+            self.value.1 == other.value.1
+        } else {
+            self.value.1.is_zero() && other.value.1.is_zero()
+        }
+    }
+}
+
+impl Eq for SignedLogWeight {}
+
+impl Hash for SignedLogWeight {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if !self.value.1.is_zero() {
+            self.is_positive().hash(state);
+        }
+        self.value.1.hash(state);
+    }
+}
+
+impl fmt::Display for SignedLogWeight {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.value.0, self.value.1)
+    }
+}
+
+impl SerializableSemiring for SignedLogWeight {
+    fn weight_type() -> String {
+        "signed_log".to_string()
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, sign) = TropicalWeight::parse_binary(i)?;
+        let (i, weight) = LogWeight::parse_binary(i)?;
+        Ok((i, Self::new((sign, weight))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value.0.write_binary(file)?;
+        self.value.1.write_binary(file)
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, sign) = TropicalWeight::parse_text(i)?;
+        let (i, _) = tag(",")(i)?;
+        let (i, weight) = LogWeight::parse_text(i)?;
+        Ok((i, Self::new((sign, weight))))
+    }
+}
+
+impl From<LogWeight> for SignedLogWeight {
+    fn from(weight: LogWeight) -> Self {
+        Self::new((TropicalWeight::new(1.0), weight))
+    }
+}
+
+/// Fails if the weight is negative.
+impl TryFrom<SignedLogWeight> for LogWeight {
+    type Error = anyhow::Error;
+
+    fn try_from(weight: SignedLogWeight) -> Result<Self> {
+        ensure!(
+            weight.is_positive() || weight.value.1.is_zero(),
+            "Can't convert the negative weight {} to LogWeight",
+            weight
+        );
+        Ok(weight.value.1)
+    }
+}
+
+test_semiring_serializable!(
+    tests_signed_log_weight_serializable,
+    SignedLogWeight,
+    SignedLogWeight::one() SignedLogWeight::zero() SignedLogWeight::from_sign_and_log(true, 0.3)
+    SignedLogWeight::from_sign_and_log(false, 1.2) SignedLogWeight::from_sign_and_log(false, -0.5)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed(v: f32) -> SignedLogWeight {
+        SignedLogWeight::from_sign_and_log(v >= 0.0, -v.abs().ln())
+    }
+
+    fn real(w: &SignedLogWeight) -> f32 {
+        let magnitude = (-w.value2().value()).exp();
+        if w.is_positive() {
+            magnitude
+        } else {
+            -magnitude
+        }
+    }
+
+    #[test]
+    fn test_signed_log_weight() -> Result<()> {
+        let cases = [
+            (3.0, 2.0),
+            (3.0, -2.0),
+            (-3.0, 2.0),
+            (2.0, -3.0),
+            (-2.0, -3.0),
+        ];
+        for (a, b) in cases.iter() {
+            let sum = signed(*a).plus(signed(*b))?;
+            assert!((real(&sum) - (a + b)).abs() < 1e-5, "{} + {}", a, b);
+            let product = signed(*a).times(signed(*b))?;
+            assert!((real(&product) - a * b).abs() < 1e-4, "{} * {}", a, b);
+            let quotient = signed(*a).divide(&signed(*b), DivideType::DivideAny)?;
+            assert!((real(&quotient) - a / b).abs() < 1e-5, "{} / {}", a, b);
+        }
+
+        // Cancellation.
+        assert_eq!(signed(2.0).plus(signed(-2.0))?, SignedLogWeight::zero());
+        assert_eq!(
+            SignedLogWeight::from_sign_and_log(false, f32::INFINITY),
+            SignedLogWeight::zero()
+        );
+        assert_eq!(signed(2.0).plus(SignedLogWeight::zero())?, signed(2.0));
+        assert_eq!(signed(-2.0).times(SignedLogWeight::one())?, signed(-2.0));
+        assert!(signed(-2.0)
+            .divide(&SignedLogWeight::zero(), DivideType::DivideAny)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_log_weight_conversions() -> Result<()> {
+        let w = SignedLogWeight::from(LogWeight::new(0.5));
+        assert_eq!(w, SignedLogWeight::from_sign_and_log(true, 0.5));
+        assert_eq!(LogWeight::try_from(w)?, LogWeight::new(0.5));
+        assert!(LogWeight::try_from(signed(-1.0)).is_err());
+        assert_eq!(SignedLogWeight::weight_type(), "signed_log");
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Write;
+
+use anyhow::Result;
+use itertools::{EitherOrBoth, Itertools};
+use nom::bytes::complete::tag;
+use nom::combinator::map_res;
+use nom::error::ErrorKind;
+use nom::multi::{count, many0};
+use nom::number::complete::le_i32;
+use nom::sequence::{preceded, separated_pair};
+use nom::IResult;
+
+use crate::parsers::bin_fst::utils_serialization::write_bin_i32;
+use crate::parsers::nom_utils::num;
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, SerializableSemiring,
+    WeaklyDivisibleSemiring, WeightQuantize,
+};
+
+/// Sparse cartesian power semiring: W ^ n with n unbounded.
+///
+/// The components are indexed by a feature index. Only the components differing from the
+/// default value are stored, all the other ones being implicitly equal to it. All the
+/// operations are done element-wise, the default value included. Equivalent to OpenFST's
+/// `SparsePowerWeight`.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::semirings::{Semiring, SparsePowerWeight, TropicalWeight};
+/// # fn main() -> Result<()> {
+/// let mut w1 = SparsePowerWeight::<TropicalWeight>::one();
+/// w1.set_value_at(3, TropicalWeight::new(2.0));
+/// let mut w2 = SparsePowerWeight::<TropicalWeight>::one();
+/// w2.set_value_at(1000, TropicalWeight::new(1.5));
+///
+/// let w3 = w1.times(&w2)?;
+/// assert_eq!(w3.value_at(3), &TropicalWeight::new(2.0));
+/// assert_eq!(w3.value_at(1000), &TropicalWeight::new(1.5));
+/// assert_eq!(w3.value_at(42), &TropicalWeight::one());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Hash)]
+pub struct SparsePowerWeight<W>
+where
+    W: Semiring,
+{
+    /// The default value and the components differing from it.
+    pub(crate) value: (W, BTreeMap<usize, W>),
+}
+
+impl<W: Semiring> SparsePowerWeight<W> {
+    /// Value of the component `index`.
+    pub fn value_at(&self, index: usize) -> &W {
+        self.value.1.get(&index).unwrap_or(&self.value.0)
+    }
+
+    /// Sets the value of the component `index`.
+    pub fn set_value_at(&mut self, index: usize, weight: W) {
+        if weight == self.value.0 {
+            self.value.1.remove(&index);
+        } else {
+            self.value.1.insert(index, weight);
+        }
+    }
+
+    /// Value of all the components not explicitly stored.
+    pub fn default_value(&self) -> &W {
+        &self.value.0
+    }
+
+    /// Iterates over the components differing from the default value, in increasing index order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &W)> {
+        self.value.1.iter().map(|(index, weight)| (*index, weight))
+    }
+
+    /// Number of components differing from the default value.
+    pub fn len(&self) -> usize {
+        self.value.1.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.1.is_empty()
+    }
+
+    /// Applies `op` element-wise on all the components of `self` and `rhs`, walking the stored
+    /// components of both weights in increasing index order.
+    fn combine<F>(&mut self, rhs: &Self, op: F) -> Result<()>
+    where
+        F: Fn(&mut W, &W) -> Result<()>,
+    {
+        let mut default = self.value.0.clone();
+        op(&mut default, &rhs.value.0)?;
+        let mut weights = BTreeMap::new();
+        let entries = self
+            .value
+            .1
+            .iter()
+            .merge_join_by(rhs.value.1.iter(), |(i1, _), (i2, _)| i1.cmp(i2));
+        for entry in entries {
+            let (index, mut weight, w_rhs) = match entry {
+                EitherOrBoth::Both((index, w), (_, w_rhs)) => (index, w.clone(), w_rhs),
+                EitherOrBoth::Left((index, w)) => (index, w.clone(), &rhs.value.0),
+                EitherOrBoth::Right((index, w_rhs)) => (index, self.value.0.clone(), w_rhs),
+            };
+            op(&mut weight, w_rhs)?;
+            if weight != default {
+                weights.insert(*index, weight);
+            }
+        }
+        self.value = (default, weights);
+        Ok(())
+    }
+
+    fn remove_defaults(&mut self) {
+        let default = &self.value.0;
+        self.value.1.retain(|_, weight| weight != default);
+    }
+}
+
+impl<W: Semiring> AsRef<Self> for SparsePowerWeight<W> {
+    fn as_ref(&self) -> &SparsePowerWeight<W> {
+        self
+    }
+}
+
+impl<W> Semiring for SparsePowerWeight<W>
+where
+    W: Semiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+{
+    type Type = (W, BTreeMap<usize, W>);
+    type ReverseWeight = SparsePowerWeight<W::ReverseWeight>;
+
+    fn zero() -> Self {
+        Self::new((W::zero(), BTreeMap::new()))
+    }
+
+    fn one() -> Self {
+        Self::new((W::one(), BTreeMap::new()))
+    }
+
+    fn new(value: <Self as Semiring>::Type) -> Self {
+        let mut weight = Self { value };
+        weight.remove_defaults();
+        weight
+    }
+
+    fn plus_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.combine(rhs.borrow(), |w, w_rhs| w.plus_assign(w_rhs))
+    }
+
+    fn times_assign<P: Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+        self.combine(rhs.borrow(), |w, w_rhs| w.times_assign(w_rhs))
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.value
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.value
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        *self = Self::new(value);
+    }
+
+    fn reverse(&self) -> Result<Self::ReverseWeight> {
+        let weights = self
+            .value
+            .1
+            .iter()
+            .map(|(index, w)| Ok((*index, w.reverse()?)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        Ok(SparsePowerWeight::new((self.value.0.reverse()?, weights)))
+    }
+
+    fn properties() -> SemiringProperties {
+        W::properties()
+            & (SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE
+                | SemiringProperties::IDEMPOTENT)
+    }
+}
+
+impl<W> ReverseBack<SparsePowerWeight<W>> for SparsePowerWeight<W::ReverseWeight>
+where
+    W: Semiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+{
+    fn reverse_back(&self) -> Result<SparsePowerWeight<W>> {
+        let weights = self
+            .value
+            .1
+            .iter()
+            .map(|(index, w)| Ok((*index, w.reverse_back()?)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        Ok(SparsePowerWeight::new((
+            self.value.0.reverse_back()?,
+            weights,
+        )))
+    }
+}
+
+impl<W> WeaklyDivisibleSemiring for SparsePowerWeight<W>
+where
+    W: WeaklyDivisibleSemiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+{
+    fn divide_assign(&mut self, rhs: &Self, divide_type: DivideType) -> Result<()> {
+        self.combine(rhs, |w, w_rhs| w.divide_assign(w_rhs, divide_type))
+    }
+}
+
+impl<W> WeightQuantize for SparsePowerWeight<W>
+where
+    W: WeightQuantize,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+{
+    fn quantize_assign(&mut self, delta: f32) -> Result<()> {
+        self.value.0.quantize_assign(delta)?;
+        for w in self.value.1.values_mut() {
+            w.quantize_assign(delta)?;
+        }
+        self.remove_defaults();
+        Ok(())
+    }
+}
+
+/// Written `default,index_1,weight_1,index_2,weight_2,...` as in OpenFST.
+impl<W> fmt::Display for SparsePowerWeight<W>
+where
+    W: SerializableSemiring,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value.0)?;
+        for (index, weight) in self.value.1.iter() {
+            write!(f, ",{},{}", index, weight)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W> SerializableSemiring for SparsePowerWeight<W>
+where
+    W: SerializableSemiring,
+    W::ReverseWeight: Semiring<ReverseWeight = W>,
+{
+    fn weight_type() -> String {
+        format!("{}_^n", W::weight_type())
+    }
+
+    fn parse_binary(i: &[u8]) -> IResult<&[u8], Self> {
+        let (i, default) = W::parse_binary(i)?;
+        let (i, n) = map_res(le_i32, usize::try_from)(i)?;
+        // Each component takes at least 4 bytes, checked before allocating them.
+        if n > i.len() / 4 {
+            return Err(nom::Err::Error((i, ErrorKind::Verify)));
+        }
+        let (i, weights) = count(
+            |i| {
+                let (i, index) = map_res(le_i32, usize::try_from)(i)?;
+                let (i, weight) = W::parse_binary(i)?;
+                Ok((i, (index, weight)))
+            },
+            n,
+        )(i)?;
+        Ok((i, Self::new((default, weights.into_iter().collect()))))
+    }
+
+    fn write_binary<F: Write>(&self, file: &mut F) -> Result<()> {
+        self.value.0.write_binary(file)?;
+        write_bin_i32(file, i32::try_from(self.value.1.len())?)?;
+        for (index, weight) in self.value.1.iter() {
+            write_bin_i32(file, i32::try_from(*index)?)?;
+            weight.write_binary(file)?;
+        }
+        Ok(())
+    }
+
+    fn parse_text(i: &str) -> IResult<&str, Self> {
+        let (i, default) = W::parse_text(i)?;
+        let (i, weights) = many0(preceded(
+            tag(","),
+            separated_pair(num, tag(","), W::parse_text),
+        ))(i)?;
+        Ok((i, Self::new((default, weights.into_iter().collect()))))
+    }
+}
+
+#[cfg(test)]
+use crate::semirings::{LogWeight, TropicalWeight};
+
+test_semiring_serializable!(
+    tests_sparse_power_weight_serializable,
+    SparsePowerWeight::<TropicalWeight>,
+    SparsePowerWeight::<TropicalWeight>::one()
+    SparsePowerWeight::<TropicalWeight>::zero()
+    SparsePowerWeight::<TropicalWeight>::new((TropicalWeight::one(), vec![(2, TropicalWeight::new(0.5)), (1000, TropicalWeight::zero())].into_iter().collect()))
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sparse(default: f32, weights: &[(usize, f32)]) -> SparsePowerWeight<LogWeight> {
+        SparsePowerWeight::new((
+            LogWeight::new(default),
+            weights
+                .iter()
+                .map(|(index, w)| (*index, LogWeight::new(*w)))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_sparse_power_weight() -> Result<()> {
+        let w1 = sparse(0.0, &[(1, 1.0), (5, 2.0)]);
+        let w2 = sparse(0.0, &[(5, 0.5), (7, 3.0)]);
+
+        assert_eq!(w1.times(&w2)?, sparse(0.0, &[(1, 1.0), (5, 2.5), (7, 3.0)]));
+        assert_eq!(
+            w1.times(&w2)?.divide(&w2, DivideType::DivideAny)?,
+            sparse(0.0, &[(1, 1.0), (5, 2.0)])
+        );
+        assert_eq!(
+            w1.plus(&w2)?.value_at(1),
+            &LogWeight::new(1.0).plus(LogWeight::new(0.0))?
+        );
+        assert_eq!(w1.plus(SparsePowerWeight::zero())?, w1);
+        assert_eq!(w1.times(SparsePowerWeight::one())?, w1);
+        assert_eq!(
+            w1.times(SparsePowerWeight::zero())?,
+            SparsePowerWeight::zero()
+        );
+        assert_eq!(w1.reverse()?.reverse_back()?, w1);
+        assert_eq!(
+            sparse(0.0, &[(3, 0.1001), (4, 0.0001)]).quantize(0.01)?,
+            sparse(0.0, &[(3, 0.1)])
+        );
+
+        // Components equal to the default value are not stored.
+        assert_eq!(sparse(1.0, &[(3, 1.0)]).len(), 0);
+        let mut w = w1.clone();
+        w.set_value_at(1, LogWeight::one());
+        assert_eq!(
+            w.iter().collect::<Vec<_>>(),
+            vec![(5, &LogWeight::new(2.0))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_power_weight_serialization() -> Result<()> {
+        assert_eq!(SparsePowerWeight::<LogWeight>::weight_type(), "log_^n");
+        let w = sparse(0.0, &[(2, 1.5), (10, 3.0)]);
+        assert_eq!(format!("{}", w), "0,2,1.5,10,3");
+
+        let (rest, w2) = SparsePowerWeight::<LogWeight>::parse_text("0,2,1.5,10,3")
+            .map_err(|e| format_err!("Can't parse weight : {:?}", e))?;
+        assert_eq!(rest, "");
+        assert_eq!(w2, w);
+
+        let mut serialization = vec![];
+        SparsePowerWeight::<TropicalWeight>::one().write_binary(&mut serialization)?;
+        let mut expected = 0.0f32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&0i32.to_le_bytes());
+        assert_eq!(serialization, expected);
+
+        // Indices are written on 32 bits.
+        let mut w = SparsePowerWeight::<TropicalWeight>::one();
+        w.set_value_at(i32::MAX as usize + 1, TropicalWeight::new(1.0));
+        assert!(w.write_binary(&mut vec![]).is_err());
+
+        // Negative sizes and indices are rejected.
+        let binary = |n: i32, components: &[(i32, f32)]| {
+            let mut data = 0.0f32.to_le_bytes().to_vec();
+            data.extend_from_slice(&n.to_le_bytes());
+            for (index, value) in components {
+                data.extend_from_slice(&index.to_le_bytes());
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            data
+        };
+        let parse = |data: &[u8]| SparsePowerWeight::<TropicalWeight>::parse_binary(data).is_ok();
+        let mut w = SparsePowerWeight::<TropicalWeight>::one();
+        w.set_value_at(3, TropicalWeight::new(2.0));
+        let (_, w2) = SparsePowerWeight::<TropicalWeight>::parse_binary(&binary(1, &[(3, 2.0)]))
+            .map_err(|e| format_err!("Can't parse weight : {:?}", e))?;
+        assert_eq!(w2, w);
+        assert!(!parse(&binary(1, &[(-3, 2.0)])));
+        assert!(!parse(&binary(-1, &[])));
+        assert!(!parse(&binary(i32::MAX, &[(3, 2.0)])));
+        Ok(())
+    }
+}
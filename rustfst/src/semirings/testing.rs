@@ -0,0 +1,382 @@
+//! Utilities to check that a semiring satisfies the axioms it claims in its
+//! `SemiringProperties`. Useful when defining custom weights.
+//!
+//! The checks can be run on randomly generated weights with `check_semiring_laws` or be called
+//! on specific weights with `check_semiring_laws_on`, for instance from a `proptest!` block.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use rustfst::semirings::testing::{check_division_laws, check_semiring_laws};
+//! # use rustfst::semirings::{Semiring, TropicalWeight};
+//! # fn main() -> Result<()> {
+//! let mut i = 0;
+//! let mut generator = || {
+//!     i += 1;
+//!     TropicalWeight::new((i % 7) as f32)
+//! };
+//! check_semiring_laws(&mut generator)?;
+//! check_division_laws(&mut generator)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Result;
+
+use crate::semirings::{
+    DivideType, ReverseBack, Semiring, SemiringProperties, WeaklyDivisibleSemiring,
+};
+
+/// Number of weight triples tested by `check_semiring_laws` and `check_division_laws`.
+pub const NUM_ITERATIONS: usize = 100;
+
+/// Checks the semiring axioms on `NUM_ITERATIONS` triples of weights returned by `generator`.
+/// See `check_semiring_laws_on` for the list of the checks.
+pub fn check_semiring_laws<W, G>(mut generator: G) -> Result<()>
+where
+    W: Semiring,
+    G: FnMut() -> W,
+{
+    for _ in 0..NUM_ITERATIONS {
+        let w1 = generator();
+        let w2 = generator();
+        let w3 = generator();
+        check_semiring_laws_on(&w1, &w2, &w3)?;
+    }
+    Ok(())
+}
+
+/// Checks the semiring axioms on the three weights :
+/// - Plus is associative and commutative and `zero` is its identity.
+/// - Times is associative, `one` is its identity and `zero` is its annihilator.
+/// - Times distributes over Plus on the left (resp. right) if the semiring claims the
+///   `LEFT_SEMIRING` (resp. `RIGHT_SEMIRING`) property.
+/// - Times is commutative if the semiring claims the `COMMUTATIVE` property.
+/// - Plus is idempotent if the semiring claims the `IDEMPOTENT` property.
+/// - Plus returns one of its arguments if the semiring claims the `PATH` property.
+/// - Reversing a weight and reversing it back returns the same weight.
+pub fn check_semiring_laws_on<W: Semiring>(w1: &W, w2: &W, w3: &W) -> Result<()> {
+    let zero = W::zero();
+    let one = W::one();
+    let properties = W::properties();
+
+    // Plus
+    let lhs = w1.plus(w2)?.plus(w3)?;
+    let rhs = w1.plus(w2.plus(w3)?)?;
+    ensure!(
+        lhs == rhs,
+        "Plus is not associative : ({:?} + {:?}) + {:?} = {:?} but {:?} + ({:?} + {:?}) = {:?}",
+        w1,
+        w2,
+        w3,
+        lhs,
+        w1,
+        w2,
+        w3,
+        rhs
+    );
+    ensure!(
+        w1.plus(w2)? == w2.plus(w1)?,
+        "Plus is not commutative for {:?} and {:?}",
+        w1,
+        w2
+    );
+    ensure!(
+        w1.plus(&zero)? == *w1 && zero.plus(w1)? == *w1,
+        "Zero is not the identity of Plus for {:?}",
+        w1
+    );
+
+    // Times
+    let lhs = w1.times(w2)?.times(w3)?;
+    let rhs = w1.times(w2.times(w3)?)?;
+    ensure!(
+        lhs == rhs,
+        "Times is not associative : ({:?} * {:?}) * {:?} = {:?} but {:?} * ({:?} * {:?}) = {:?}",
+        w1,
+        w2,
+        w3,
+        lhs,
+        w1,
+        w2,
+        w3,
+        rhs
+    );
+    ensure!(
+        w1.times(&one)? == *w1 && one.times(w1)? == *w1,
+        "One is not the identity of Times for {:?}",
+        w1
+    );
+    ensure!(
+        w1.times(&zero)? == zero && zero.times(w1)? == zero,
+        "Zero is not an annihilator of Times for {:?}",
+        w1
+    );
+
+    // Properties
+    if properties.contains(SemiringProperties::LEFT_SEMIRING) {
+        let lhs = w1.times(w2.plus(w3)?)?;
+        let rhs = w1.times(w2)?.plus(w1.times(w3)?)?;
+        ensure!(
+            lhs == rhs,
+            "Times is not left distributive over Plus for {:?}, {:?} and {:?}",
+            w1,
+            w2,
+            w3
+        );
+    }
+    if properties.contains(SemiringProperties::RIGHT_SEMIRING) {
+        let lhs = w1.plus(w2)?.times(w3)?;
+        let rhs = w1.times(w3)?.plus(w2.times(w3)?)?;
+        ensure!(
+            lhs == rhs,
+            "Times is not right distributive over Plus for {:?}, {:?} and {:?}",
+            w1,
+            w2,
+            w3
+        );
+    }
+    if properties.contains(SemiringProperties::COMMUTATIVE) {
+        ensure!(
+            w1.times(w2)? == w2.times(w1)?,
+            "Times is not commutative for {:?} and {:?}",
+            w1,
+            w2
+        );
+    }
+    if properties.contains(SemiringProperties::IDEMPOTENT) {
+        ensure!(w1.plus(w1)? == *w1, "Plus is not idempotent for {:?}", w1);
+    }
+    if properties.contains(SemiringProperties::PATH) {
+        let sum = w1.plus(w2)?;
+        ensure!(
+            sum == *w1 || sum == *w2,
+            "Plus doesn't satisfy the path property for {:?} and {:?}",
+            w1,
+            w2
+        );
+    }
+
+    // Reverse
+    ensure!(
+        w1.reverse()?.reverse_back()? == *w1,
+        "Reversing {:?} back and forth doesn't return the same weight",
+        w1
+    );
+
+    Ok(())
+}
+
+/// Checks the division on `NUM_ITERATIONS` pairs of weights returned by `generator`.
+/// See `check_division_laws_on` for the list of the checks.
+pub fn check_division_laws<W, G>(mut generator: G) -> Result<()>
+where
+    W: WeaklyDivisibleSemiring,
+    G: FnMut() -> W,
+{
+    for _ in 0..NUM_ITERATIONS {
+        let w1 = generator();
+        let w2 = generator();
+        check_division_laws_on(&w1, &w2)?;
+    }
+    Ok(())
+}
+
+/// Checks that the division is the inverse of Times for the two weights, i.e that
+/// `w1 * ((w1 * w2) / w1) = w1 * w2` for the left division and
+/// `((w1 * w2) / w2) * w2 = w1 * w2` for the right division, according to the
+/// `LEFT_SEMIRING`, `RIGHT_SEMIRING` and `COMMUTATIVE` properties of the semiring. Divisions by
+/// zero are not checked.
+pub fn check_division_laws_on<W: WeaklyDivisibleSemiring>(w1: &W, w2: &W) -> Result<()> {
+    let properties = W::properties();
+    let product = w1.times(w2)?;
+
+    if properties.contains(SemiringProperties::LEFT_SEMIRING) && !w1.is_zero() {
+        let quotient = product.divide(w1, DivideType::DivideLeft)?;
+        ensure!(
+            w1.times(&quotient)? == product,
+            "Left division is not the inverse of Times for {:?} and {:?}",
+            w1,
+            w2
+        );
+    }
+    if properties.contains(SemiringProperties::RIGHT_SEMIRING) && !w2.is_zero() {
+        let quotient = product.divide(w2, DivideType::DivideRight)?;
+        ensure!(
+            quotient.times(w2)? == product,
+            "Right division is not the inverse of Times for {:?} and {:?}",
+            w1,
+            w2
+        );
+    }
+    if properties.contains(SemiringProperties::COMMUTATIVE) && !w2.is_zero() {
+        let quotient = product.divide(w2, DivideType::DivideAny)?;
+        ensure!(
+            quotient.times(w2)? == product,
+            "Division is not the inverse of Times for {:?} and {:?}",
+            w1,
+            w2
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    use crate::semirings::{
+        IntegerWeight, LogWeight, MaxTropicalWeight, MinMaxWeight, ProductWeight,
+        SetUnionIntersect, SetWeight, TropicalWeight,
+    };
+
+    fn cycling_generator<W: Clone>(weights: Vec<W>) -> impl FnMut() -> W {
+        let mut idx = 0;
+        move || {
+            idx = (idx + 1) % weights.len();
+            weights[idx].clone()
+        }
+    }
+
+    #[test]
+    fn test_check_semiring_laws_float_weights() -> Result<()> {
+        let values = [0.0, 0.5, 1.0, 2.0, 3.5, f32::INFINITY];
+        let log_weights: Vec<_> = values.iter().map(|v| LogWeight::new(*v)).collect();
+        check_semiring_laws(cycling_generator(log_weights.clone()))?;
+        check_division_laws(cycling_generator(log_weights))?;
+
+        let product_weights: Vec<ProductWeight<TropicalWeight, LogWeight>> = values
+            .iter()
+            .zip(values.iter().rev())
+            .map(|(v1, v2)| ProductWeight::new((TropicalWeight::new(*v1), LogWeight::new(*v2))))
+            .collect();
+        check_semiring_laws(cycling_generator(product_weights))?;
+        Ok(())
+    }
+
+    /// Integer weight wrongly claiming to be idempotent.
+    #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Hash)]
+    struct FakeIdempotentWeight(IntegerWeight);
+
+    impl Semiring for FakeIdempotentWeight {
+        type Type = i32;
+        type ReverseWeight = FakeIdempotentWeight;
+
+        fn zero() -> Self {
+            FakeIdempotentWeight(IntegerWeight::zero())
+        }
+
+        fn one() -> Self {
+            FakeIdempotentWeight(IntegerWeight::one())
+        }
+
+        fn new(value: i32) -> Self {
+            FakeIdempotentWeight(IntegerWeight::new(value))
+        }
+
+        fn plus_assign<P: std::borrow::Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+            self.0.plus_assign(rhs.borrow().0)
+        }
+
+        fn times_assign<P: std::borrow::Borrow<Self>>(&mut self, rhs: P) -> Result<()> {
+            self.0.times_assign(rhs.borrow().0)
+        }
+
+        fn value(&self) -> &i32 {
+            self.0.value()
+        }
+
+        fn take_value(self) -> i32 {
+            self.0.take_value()
+        }
+
+        fn set_value(&mut self, value: i32) {
+            self.0.set_value(value)
+        }
+
+        fn reverse(&self) -> Result<Self::ReverseWeight> {
+            Ok(self.clone())
+        }
+
+        fn properties() -> SemiringProperties {
+            IntegerWeight::properties() | SemiringProperties::IDEMPOTENT
+        }
+    }
+
+    impl ReverseBack<FakeIdempotentWeight> for FakeIdempotentWeight {
+        fn reverse_back(&self) -> Result<FakeIdempotentWeight> {
+            Ok(self.clone())
+        }
+    }
+
+    #[test]
+    fn test_check_semiring_laws_detects_errors() {
+        let res = check_semiring_laws_on(
+            &FakeIdempotentWeight::new(2),
+            &FakeIdempotentWeight::new(3),
+            &FakeIdempotentWeight::new(4),
+        );
+        assert!(res.is_err());
+    }
+
+    fn proptest_small_float() -> impl Strategy<Value = f32> {
+        prop_oneof![Just(f32::INFINITY), (-10..10).prop_map(|e| e as f32)]
+    }
+
+    proptest! {
+        #[test]
+        fn test_check_semiring_laws_tropical_proptest(
+            v1 in proptest_small_float(), v2 in proptest_small_float(), v3 in proptest_small_float()
+        ) {
+            let (w1, w2, w3) = (TropicalWeight::new(v1), TropicalWeight::new(v2), TropicalWeight::new(v3));
+            check_semiring_laws_on(&w1, &w2, &w3).unwrap();
+            check_division_laws_on(&w1, &w2).unwrap();
+        }
+
+        #[test]
+        fn test_check_semiring_laws_min_max_proptest(
+            v1 in proptest_small_float(), v2 in proptest_small_float(), v3 in proptest_small_float()
+        ) {
+            let (w1, w2, w3) = (MinMaxWeight::new(v1), MinMaxWeight::new(v2), MinMaxWeight::new(v3));
+            check_semiring_laws_on(&w1, &w2, &w3).unwrap();
+            check_division_laws_on(&w1, &w2).unwrap();
+        }
+
+        #[test]
+        fn test_check_semiring_laws_max_tropical_proptest(
+            v1 in -10..10, v2 in -10..10, v3 in -10..10
+        ) {
+            let (w1, w2, w3) = (
+                MaxTropicalWeight::new(v1 as f32),
+                MaxTropicalWeight::new(v2 as f32),
+                MaxTropicalWeight::new(v3 as f32),
+            );
+            check_semiring_laws_on(&w1, &w2, &w3).unwrap();
+            check_division_laws_on(&w1, &w2).unwrap();
+        }
+
+        #[test]
+        fn test_check_semiring_laws_integer_proptest(
+            v1 in -100..100, v2 in -100..100, v3 in -100..100
+        ) {
+            let (w1, w2, w3) = (IntegerWeight::new(v1), IntegerWeight::new(v2), IntegerWeight::new(v3));
+            check_semiring_laws_on(&w1, &w2, &w3).unwrap();
+        }
+
+        #[test]
+        fn test_check_semiring_laws_set_proptest(
+            l1 in proptest::collection::vec(0..10usize, 0..5),
+            l2 in proptest::collection::vec(0..10usize, 0..5),
+            l3 in proptest::collection::vec(0..10usize, 0..5)
+        ) {
+            let (w1, w2, w3): (SetWeight<SetUnionIntersect>, _, _) = (
+                SetWeight::from_labels(l1), SetWeight::from_labels(l2), SetWeight::from_labels(l3)
+            );
+            check_semiring_laws_on(&w1, &w2, &w3).unwrap();
+            check_division_laws_on(&w1, &w2).unwrap();
+        }
+    }
+}
@@ -5,10 +5,12 @@ use std::fs::{read, File};
 use std::io::{BufWriter, LineWriter, Write};
 use std::path::Path;
 
-use anyhow::{Result, Context};
+use anyhow::{Context, Result};
 use itertools::Itertools;
 
-use crate::parsers::bin_symt::nom_parser::{parse_symbol_table_bin, write_bin_symt};
+use crate::parsers::bin_symt::nom_parser::{
+    parse_symbol_table_bin, write_bin_symt, SYMBOL_TABLE_MAGIC_NUMBER,
+};
 use crate::parsers::text_symt::parsed_text_symt::ParsedTextSymt;
 use crate::{Label, Symbol, EPS_SYMBOL};
 
@@ -265,7 +267,18 @@ impl SymbolTable {
             )
         })?;
 
-        let (_, symt) = parse_symbol_table_bin(&data)
+        Self::from_binary_bytes(&data)
+    }
+
+    /// Returns whether a slice of bytes starts with the magic number of the binary format.
+    pub fn is_binary(data: &[u8]) -> bool {
+        data.get(..4)
+            .is_some_and(|b| b == SYMBOL_TABLE_MAGIC_NUMBER.to_le_bytes())
+    }
+
+    /// Parses a symbol table in binary format from a slice of bytes.
+    pub fn from_binary_bytes(data: &[u8]) -> Result<Self> {
+        let (_, symt) = parse_symbol_table_bin(data)
             .map_err(|e| format_err!("Error while parsing binary SymbolTable : {:?}", e))?;
 
         Ok(symt)
@@ -275,11 +288,17 @@ impl SymbolTable {
         let buffer = File::create(path_bin_symt.as_ref())?;
         let mut writer = BufWriter::new(LineWriter::new(buffer));
 
-        write_bin_symt(&mut writer, &self)?;
+        self.write_binary(&mut writer)?;
+        writer.flush()?;
 
         Ok(())
     }
 
+    /// Writes the symbol table in binary format.
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> Result<()> {
+        write_bin_symt(writer, self)
+    }
+
     /// Writes the text_fst representation of the symbol table into a String.
     pub fn text(&self) -> Result<String> {
         let buffer = Vec::<u8>::new();
@@ -355,6 +374,23 @@ mod tests {
         assert_eq!(symt.contains_label(3), false);
     }
 
+    #[test]
+    fn test_symt_binary_round_trip() -> Result<()> {
+        let mut symt = SymbolTable::new();
+        symt.add_symbol("a");
+        symt.add_symbol("b");
+
+        let mut data = vec![];
+        symt.write_binary(&mut data)?;
+
+        assert_eq!(SymbolTable::from_binary_bytes(&data)?, symt);
+        assert!(SymbolTable::from_binary_bytes(symt.text()?.as_bytes()).is_err());
+        assert!(SymbolTable::is_binary(&data));
+        assert!(!SymbolTable::is_binary(symt.text()?.as_bytes()));
+        assert!(!SymbolTable::is_binary(&data[..3]));
+        Ok(())
+    }
+
     #[test]
     fn test_symt_add_twice_symbol() {
         let mut symt = SymbolTable::new();
@@ -0,0 +1,532 @@
+//! Conversion of ARPA backoff language models to FSTs. Equivalent to Kaldi's `arpa2fst`.
+//!
+//! Each history of the model is represented by a state. An n-gram `h w` is an arc labeled `w`
+//! leaving the state of `h` and the backoff weight of a history is an arc leaving its state
+//! towards the state of the history without its first word. The backoff arcs are labeled with
+//! `ArpaConfig::backoff_symbol` (epsilon by default). Highest order n-grams directly lead to the
+//! state of their backoff history and n-grams ending with the end of sentence symbol are final
+//! weights. The start state is the state of the begin of sentence history. As in Kaldi, the states
+//! that are not final and only have a backoff arc are removed.
+//!
+//! The weights are the negated natural logarithms of the probabilities so the FST can be built
+//! over `TropicalWeight` or `LogWeight`.
+//!
+//! # Example
+//!
+//! ```
+//! # use anyhow::Result;
+//! # use rustfst::fst_impls::VectorFst;
+//! # use rustfst::fst_traits::{CoreFst, ExpandedFst, Fst};
+//! # use rustfst::semirings::TropicalWeight;
+//! # use rustfst::utils::arpa::{arpa_to_fst, ArpaConfig};
+//! # fn main() -> Result<()> {
+//! let arpa = "\\data\\
+//! ngram 1=3
+//! ngram 2=1
+//!
+//! \\1-grams:
+//! -99\t<s>\t-0.5
+//! -0.3\thello
+//! -0.5\t</s>
+//!
+//! \\2-grams:
+//! -0.1\t<s> hello
+//!
+//! \\end\\
+//! ";
+//! let fst: VectorFst<TropicalWeight> = arpa_to_fst(arpa, &ArpaConfig::default())?;
+//!
+//! // States : the empty history and `<s>`. The state of `hello` only has a backoff arc so it is
+//! // removed.
+//! assert_eq!(fst.num_states(), 2);
+//! let symt = fst.input_symbols().unwrap();
+//! assert_eq!(symt.get_label("hello"), Some(2));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::f32;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::rc::Rc;
+
+use anyhow::{Context, Result};
+
+use crate::arc::Arc;
+use crate::fst_traits::MutableFst;
+use crate::semirings::Semiring;
+use crate::{Label, StateId, SymbolTable, EPS_LABEL};
+
+/// Struct to configure how an ARPA language model is converted to an FST.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArpaConfig {
+    /// Symbol used to label the backoff arcs (e.g `#0`). Epsilon is used if `None`.
+    pub backoff_symbol: Option<String>,
+    /// Begin of sentence symbol.
+    pub bos_symbol: String,
+    /// End of sentence symbol.
+    pub eos_symbol: String,
+    /// Symbol table to use for the words. If `None`, a new one is created with the words in the
+    /// order they appear in the model, followed by the backoff symbol.
+    pub symbol_table: Option<SymbolTable>,
+}
+
+impl Default for ArpaConfig {
+    fn default() -> Self {
+        Self {
+            backoff_symbol: None,
+            bos_symbol: "<s>".to_string(),
+            eos_symbol: "</s>".to_string(),
+            symbol_table: None,
+        }
+    }
+}
+
+/// Converts the ARPA language model stored in `path` to an FST. See `arpa_to_fst`.
+pub fn read_arpa<W, F, P>(path: P, config: &ArpaConfig) -> Result<F>
+where
+    W: Semiring<Type = f32>,
+    F: MutableFst<W = W>,
+    P: AsRef<Path>,
+{
+    let arpa = read_to_string(path.as_ref())
+        .with_context(|| format!("Can't open ARPA file : {:?}", path.as_ref()))?;
+    arpa_to_fst(&arpa, config)
+}
+
+/// Converts an ARPA language model to an FST. The symbol table of the words is attached to the
+/// FST as input and output symbols.
+pub fn arpa_to_fst<W, F>(arpa: &str, config: &ArpaConfig) -> Result<F>
+where
+    W: Semiring<Type = f32>,
+    F: MutableFst<W = W>,
+{
+    let mut symt = match &config.symbol_table {
+        Some(symt) => symt.clone(),
+        None => SymbolTable::new(),
+    };
+    let ngrams = parse_arpa(arpa, &mut symt, config.symbol_table.is_none())?;
+
+    let backoff_label = match &config.backoff_symbol {
+        None => EPS_LABEL,
+        Some(symbol) if config.symbol_table.is_none() => symt.add_symbol(symbol.as_str()),
+        Some(symbol) => symt
+            .get_label(symbol.as_str())
+            .ok_or_else(|| format_err!("Backoff symbol {:?} is not in the symbol table", symbol))?,
+    };
+    let bos = symt.get_label(config.bos_symbol.as_str());
+    let eos = symt.get_label(config.eos_symbol.as_str());
+
+    let mut compiler = ArpaCompiler::<F> {
+        fst: F::new(),
+        histories: HashMap::new(),
+    };
+    let null_state = compiler.add_state(vec![]);
+    let max_order = ngrams.len();
+    for (idx, ngrams_order) in ngrams.iter().enumerate() {
+        let is_highest = idx + 1 == max_order;
+        for ngram in ngrams_order {
+            compiler.add_ngram(ngram, is_highest, bos, eos, backoff_label)?;
+        }
+    }
+
+    let start = bos
+        .and_then(|bos| compiler.histories.get(&vec![bos]).cloned())
+        .unwrap_or(null_state);
+    let mut fst = compiler.fst;
+    fst.set_start(start)?;
+    remove_redundant_states(&mut fst, backoff_label)?;
+
+    let symt = Rc::new(symt);
+    fst.set_input_symbols(Rc::clone(&symt));
+    fst.set_output_symbols(symt);
+    Ok(fst)
+}
+
+struct NGram {
+    words: Vec<Label>,
+    logprob: f32,
+    backoff: Option<f32>,
+}
+
+struct ArpaCompiler<F> {
+    fst: F,
+    histories: HashMap<Vec<Label>, StateId>,
+}
+
+impl<W: Semiring<Type = f32>, F: MutableFst<W = W>> ArpaCompiler<F> {
+    fn add_state(&mut self, history: Vec<Label>) -> StateId {
+        let state = self.fst.add_state();
+        self.histories.insert(history, state);
+        state
+    }
+
+    /// State of the longest suffix of `history` present in the model.
+    fn find_backoff_state(&self, history: &[Label]) -> StateId {
+        (0..=history.len())
+            .filter_map(|idx| self.histories.get(&history[idx..]))
+            .next()
+            .cloned()
+            // The empty history always has a state.
+            .unwrap()
+    }
+
+    fn add_ngram(
+        &mut self,
+        ngram: &NGram,
+        is_highest: bool,
+        bos: Option<Label>,
+        eos: Option<Label>,
+        backoff_label: Label,
+    ) -> Result<()> {
+        let words = &ngram.words;
+        let (word, history) = words.split_last().unwrap();
+        let weight = W::new(-ngram.logprob * f32::consts::LN_10);
+        ensure!(
+            !history.iter().any(|w| Some(*w) == eos),
+            "Invalid n-gram : the end of sentence symbol can only be the last word"
+        );
+        ensure!(
+            !words.iter().skip(1).any(|w| Some(*w) == bos),
+            "Invalid n-gram : the begin of sentence symbol can only be the first word"
+        );
+        let source = *self.histories.get(history).ok_or_else(|| {
+            format_err!(
+                "Invalid n-gram : its history is not in the model : {:?}",
+                history
+            )
+        })?;
+
+        if Some(*word) == eos {
+            return self.fst.set_final(source, weight);
+        }
+
+        let dest = if is_highest {
+            self.find_backoff_state(&words[1..])
+        } else {
+            let backoff_state = self.find_backoff_state(&words[1..]);
+            let dest = self.add_state(words.clone());
+            let backoff_weight = W::new(-ngram.backoff.unwrap_or(0.0) * f32::consts::LN_10);
+            self.fst.add_arc(
+                dest,
+                Arc::new(backoff_label, backoff_label, backoff_weight, backoff_state),
+            )?;
+            dest
+        };
+
+        // The begin of sentence is never accepted : it only defines the start state.
+        if Some(*word) != bos {
+            self.fst
+                .add_arc(source, Arc::new(*word, *word, weight, dest))?;
+        }
+        Ok(())
+    }
+}
+
+/// Removes the states whose only arc is a backoff arc and that are not final. The arcs leading to
+/// them are redirected to their backoff state.
+fn remove_redundant_states<F: MutableFst>(fst: &mut F, backoff_label: Label) -> Result<()> {
+    let start = fst.start();
+    let mut redundant = HashMap::new();
+    for state in fst.states_iter() {
+        if Some(state) == start || fst.is_final(state)? || fst.num_arcs(state)? != 1 {
+            continue;
+        }
+        let arc = fst.arcs_iter(state)?.next().unwrap();
+        if arc.ilabel == backoff_label {
+            redundant.insert(state, (arc.weight.clone(), arc.nextstate));
+        }
+    }
+    if redundant.is_empty() {
+        return Ok(());
+    }
+
+    for state in 0..fst.num_states() {
+        for arc in fst.arcs_iter_mut(state)? {
+            while let Some((weight, nextstate)) = redundant.get(&arc.nextstate) {
+                arc.weight.times_assign(weight)?;
+                arc.nextstate = *nextstate;
+            }
+        }
+    }
+    fst.del_states(redundant.keys().cloned())
+}
+
+/// Parses the n-grams of the model, grouped by order.
+fn parse_arpa(arpa: &str, symt: &mut SymbolTable, add_words: bool) -> Result<Vec<Vec<NGram>>> {
+    let mut lines = arpa
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    // Header : number of n-grams of each order.
+    lines
+        .by_ref()
+        .find(|(_, line)| *line == "\\data\\")
+        .ok_or_else(|| format_err!("Invalid ARPA file : missing \\data\\ section"))?;
+    let mut counts = vec![];
+    let mut section = None;
+    for (line_idx, line) in lines.by_ref() {
+        if line.starts_with('\\') {
+            section = Some((line_idx, line));
+            break;
+        }
+        let count = line
+            .strip_prefix("ngram ")
+            .and_then(|l| {
+                let mut parts = l.splitn(2, '=');
+                let order = parts.next()?.trim().parse::<usize>().ok()?;
+                let count = parts.next()?.trim().parse::<usize>().ok()?;
+                Some((order, count))
+            })
+            .ok_or_else(|| format_err!("Invalid ARPA header at line {} : {:?}", line_idx, line))?;
+        ensure!(
+            count.0 == counts.len() + 1,
+            "Invalid ARPA header at line {} : unexpected order {}",
+            line_idx,
+            count.0
+        );
+        counts.push(count.1);
+    }
+    ensure!(!counts.is_empty(), "Invalid ARPA file : no n-gram counts");
+
+    let mut ngrams = vec![];
+    for (idx, count) in counts.iter().enumerate() {
+        let order = idx + 1;
+        let (line_idx, line) =
+            section.ok_or_else(|| format_err!("Invalid ARPA file : missing {}-grams", order))?;
+        ensure!(
+            line == format!("\\{}-grams:", order),
+            "Invalid ARPA file at line {} : expected \\{}-grams: section but found {:?}",
+            line_idx,
+            order,
+            line
+        );
+        section = None;
+
+        let mut ngrams_order = vec![];
+        for (line_idx, line) in lines.by_ref() {
+            if line.starts_with('\\') {
+                section = Some((line_idx, line));
+                break;
+            }
+            let ngram = parse_ngram(line, order, symt, add_words)
+                .with_context(|| format!("Invalid ARPA file at line {}", line_idx))?;
+            ngrams_order.push(ngram);
+        }
+        ensure!(
+            ngrams_order.len() == *count,
+            "Invalid ARPA file : {} {}-grams expected but {} found",
+            count,
+            order,
+            ngrams_order.len()
+        );
+        ngrams.push(ngrams_order);
+    }
+
+    match section {
+        Some((_, "\\end\\")) => Ok(ngrams),
+        Some((line_idx, line)) => bail!(
+            "Invalid ARPA file at line {} : expected \\end\\ but found {:?}",
+            line_idx,
+            line
+        ),
+        None => bail!("Invalid ARPA file : missing \\end\\"),
+    }
+}
+
+fn parse_ngram(line: &str, order: usize, symt: &mut SymbolTable, add_words: bool) -> Result<NGram> {
+    let fields: Vec<_> = line.split_whitespace().collect();
+    ensure!(
+        fields.len() == order + 1 || fields.len() == order + 2,
+        "Invalid {}-gram : {:?}",
+        order,
+        line
+    );
+    let logprob = fields[0].parse()?;
+    let words = fields[1..=order]
+        .iter()
+        .map(|word| match symt.get_label(*word) {
+            Some(label) => Ok(label),
+            None if add_words => Ok(symt.add_symbol(*word)),
+            None => bail!("Word {:?} is not in the symbol table", word),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let backoff = fields.get(order + 1).map(|b| b.parse()).transpose()?;
+    Ok(NGram {
+        words,
+        logprob,
+        backoff,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst, Fst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+
+    static ARPA: &str = "
+\\data\\
+ngram 1=4
+ngram 2=4
+ngram 3=1
+
+\\1-grams:
+-1.0\t</s>
+-99\t<s>\t-0.5
+-0.5\ta\t-0.25
+-0.75\tb\t-0.3
+
+\\2-grams:
+-0.2\t<s> a\t-0.1
+-0.4\ta b
+-0.6\tb a
+-0.3\ta </s>
+
+\\3-grams:
+-0.05\t<s> a b
+
+\\end\\
+";
+
+    fn cost(log10: f32) -> f32 {
+        -log10 * f32::consts::LN_10
+    }
+
+    fn find_arc(
+        fst: &VectorFst<TropicalWeight>,
+        state: StateId,
+        label: Label,
+    ) -> Result<Arc<TropicalWeight>> {
+        fst.arcs_iter(state)?
+            .find(|arc| arc.ilabel == label)
+            .cloned()
+            .ok_or_else(|| format_err!("Missing arc"))
+    }
+
+    #[test]
+    fn test_arpa_to_fst() -> Result<()> {
+        let fst: VectorFst<TropicalWeight> = arpa_to_fst(ARPA, &ArpaConfig::default())?;
+        let symt = fst.input_symbols().unwrap();
+        assert_eq!(symt.len(), 5);
+        let (a, b) = (symt.get_label("a").unwrap(), symt.get_label("b").unwrap());
+
+        // States : null, <s>, a, b, <s> a. `a b` and `b a` are removed as they only have a backoff
+        // arc.
+        assert_eq!(fst.num_states(), 5);
+        let null = 0;
+        let start = fst.start().unwrap();
+        assert_ne!(start, null);
+        assert!(fst.is_acceptor());
+
+        // Backoff of <s>.
+        let backoff = find_arc(&fst, start, EPS_LABEL)?;
+        assert_eq!(backoff.nextstate, null);
+        assert_eq!(backoff.weight, TropicalWeight::new(cost(-0.5)));
+
+        // <s> a -> <s> a
+        let arc_a = find_arc(&fst, start, a)?;
+        assert_eq!(arc_a.weight, TropicalWeight::new(cost(-0.2)));
+        let state_sa = arc_a.nextstate;
+        assert_eq!(
+            find_arc(&fst, state_sa, EPS_LABEL)?.weight,
+            TropicalWeight::new(cost(-0.1))
+        );
+
+        // Highest order : <s> a b -> b.
+        let arc_b = find_arc(&fst, state_sa, b)?;
+        assert_eq!(arc_b.weight, TropicalWeight::new(cost(-0.05)));
+        let state_b = find_arc(&fst, null, b)?.nextstate;
+        assert_eq!(arc_b.nextstate, state_b);
+
+        // a b -> b as the state of `a b` only has a backoff arc.
+        let state_a = find_arc(&fst, null, a)?.nextstate;
+        let arc_ab = find_arc(&fst, state_a, b)?;
+        assert_eq!(arc_ab.nextstate, state_b);
+        assert_eq!(arc_ab.weight, TropicalWeight::new(cost(-0.4)));
+        assert_eq!(find_arc(&fst, state_b, a)?.nextstate, state_a);
+
+        // Final weights.
+        assert_eq!(
+            fst.final_weight(null)?,
+            Some(&TropicalWeight::new(cost(-1.0)))
+        );
+        assert_eq!(
+            fst.final_weight(state_a)?,
+            Some(&TropicalWeight::new(cost(-0.3)))
+        );
+        assert_eq!(fst.final_weight(start)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arpa_to_fst_backoff_symbol() -> Result<()> {
+        let config = ArpaConfig {
+            backoff_symbol: Some("#0".to_string()),
+            ..ArpaConfig::default()
+        };
+        let fst: VectorFst<LogWeight> = arpa_to_fst(ARPA, &config)?;
+        let symt = fst.input_symbols().unwrap();
+        assert_eq!(symt.get_label("#0"), Some(5));
+        let start = fst.start().unwrap();
+        assert!(fst.arcs_iter(start)?.any(|arc| arc.ilabel == 5));
+        assert!(fst.arcs_iter(start)?.all(|arc| arc.ilabel != EPS_LABEL));
+        Ok(())
+    }
+
+    #[test]
+    fn test_arpa_to_fst_symbol_table() -> Result<()> {
+        let mut symt = SymbolTable::new();
+        symt.add_symbols(vec!["#0", "b", "a", "<s>", "</s>"]);
+        let config = ArpaConfig {
+            backoff_symbol: Some("#0".to_string()),
+            symbol_table: Some(symt.clone()),
+            ..ArpaConfig::default()
+        };
+        let fst: VectorFst<TropicalWeight> = arpa_to_fst(ARPA, &config)?;
+        assert_eq!(fst.input_symbols().as_deref(), Some(&symt));
+
+        let mut symt_missing = SymbolTable::new();
+        symt_missing.add_symbols(vec!["#0", "b", "<s>", "</s>"]);
+        let config = ArpaConfig {
+            symbol_table: Some(symt_missing.clone()),
+            ..config
+        };
+        assert!(arpa_to_fst::<_, VectorFst<TropicalWeight>>(ARPA, &config).is_err());
+        symt_missing.add_symbol("a");
+        let config = ArpaConfig {
+            symbol_table: Some(symt_missing),
+            backoff_symbol: Some("#1".to_string()),
+            ..config
+        };
+        assert!(arpa_to_fst::<_, VectorFst<TropicalWeight>>(ARPA, &config).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_arpa_invalid() {
+        let parse =
+            |arpa: &str| arpa_to_fst::<_, VectorFst<TropicalWeight>>(arpa, &ArpaConfig::default());
+        assert!(parse("").is_err());
+        // Wrong count.
+        assert!(parse("\\data\\\nngram 1=2\n\\1-grams:\n-1\ta\n\\end\\\n").is_err());
+        assert!(parse(&format!(
+            "\\data\\\nngram 1={}\n\\1-grams:\n-1\ta\n\\end\\\n",
+            usize::MAX
+        ))
+        .is_err());
+        // Missing end.
+        assert!(parse("\\data\\\nngram 1=1\n\\1-grams:\n-1\ta\n").is_err());
+        // Missing history.
+        assert!(parse(
+            "\\data\\\nngram 1=1\nngram 2=1\n\\1-grams:\n-1\ta\n\\2-grams:\n-1\tb a\n\\end\\\n"
+        )
+        .is_err());
+        assert!(parse("\\data\\\nngram 1=1\n\\1-grams:\n-1\ta\n\\end\\\n").is_ok());
+    }
+}
@@ -0,0 +1,404 @@
+use std::fmt::Write;
+use std::str::from_utf8;
+
+use anyhow::{Context, Result};
+
+use crate::arc::Arc;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{ExpandedFst, Fst, MutableFst, SerializableFst};
+use crate::semirings::{CompactLatticeWeight, LatticeWeight, Semiring, SerializableSemiring};
+use crate::{Label, StateId};
+
+/// Parses lattices stored in Kaldi's text archive format (e.g the output of
+/// `lattice-copy ark:in.lats ark,t:-`).
+///
+/// Each lattice starts with a line containing its key and ends with an empty line. The arcs are
+/// written `src dst ilabel olabel [weight]` and the final states `state [weight]`, a missing
+/// weight meaning `LatticeWeight::one()`. The start state is the first state of the lattice.
+///
+/// # Example
+///
+/// ```
+/// # use anyhow::Result;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst};
+/// # use rustfst::semirings::{LatticeWeight, Semiring};
+/// # use rustfst::utils::kaldi_lattices_from_text;
+/// # fn main() -> Result<()> {
+/// let text = "utt1 \n0\t1\t5\t7\t1.5,2\n1\n\n";
+/// let lattices: Vec<(String, VectorFst<LatticeWeight>)> = kaldi_lattices_from_text(text)?;
+///
+/// assert_eq!(lattices.len(), 1);
+/// assert_eq!(lattices[0].0, "utt1");
+/// assert_eq!(lattices[0].1.num_states(), 2);
+/// assert_eq!(lattices[0].1.final_weight(1)?, Some(&LatticeWeight::one()));
+/// # Ok(())
+/// # }
+/// ```
+pub fn kaldi_lattices_from_text<F: MutableFst<W = LatticeWeight>>(
+    text: &str,
+) -> Result<Vec<(String, F)>> {
+    parse_kaldi_archive(text, false)
+}
+
+/// Parses compact lattices stored in Kaldi's text archive format. Same format as
+/// `kaldi_lattices_from_text` except that the arcs are written `src dst label [weight]`, the
+/// weights being `CompactLatticeWeight`.
+pub fn kaldi_compact_lattices_from_text<F: MutableFst<W = CompactLatticeWeight>>(
+    text: &str,
+) -> Result<Vec<(String, F)>> {
+    parse_kaldi_archive(text, true)
+}
+
+/// Writes lattices in Kaldi's text archive format. The output can be read by Kaldi with
+/// `ark,t:` rspecifiers and by `kaldi_lattices_from_text`.
+pub fn kaldi_lattices_to_text<F: ExpandedFst<W = LatticeWeight>>(
+    lattices: &[(String, F)],
+) -> Result<String> {
+    write_kaldi_archive(lattices, false)
+}
+
+/// Writes compact lattices in Kaldi's text archive format. The output can be read by Kaldi with
+/// `ark,t:` rspecifiers and by `kaldi_compact_lattices_from_text`.
+pub fn kaldi_compact_lattices_to_text<F: ExpandedFst<W = CompactLatticeWeight>>(
+    lattices: &[(String, F)],
+) -> Result<String> {
+    write_kaldi_archive(lattices, true)
+}
+
+/// Parses lattices stored in Kaldi's binary archive format (e.g the output of
+/// `lattice-copy ark:in.lats ark:-`).
+///
+/// Each lattice is written as its key followed by a space, the `\0B` binary marker and the
+/// lattice in OpenFST's binary `vector` format. Archives mixing text and binary entries are not
+/// supported.
+pub fn kaldi_lattices_from_binary(data: &[u8]) -> Result<Vec<(String, VectorFst<LatticeWeight>)>> {
+    parse_kaldi_binary_archive(data)
+}
+
+/// Parses compact lattices stored in Kaldi's binary archive format. Same format as
+/// `kaldi_lattices_from_binary`, the weights being `CompactLatticeWeight`.
+pub fn kaldi_compact_lattices_from_binary(
+    data: &[u8],
+) -> Result<Vec<(String, VectorFst<CompactLatticeWeight>)>> {
+    parse_kaldi_binary_archive(data)
+}
+
+/// Writes lattices in Kaldi's binary archive format. The output can be read by Kaldi with
+/// `ark:` rspecifiers and by `kaldi_lattices_from_binary`.
+pub fn kaldi_lattices_to_binary(
+    lattices: &[(String, VectorFst<LatticeWeight>)],
+) -> Result<Vec<u8>> {
+    write_kaldi_binary_archive(lattices, false)
+}
+
+/// Writes compact lattices in Kaldi's binary archive format. The output can be read by Kaldi
+/// with `ark:` rspecifiers and by `kaldi_compact_lattices_from_binary`.
+pub fn kaldi_compact_lattices_to_binary(
+    lattices: &[(String, VectorFst<CompactLatticeWeight>)],
+) -> Result<Vec<u8>> {
+    write_kaldi_binary_archive(lattices, true)
+}
+
+fn parse_kaldi_binary_archive<W: SerializableSemiring + 'static>(
+    mut data: &[u8],
+) -> Result<Vec<(String, VectorFst<W>)>> {
+    let mut lattices = vec![];
+    while !data.is_empty() {
+        let key_end = data
+            .iter()
+            .position(|b| *b == b' ')
+            .ok_or_else(|| format_err!("Missing space after Kaldi lattice key"))?;
+        let key = from_utf8(&data[..key_end])?;
+        ensure!(
+            !key.is_empty() && !key.contains(char::is_whitespace),
+            "Invalid Kaldi lattice key : {:?}",
+            key
+        );
+        let rest = &data[key_end + 1..];
+        ensure!(
+            rest.starts_with(b"\0B"),
+            "Kaldi lattice {:?} is not in binary format",
+            key
+        );
+        let (rest, fst) = VectorFst::<W>::parse_binary(&rest[2..])
+            .map_err(|_| format_err!("Error while parsing binary Kaldi lattice {:?}", key))?;
+        lattices.push((key.to_string(), fst));
+        data = rest;
+    }
+    Ok(lattices)
+}
+
+fn write_kaldi_binary_archive<W: SerializableSemiring + 'static>(
+    lattices: &[(String, VectorFst<W>)],
+    acceptor: bool,
+) -> Result<Vec<u8>> {
+    let mut res = vec![];
+    for (key, fst) in lattices {
+        ensure!(
+            !acceptor || fst.is_acceptor(),
+            "Compact lattices must be acceptors"
+        );
+        res.extend_from_slice(key.as_bytes());
+        res.extend_from_slice(b" \0B");
+        fst.write_binary(&mut res)?;
+    }
+    Ok(res)
+}
+
+fn parse_kaldi_archive<F: MutableFst>(text: &str, acceptor: bool) -> Result<Vec<(String, F)>>
+where
+    F::W: SerializableSemiring,
+{
+    let mut lattices = vec![];
+    let mut lines = text.lines().enumerate();
+    while let Some((_, line)) = lines.next() {
+        let key = line.trim();
+        if key.is_empty() {
+            continue;
+        }
+        ensure!(
+            !key.contains(char::is_whitespace),
+            "Invalid Kaldi lattice key : {:?}",
+            key
+        );
+
+        let mut fst = F::new();
+        for (line_idx, line) in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+            parse_kaldi_line(&mut fst, line, acceptor)
+                .with_context(|| format!("Error at line {} of the Kaldi archive", line_idx + 1))?;
+        }
+        lattices.push((key.to_string(), fst));
+    }
+    Ok(lattices)
+}
+
+fn parse_kaldi_line<F: MutableFst>(fst: &mut F, line: &str, acceptor: bool) -> Result<()>
+where
+    F::W: SerializableSemiring,
+{
+    let fields: Vec<_> = line.split_whitespace().collect();
+    let num_labels = if acceptor { 1 } else { 2 };
+    let (state, arc) = match fields.len() {
+        1 | 2 => (fields[0], None),
+        n if n == 2 + num_labels || n == 3 + num_labels => (fields[0], Some(&fields[1..])),
+        _ => bail!("Invalid Kaldi lattice line : {:?}", line),
+    };
+
+    let state = add_states_until(fst, state.parse()?);
+    match arc {
+        None => {
+            let weight = fields.get(1).map(|w| parse_weight(w)).transpose()?;
+            fst.set_final(state, weight.unwrap_or_else(F::W::one))?;
+        }
+        Some(fields) => {
+            let nextstate = add_states_until(fst, fields[0].parse()?);
+            let ilabel: Label = fields[1].parse()?;
+            let olabel: Label = if acceptor { ilabel } else { fields[2].parse()? };
+            let weight = fields
+                .get(1 + num_labels)
+                .map(|w| parse_weight(w))
+                .transpose()?;
+            fst.add_arc(
+                state,
+                Arc::new(ilabel, olabel, weight.unwrap_or_else(F::W::one), nextstate),
+            )?;
+        }
+    };
+    Ok(())
+}
+
+/// Adds states to the FST until `state` exists. The first state added is the start state.
+fn add_states_until<F: MutableFst>(fst: &mut F, state: StateId) -> StateId {
+    if state >= fst.num_states() {
+        fst.add_states(state + 1 - fst.num_states());
+    }
+    if fst.start().is_none() {
+        // Can't fail as the state has just been added
+        fst.set_start(state).unwrap();
+    }
+    state
+}
+
+fn parse_weight<W: SerializableSemiring>(s: &str) -> Result<W> {
+    match W::parse_text(s) {
+        Ok(("", weight)) => Ok(weight),
+        _ => bail!("Invalid Kaldi lattice weight : {:?}", s),
+    }
+}
+
+fn write_kaldi_archive<F: ExpandedFst>(lattices: &[(String, F)], acceptor: bool) -> Result<String>
+where
+    F::W: SerializableSemiring,
+{
+    let mut res = String::new();
+    for (key, fst) in lattices {
+        writeln!(res, "{} ", key)?;
+        if let Some(start) = fst.start() {
+            // The start state is written first as it is the way Kaldi finds it.
+            let states = Some(start)
+                .into_iter()
+                .chain(fst.states_iter().filter(|s| *s != start));
+            for state in states {
+                for arc in fst.arcs_iter(state)? {
+                    write!(res, "{}\t{}\t{}", state, arc.nextstate, arc.ilabel)?;
+                    if !acceptor {
+                        write!(res, "\t{}", arc.olabel)?;
+                    } else {
+                        ensure!(
+                            arc.ilabel == arc.olabel,
+                            "Compact lattices must be acceptors"
+                        );
+                    }
+                    if !arc.weight.is_one() {
+                        write!(res, "\t{}", arc.weight)?;
+                    }
+                    writeln!(res)?;
+                }
+                if let Some(final_weight) = fst.final_weight(state)? {
+                    if final_weight.is_one() {
+                        writeln!(res, "{}", state)?;
+                    } else {
+                        writeln!(res, "{}\t{}", state, final_weight)?;
+                    }
+                }
+            }
+        }
+        writeln!(res)?;
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::{
+        determinize, push, shortest_path, DeterminizeType, PushType, ReweightType,
+    };
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst, Fst};
+
+    static LATTICES: &str = "utt1 \n\
+                             0\t1\t5\t7\t1.5,2\n\
+                             0\t1\t5\t7\t0.5,1\n\
+                             0\t2\t6\t0\t1,1\n\
+                             1\t3\t0\t0\n\
+                             2\t3\t0\t0\t0.5,0\n\
+                             3\t0.25,0\n\
+                             \n\
+                             utt2 \n\
+                             \n";
+
+    static COMPACT_LATTICES: &str = "utt1 \n\
+                                     0\t1\t7\t1.5,2,1_2_3\n\
+                                     0\t1\t7\t0.5,1,4_5\n\
+                                     0\t1\t8\t1,1,6\n\
+                                     1\t0.25,0,\n\
+                                     \n";
+
+    #[test]
+    fn test_kaldi_lattices_text() -> Result<()> {
+        let lattices: Vec<(String, VectorFst<LatticeWeight>)> = kaldi_lattices_from_text(LATTICES)?;
+        assert_eq!(lattices.len(), 2);
+        assert_eq!(lattices[1].0, "utt2");
+        assert_eq!(lattices[1].1.num_states(), 0);
+
+        let fst = &lattices[0].1;
+        assert_eq!(fst.start(), Some(0));
+        assert_eq!(fst.num_states(), 4);
+        assert_eq!(fst.num_arcs(0)?, 3);
+        assert_eq!(fst.final_weight(3)?, Some(&LatticeWeight::new((0.25, 0.0))));
+        let arc = fst.arcs_iter(1)?.next().unwrap();
+        assert_eq!(arc.weight, LatticeWeight::one());
+
+        let text = kaldi_lattices_to_text(&lattices)?;
+        let lattices_2: Vec<(String, VectorFst<LatticeWeight>)> = kaldi_lattices_from_text(&text)?;
+        assert_eq!(lattices_2, lattices);
+        Ok(())
+    }
+
+    #[test]
+    fn test_kaldi_compact_lattices_text() -> Result<()> {
+        let lattices: Vec<(String, VectorFst<CompactLatticeWeight>)> =
+            kaldi_compact_lattices_from_text(COMPACT_LATTICES)?;
+        assert_eq!(lattices.len(), 1);
+        let fst = &lattices[0].1;
+        assert!(fst.is_acceptor());
+        assert_eq!(
+            fst.final_weight(1)?,
+            Some(&CompactLatticeWeight::new((
+                LatticeWeight::new((0.25, 0.0)),
+                vec![]
+            )))
+        );
+
+        let text = kaldi_compact_lattices_to_text(&lattices)?;
+        assert_eq!(text, COMPACT_LATTICES);
+        Ok(())
+    }
+
+    #[test]
+    fn test_kaldi_lattices_binary() -> Result<()> {
+        let lattices: Vec<(String, VectorFst<LatticeWeight>)> = kaldi_lattices_from_text(LATTICES)?;
+        let data = kaldi_lattices_to_binary(&lattices)?;
+        assert!(data.starts_with(b"utt1 \0B"));
+        assert!(data.windows(b"lattice4".len()).any(|w| w == b"lattice4"));
+        assert_eq!(kaldi_lattices_from_binary(&data)?, lattices);
+
+        let lattices: Vec<(String, VectorFst<CompactLatticeWeight>)> =
+            kaldi_compact_lattices_from_text(COMPACT_LATTICES)?;
+        let data = kaldi_compact_lattices_to_binary(&lattices)?;
+        assert_eq!(kaldi_compact_lattices_from_binary(&data)?, lattices);
+        assert!(kaldi_lattices_from_binary(&data).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_kaldi_lattices_binary_invalid() -> Result<()> {
+        assert!(kaldi_lattices_from_binary(b"utt1").is_err());
+        assert!(kaldi_lattices_from_binary(b"utt1 \n0\t1\t2\t3\n\n").is_err());
+
+        let lattices: Vec<(String, VectorFst<LatticeWeight>)> = kaldi_lattices_from_text(LATTICES)?;
+        let data = kaldi_lattices_to_binary(&lattices)?;
+        assert!(kaldi_lattices_from_binary(&data[..data.len() - 1]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_kaldi_lattices_invalid() {
+        let parse = |text: &str| kaldi_lattices_from_text::<VectorFst<LatticeWeight>>(text);
+        assert!(parse("utt1 \n0\t1\t2\n\n").is_err());
+        assert!(parse("utt1 \n0\t1\t2\t3\t1.5\n\n").is_err());
+        assert!(parse("utt1 \na\n\n").is_err());
+        assert!(
+            kaldi_compact_lattices_from_text::<VectorFst<CompactLatticeWeight>>(
+                "utt1 \n0\t1\t2\t3\t1,1,\n\n"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_kaldi_lattices_algorithms() -> Result<()> {
+        let lattices: Vec<(String, VectorFst<LatticeWeight>)> = kaldi_lattices_from_text(LATTICES)?;
+        let fst = &lattices[0].1;
+
+        let fst_det: VectorFst<LatticeWeight> =
+            determinize(fst, DeterminizeType::DeterminizeFunctional)?;
+        assert_eq!(fst_det.num_arcs(0)?, 2);
+
+        let best: VectorFst<LatticeWeight> = shortest_path(fst, 1, false)?;
+        let text = kaldi_lattices_to_text(&[("best".to_string(), best)])?;
+        assert!(text.contains("0.5,1"));
+        assert!(!text.contains("1.5,2"));
+
+        let pushed: VectorFst<LatticeWeight> =
+            push(fst, ReweightType::ReweightToInitial, PushType::PUSH_WEIGHTS)?;
+        assert_eq!(pushed.num_states(), fst.num_states());
+        Ok(())
+    }
+}
@@ -1,5 +1,14 @@
+pub mod arpa;
+
 mod fst_to_labels;
+mod kaldi_lattice;
 mod labels_to_fst;
 
 pub use self::fst_to_labels::decode_linear_fst;
+pub use self::kaldi_lattice::{
+    kaldi_compact_lattices_from_binary, kaldi_compact_lattices_from_text,
+    kaldi_compact_lattices_to_binary, kaldi_compact_lattices_to_text, kaldi_lattices_from_binary,
+    kaldi_lattices_from_text, kaldi_lattices_to_binary, kaldi_lattices_to_text,
+};
 pub use self::labels_to_fst::{acceptor, transducer};
+pub use crate::parsers::bin_fst::fst_header::peek_fst_types;